@@ -1,13 +1,16 @@
 use crate::CanResult;
 
 /// Trait representing a CAN interface
+///
+/// The methods take a shared reference so frames can be transmitted while another task is waiting
+/// for a frame to be received.
 pub trait Can<const MAX_PAYLOAD_SIZE: usize> {
     /// Associated frame type.
     type Frame: crate::Frame<MAX_PAYLOAD_SIZE>;
 
     /// Puts a frame in the transmit buffer. Blocks until space is available in the transmit buffer.
-    async fn transmit(&mut self, frame: &Self::Frame) -> CanResult<()>;
+    async fn transmit(&self, frame: &Self::Frame) -> CanResult<()>;
 
     /// Blocks until a frame is received or an error occurres.
-    async fn receive(&mut self) -> CanResult<Self::Frame>;
+    async fn receive(&self) -> CanResult<Self::Frame>;
 }
@@ -1,4 +1,5 @@
 use core::fmt::{Display, Formatter, Result as FmtResult};
+use cyphal::{CyphalError, TransportErrorKind};
 
 /// Represents a CAN Error
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -58,5 +59,42 @@ impl Display for CanError {
     }
 }
 
+impl From<CanError> for CyphalError {
+    fn from(value: CanError) -> Self {
+        match value {
+            CanError::Overrun => CyphalError::BufferFull,
+            CanError::InvalidId => CyphalError::OutOfRange,
+            CanError::Bit => CyphalError::Transport(TransportErrorKind::Bit),
+            CanError::Stuff => CyphalError::Transport(TransportErrorKind::Stuff),
+            CanError::Crc => CyphalError::Transport(TransportErrorKind::FrameCrc),
+            CanError::Form => CyphalError::Transport(TransportErrorKind::Form),
+            CanError::Acknowledge => CyphalError::Transport(TransportErrorKind::Acknowledge),
+            CanError::Other => CyphalError::Transport(TransportErrorKind::Other),
+        }
+    }
+}
+
 /// The result of a Cyphal CAN operation.  On failure, a `CANError` will be included.
 pub type CanResult<T> = Result<T, CanError>;
+
+#[cfg(test)]
+mod test {
+    use crate::CanError;
+    use cyphal::{CyphalError, TransportErrorKind};
+
+    #[test]
+    fn test_into_cyphal_error() {
+        assert_eq!(
+            CyphalError::from(CanError::Overrun),
+            CyphalError::BufferFull
+        );
+        assert_eq!(
+            CyphalError::from(CanError::Stuff),
+            CyphalError::Transport(TransportErrorKind::Stuff)
+        );
+        assert_eq!(
+            CyphalError::from(CanError::Acknowledge),
+            CyphalError::Transport(TransportErrorKind::Acknowledge)
+        );
+    }
+}
@@ -18,44 +18,36 @@ pub trait Frame<const MAX_PAYLOAD_SIZE: usize>: Sized {
 
     /// Returns true if it's a Single Frame Transfer
     fn is_single_trame_transfer(&self) -> bool {
-        self.dlc() == MAX_PAYLOAD_SIZE && (self.data()[MAX_PAYLOAD_SIZE - 1] & 0xE0) == 0xE0
+        self.dlc() > 0 && (self.tail_byte() & 0xE0) == 0xE0
     }
 
     /// Returns true if it's the start of a Transfer
     fn is_start_of_transfer(&self) -> bool {
-        if self.is_single_trame_transfer() {
-            return true;
-        }
-
-        self.data()[self.dlc() - 1] & 0x80 != 0
+        self.dlc() > 0 && self.tail_byte() & 0x80 != 0
     }
 
     /// Returns true if it's the end of a Transfer
     fn is_end_of_transfer(&self) -> bool {
-        if self.is_single_trame_transfer() {
-            return true;
-        }
-
-        self.data()[self.dlc() - 1] & 0x40 != 0
+        self.dlc() > 0 && self.tail_byte() & 0x40 != 0
     }
 
     /// Returns true if the Toggle bit is set
     fn is_toggle_bit_set(&self) -> bool {
-        if self.is_single_trame_transfer() {
-            return true;
-        }
-
-        self.data()[self.dlc() - 1] & 0x20 != 0
+        self.dlc() > 0 && self.tail_byte() & 0x20 != 0
     }
 
     /// Returns the Transfer ID
     fn transfer(&self) -> CanTransferId {
-        let value = if self.is_single_trame_transfer() {
-            self.data()[MAX_PAYLOAD_SIZE - 1] & 0x0F
-        } else {
-            self.data()[self.dlc() - 1] & 0xF
-        };
+        CanTransferId::new(self.tail_byte() & 0x1F).unwrap()
+    }
 
-        CanTransferId::new(value).unwrap()
+    /// Returns the tail byte, which is the last byte of the frame data.
+    ///
+    /// Frames without data don't have a tail byte, in which case 0 is returned.
+    fn tail_byte(&self) -> u8 {
+        match self.dlc() {
+            0 => 0,
+            dlc => self.data()[dlc - 1],
+        }
     }
 }
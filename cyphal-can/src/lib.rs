@@ -21,6 +21,9 @@ pub use frame::Frame;
 mod message_can_id;
 pub use message_can_id::MessageCanId;
 
+mod rejected_transfers;
+pub use rejected_transfers::RejectedTransfers;
+
 mod service_can_id;
 pub use service_can_id::ServiceCanId;
 
@@ -1,6 +1,9 @@
 use crate::{CanError, CanResult};
+use crc::Crc;
 use cyphal::{NodeId, Priority, SubjectId};
 
+const CRC16: Crc<u16> = Crc::<u16>::new(&crc::CRC_16_IBM_3740);
+
 /// Represents an extended CAN ID used for messages
 #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
 pub struct MessageCanId {
@@ -11,7 +14,10 @@ pub struct MessageCanId {
 }
 
 impl MessageCanId {
-    /// Constructs a new message CAN ID
+    /// Constructs a new message CAN ID.
+    ///
+    /// Anonymous CAN IDs get the pseudo-ID of an empty payload, use `new_anonymous` to derive it
+    /// from the actual payload.
     pub fn new(priority: Priority, subject: SubjectId, source: Option<NodeId>) -> CanResult<Self> {
         match source {
             Some(s) => Ok(MessageCanId {
@@ -20,16 +26,24 @@ impl MessageCanId {
                 subject,
                 source: s,
             }),
-            None => Ok(MessageCanId {
-                priority,
-                anonymous: true,
-                subject,
-                //FIXME: generate a pseudorandom pseudo-ID value for source
-                source: 0.try_into().unwrap(),
-            }),
+            None => Self::new_anonymous(priority, subject, &[]),
         }
     }
 
+    /// Constructs a new message CAN ID for an anonymous transfer.
+    ///
+    /// The source is replaced by a pseudo-ID made of the lower 7 bits of the CRC-16/CCITT-FALSE of
+    /// the payload, so that anonymous nodes publishing different payloads at the same time are
+    /// unlikely to use the same CAN ID.
+    pub fn new_anonymous(priority: Priority, subject: SubjectId, data: &[u8]) -> CanResult<Self> {
+        Ok(MessageCanId {
+            priority,
+            anonymous: true,
+            subject,
+            source: CRC16.checksum(data) & 0x7F,
+        })
+    }
+
     /// Returns the priority of the message
     pub fn priority(&self) -> Priority {
         self.priority
@@ -47,7 +61,7 @@ impl MessageCanId {
 
     /// Returns the Node ID from where the message originates.
     ///
-    /// Note: Anonymous messages contain a generated pseudorandom pseudo-ID value
+    /// Note: Anonymous messages contain a pseudo-ID derived from their payload
     pub fn source(&self) -> NodeId {
         self.source
     }
@@ -123,6 +137,7 @@ mod test {
 
     use crate::MessageCanId;
     use cyphal::{NodeId, Priority, SubjectId};
+    use std::vec::Vec;
 
     #[test]
     #[allow(non_snake_case)]
@@ -144,7 +159,6 @@ mod test {
     }
 
     #[test]
-    #[ignore = "need to generate a pseudorandom pseudo-ID value for source"]
     #[allow(non_snake_case)]
     fn test_0x11133775() {
         // uavcan.primitive.String.1.0 under subject-ID 4919 (133716) published by an anonymous node, the
@@ -156,14 +170,28 @@ mod test {
         let priority = Priority::Nominal;
         let subject: u16 = 4919;
 
+        let mut payload: Vec<u8> = [12, 0].to_vec();
+        payload.extend_from_slice(b"Hello world!");
+
         // Act
-        let target = MessageCanId::new(priority, subject.try_into().unwrap(), None).unwrap();
+        let target = MessageCanId::new_anonymous(priority, subject, &payload).unwrap();
 
         // Assert
         assert_eq!(target.priority(), priority);
         assert!(target.is_anonymous());
         assert_eq!(target.subject(), subject);
-        //FIXME: generate a pseudorandom pseudo-ID value for source
-        //assert_eq!(target.as_raw() & 0xFFFFFF00, 0x11133775 & 0xFFFFFF00);
+        assert_eq!(target.source(), 0x7F);
+        // the example predates the reserved bits 21 and 22 being set, and its pseudo-ID is random
+        assert_eq!(target.as_raw() & 0xFF9FFF80, 0x11133775 & 0xFF9FFF80);
+    }
+
+    #[test]
+    fn test_pseudo_id() {
+        let a = MessageCanId::new_anonymous(Priority::Nominal, 1, &[1, 2, 3]).unwrap();
+        let b = MessageCanId::new_anonymous(Priority::Nominal, 1, &[1, 2, 4]).unwrap();
+
+        assert_ne!(a.source(), b.source());
+        assert!(a.source() <= 127 && b.source() <= 127);
+        assert_eq!(MessageCanId::try_from(a.as_raw()).unwrap(), a);
     }
 }
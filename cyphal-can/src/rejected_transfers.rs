@@ -0,0 +1,34 @@
+use cyphal::CyphalError;
+
+/// Counters of the incoming transfers rejected by the CAN transport
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RejectedTransfers {
+    crc_mismatch: usize,
+    malformed: usize,
+}
+
+impl RejectedTransfers {
+    /// Returns the number of multi-frame transfers rejected because the transfer CRC didn't match
+    pub fn crc_mismatch(&self) -> usize {
+        self.crc_mismatch
+    }
+
+    /// Returns the number of transfers rejected because of an invalid tail byte sequence, such as a
+    /// missing start of transfer, a toggle bit error or a missing frame
+    pub fn malformed(&self) -> usize {
+        self.malformed
+    }
+
+    /// Returns the total number of rejected transfers
+    pub fn total(&self) -> usize {
+        self.crc_mismatch + self.malformed
+    }
+
+    /// Counts a transfer rejected because of the error
+    pub(crate) fn count(&mut self, error: CyphalError) {
+        match error {
+            CyphalError::CrcMismatch => self.crc_mismatch = self.crc_mismatch.saturating_add(1),
+            _ => self.malformed = self.malformed.saturating_add(1),
+        }
+    }
+}
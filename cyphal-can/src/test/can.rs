@@ -1,7 +1,12 @@
 extern crate std;
 
 use crate::{Can, CanError, CanId, CanResult, Frame, CLASSIC_PAYLOAD_SIZE};
-use std::vec::Vec;
+use core::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    task::Poll,
+};
+use std::{collections::VecDeque, vec::Vec};
 
 #[derive(Debug, Copy, Clone)]
 pub struct TestFrame {
@@ -39,21 +44,41 @@ impl Frame<CLASSIC_PAYLOAD_SIZE> for TestFrame {
     }
 }
 
+/// CAN interface replaying `received_frames`.
+///
+/// Once all the frames have been received, `receive` fails, or stays pending if `pending` is set
+/// so more frames can be added.
+#[derive(Default)]
 pub struct TestCan {
-    pub sent_frames: Vec<TestFrame>,
-    pub receive_fn: fn() -> CanResult<TestFrame>,
+    pub sent_frames: RefCell<Vec<TestFrame>>,
+    pub received_frames: RefCell<VecDeque<TestFrame>>,
+    pub pending: Cell<bool>,
+}
+
+impl TestCan {
+    pub fn new(received_frames: VecDeque<TestFrame>) -> Self {
+        Self {
+            received_frames: RefCell::new(received_frames),
+            ..Default::default()
+        }
+    }
 }
 
 impl Can<CLASSIC_PAYLOAD_SIZE> for TestCan {
     type Frame = TestFrame;
 
-    async fn transmit(&mut self, frame: &Self::Frame) -> CanResult<()> {
-        self.sent_frames.push(*frame);
+    async fn transmit(&self, frame: &Self::Frame) -> CanResult<()> {
+        self.sent_frames.borrow_mut().push(*frame);
         Ok(())
     }
 
-    async fn receive(&mut self) -> CanResult<Self::Frame> {
-        (self.receive_fn)()
+    async fn receive(&self) -> CanResult<Self::Frame> {
+        poll_fn(|_| match self.received_frames.borrow_mut().pop_front() {
+            Some(frame) => Poll::Ready(Ok(frame)),
+            None if self.pending.get() => Poll::Pending,
+            None => Poll::Ready(Err(CanError::Other)),
+        })
+        .await
     }
 }
 
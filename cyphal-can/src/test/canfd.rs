@@ -1,6 +1,7 @@
 extern crate std;
 
 use crate::{Can, CanError, CanId, CanResult, Frame, FD_PAYLOAD_SIZE};
+use core::cell::RefCell;
 use std::vec::Vec;
 
 #[derive(Debug, Copy, Clone)]
@@ -39,19 +40,20 @@ impl Frame<FD_PAYLOAD_SIZE> for TestFdFrame {
     }
 }
 
+#[derive(Default)]
 pub struct TestCanFd {
-    pub sent_frames: Vec<TestFdFrame>,
+    pub sent_frames: RefCell<Vec<TestFdFrame>>,
 }
 
 impl Can<64> for TestCanFd {
     type Frame = TestFdFrame;
 
-    async fn transmit(&mut self, frame: &Self::Frame) -> CanResult<()> {
-        self.sent_frames.push(*frame);
+    async fn transmit(&self, frame: &Self::Frame) -> CanResult<()> {
+        self.sent_frames.borrow_mut().push(*frame);
         Ok(())
     }
 
-    async fn receive(&mut self) -> CanResult<Self::Frame> {
+    async fn receive(&self) -> CanResult<Self::Frame> {
         todo!()
     }
 }
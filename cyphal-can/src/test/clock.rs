@@ -0,0 +1,27 @@
+use core::{cell::Cell, future::poll_fn, task::Poll, time::Duration};
+use cyphal::Clock;
+
+/// Clock advancing by `step` every time it's read.  Sleeping only completes once the clock has been
+/// moved past the deadline.
+#[derive(Default)]
+pub struct TestClock {
+    pub now: Cell<Duration>,
+    pub step: Duration,
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Duration {
+        let now = self.now.get();
+        self.now.set(now + self.step);
+
+        now
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        poll_fn(|_| match self.now.get() >= deadline {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        })
+        .await
+    }
+}
@@ -1,4 +1,4 @@
-use cyphal::{CyphalResult, Message, NodeId, Priority, SubjectId};
+use cyphal::{CyphalError, CyphalResult, Message, NodeId, Priority, SubjectId};
 
 pub const SMALL_MESSAGE_SIZE: usize = 2;
 pub const LARGE_MESSAGE_SIZE: usize = 65;
@@ -29,6 +29,17 @@ impl TestSmallMessage {
 impl Message for TestSmallMessage {
     const SIZE: usize = SMALL_MESSAGE_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn priority(&self) -> Priority {
         self.priority
     }
@@ -72,6 +83,17 @@ impl TestLargeMessage {
 impl Message for TestLargeMessage {
     const SIZE: usize = LARGE_MESSAGE_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn source(&self) -> Option<NodeId> {
         self.source
     }
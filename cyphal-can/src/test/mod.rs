@@ -4,8 +4,14 @@ pub use can::*;
 mod canfd;
 pub use canfd::*;
 
+mod clock;
+pub use clock::*;
+
 mod message;
 pub use message::*;
 
+mod router;
+pub use router::*;
+
 mod service;
 pub use service::*;
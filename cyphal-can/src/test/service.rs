@@ -73,6 +73,24 @@ pub struct TestResponse {
     data: [u8; TEST_RESPONSE_SIZE],
 }
 
+impl TestResponse {
+    pub fn new(
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: [u8; TEST_RESPONSE_SIZE],
+    ) -> CyphalResult<Self> {
+        Ok(Self {
+            priority,
+            service,
+            source,
+            destination,
+            data,
+        })
+    }
+}
+
 impl Response for TestResponse {
     const SIZE: usize = TEST_RESPONSE_SIZE;
 
@@ -1,129 +1,198 @@
 extern crate alloc;
 
 use crate::{CanId, CanTransferId, Frame, MessageCanId, ServiceCanId};
-use alloc::collections::{BTreeMap, VecDeque};
-use cyphal::Priority;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use core::time::Duration;
+use cyphal::{NodeId, Priority, ServiceId, SubjectId};
+
+/// Identifies the session a transfer belongs to
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum Session {
+    Message(SubjectId, NodeId),
+    Request(ServiceId, NodeId, NodeId),
+    Response(ServiceId, NodeId, NodeId),
+}
+
+impl From<CanId> for Session {
+    fn from(id: CanId) -> Self {
+        match id {
+            CanId::Message(id) => Session::Message(id.subject(), id.source()),
+            CanId::Service(id) if id.is_request() => {
+                Session::Request(id.service(), id.source(), id.destination())
+            }
+            CanId::Service(id) => Session::Response(id.service(), id.source(), id.destination()),
+        }
+    }
+}
+
+/// Transfer being reassembled
+struct PendingTransfer<F> {
+    transfer: CanTransferId,
+    timestamp: Duration,
+    frames: VecDeque<F>,
+}
+
+/// Reception state of a session
+struct SessionState<F> {
+    last_transfer: Option<(CanTransferId, Duration)>,
+    pending: Option<PendingTransfer<F>>,
+}
 
 pub struct InboundQueue<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>> {
-    pending_messages: BTreeMap<(MessageCanId, CanTransferId), VecDeque<F>>,
-    complete_messages: BTreeMap<(MessageCanId, CanTransferId), VecDeque<F>>,
-    pending_requests: BTreeMap<(ServiceCanId, CanTransferId), VecDeque<F>>,
-    complete_requests: BTreeMap<(ServiceCanId, CanTransferId), VecDeque<F>>,
-    pending_responses: BTreeMap<CanTransferId, VecDeque<F>>,
-    complete_responses: BTreeMap<CanTransferId, VecDeque<F>>,
+    timeout: Duration,
+    sessions: BTreeMap<Session, SessionState<F>>,
+    // the frames of the complete transfers and when they were completed
+    complete_messages: BTreeMap<(MessageCanId, CanTransferId), (VecDeque<F>, Duration)>,
+    complete_requests: BTreeMap<(ServiceCanId, CanTransferId), (VecDeque<F>, Duration)>,
+    expected_responses: BTreeSet<(ServiceId, NodeId, CanTransferId)>,
+    complete_responses: BTreeMap<(ServiceId, NodeId, CanTransferId), VecDeque<F>>,
 }
 
 impl<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>> InboundQueue<PAYLOAD_SIZE, F> {
-    pub fn new() -> Self {
+    pub fn new(timeout: Duration) -> Self {
         Self {
-            pending_messages: BTreeMap::new(),
+            timeout,
+            sessions: BTreeMap::new(),
             complete_messages: BTreeMap::new(),
-            pending_requests: BTreeMap::new(),
             complete_requests: BTreeMap::new(),
-            pending_responses: BTreeMap::new(),
+            expected_responses: BTreeSet::new(),
             complete_responses: BTreeMap::new(),
         }
     }
 
-    pub fn push(&mut self, frame: F) {
-        match frame.id() {
-            CanId::Message(id) => {
-                // Check to see if it's a single frame transfer
+    /// Sets the transfer-ID timeout
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Adds a frame received at `timestamp` to the queue.
+    ///
+    /// Once all the frames of a transfer have been received, messages and requests are kept until
+    /// they are taken or for the transfer-ID timeout, and responses until they are taken if they are
+    /// expected.  Frames of a transfer ID already received on the same session within the
+    /// transfer-ID timeout are dropped, as are frames that don't belong to the transfer being
+    /// reassembled.
+    pub fn push(&mut self, frame: F, timestamp: Duration) {
+        self.evict(timestamp);
+
+        let id = frame.id();
+
+        // anonymous transfers can't be deduplicated since the source is a pseudo-ID
+        if let CanId::Message(message) = id {
+            if message.is_anonymous() {
                 if frame.is_single_trame_transfer() {
-                    let transfer = frame.transfer();
-                    let queue = VecDeque::from([frame]);
-                    self.complete_messages.insert((id, transfer), queue);
+                    self.complete(id, frame.transfer(), VecDeque::from([frame]), timestamp);
+                }
 
+                return;
+            }
+        }
+
+        let timeout = self.timeout;
+        let transfer = frame.transfer();
+
+        let session = if frame.is_start_of_transfer() {
+            let session = self
+                .sessions
+                .entry(Session::from(id))
+                .or_insert(SessionState {
+                    last_transfer: None,
+                    pending: None,
+                });
+
+            // drop duplicates of the last transfer received on this session
+            if let Some((last, last_timestamp)) = session.last_transfer {
+                if last == transfer && timestamp.saturating_sub(last_timestamp) < timeout {
                     return;
                 }
+            }
 
-                let transfer = frame.transfer();
-
-                // if it's the last frame, remove the frames from pending and add to complete
-                if frame.is_end_of_transfer() {
-                    if let Some(mut queue) = self.pending_messages.remove(&(id, transfer)) {
-                        queue.push_back(frame);
-                        self.complete_messages.insert((id, transfer), queue);
-                    }
-                } else {
-                    match self.pending_messages.get_mut(&(id, transfer)) {
-                        Some(queue) => queue.push_back(frame),
-                        None => {
-                            // only add transmission if it's the start of one
-                            if frame.is_start_of_transfer() {
-                                let queue = VecDeque::from([frame]);
-                                self.pending_messages.insert((id, transfer), queue);
-                            }
-                        }
-                    }
-                }
+            // a new transfer replaces the one being reassembled
+            session.pending = Some(PendingTransfer {
+                transfer,
+                timestamp,
+                frames: VecDeque::new(),
+            });
+
+            session
+        } else {
+            match self.sessions.get_mut(&Session::from(id)) {
+                Some(session) => session,
+                None => return,
+            }
+        };
+
+        let pending = match session.pending.as_mut() {
+            Some(pending) if pending.transfer == transfer => pending,
+            _ => return,
+        };
+
+        let is_end_of_transfer = frame.is_end_of_transfer();
+        pending.frames.push_back(frame);
+
+        if is_end_of_transfer {
+            let pending = session.pending.take().unwrap();
+            session.last_transfer = Some((transfer, pending.timestamp));
+
+            self.complete(id, transfer, pending.frames, timestamp);
+        }
+    }
+
+    fn complete(
+        &mut self,
+        id: CanId,
+        transfer: CanTransferId,
+        frames: VecDeque<F>,
+        timestamp: Duration,
+    ) {
+        match id {
+            CanId::Message(id) => {
+                self.complete_messages
+                    .insert((id, transfer), (frames, timestamp));
+            }
+            CanId::Service(id) if id.is_request() => {
+                self.complete_requests
+                    .insert((id, transfer), (frames, timestamp));
             }
             CanId::Service(id) => {
-                if id.is_request() {
-                    // Check to see if it's a single frame transfer
-                    if frame.is_single_trame_transfer() {
-                        let transfer = frame.transfer();
-                        let queue = VecDeque::from([frame]);
-                        self.complete_requests.insert((id, transfer), queue);
-
-                        return;
-                    }
-
-                    let transfer = frame.transfer();
-
-                    // if it's the last frame, remove the frames from pending and add to complete
-                    if frame.is_end_of_transfer() {
-                        if let Some(mut queue) = self.pending_requests.remove(&(id, transfer)) {
-                            queue.push_back(frame);
-                            self.complete_requests.insert((id, transfer), queue);
-                        }
-                    } else {
-                        match self.pending_requests.get_mut(&(id, transfer)) {
-                            Some(queue) => queue.push_back(frame),
-                            None => {
-                                // only add transmission if it's the start of one
-                                if frame.is_start_of_transfer() {
-                                    let queue = VecDeque::from([frame]);
-                                    self.pending_requests.insert((id, transfer), queue);
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    // Check to see if it's a single frame transfer
-                    if frame.is_single_trame_transfer() {
-                        let transfer = frame.transfer();
-                        let queue = VecDeque::from([frame]);
-                        self.complete_responses.insert(transfer, queue);
-
-                        return;
-                    }
-
-                    let transfer = frame.transfer();
-
-                    // if it's the last frame, remove the frames from pending and add to complete
-                    if frame.is_end_of_transfer() {
-                        if let Some(mut queue) = self.pending_responses.remove(&transfer) {
-                            queue.push_back(frame);
-                            self.complete_responses.insert(transfer, queue);
-                        }
-                    } else {
-                        match self.pending_responses.get_mut(&transfer) {
-                            Some(queue) => queue.push_back(frame),
-                            None => {
-                                // only add transmission if it's the start of one
-                                if frame.is_start_of_transfer() {
-                                    let queue = VecDeque::from([frame]);
-                                    self.pending_responses.insert(transfer, queue);
-                                }
-                            }
-                        }
-                    }
+                let key = (id.service(), id.source(), transfer);
+                if self.expected_responses.contains(&key) {
+                    self.complete_responses.insert(key, frames);
                 }
             }
         }
     }
 
+    /// Removes the transfers that timed out, the messages and requests completed longer than the
+    /// transfer-ID timeout ago, and forgets the sessions that have been idle for longer than the
+    /// transfer-ID timeout.
+    fn evict(&mut self, now: Duration) {
+        let timeout = self.timeout;
+
+        self.complete_messages
+            .retain(|_, (_, timestamp)| now.saturating_sub(*timestamp) < timeout);
+        self.complete_requests
+            .retain(|_, (_, timestamp)| now.saturating_sub(*timestamp) < timeout);
+
+        self.sessions.retain(|_, session| {
+            if session
+                .pending
+                .as_ref()
+                .is_some_and(|p| now.saturating_sub(p.timestamp) >= timeout)
+            {
+                session.pending = None;
+            }
+            if session
+                .last_transfer
+                .is_some_and(|(_, t)| now.saturating_sub(t) >= timeout)
+            {
+                session.last_transfer = None;
+            }
+
+            session.pending.is_some() || session.last_transfer.is_some()
+        });
+    }
+
     pub fn get_message_frames(&mut self) -> Option<VecDeque<(MessageCanId, VecDeque<F>)>> {
         let keys = self.complete_messages.keys();
         if keys.len() == 0 {
@@ -142,14 +211,14 @@ impl<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>> InboundQueue<PAYLOAD_SIZ
 
         while let Some(kvp) = self.complete_messages.pop_first() {
             match kvp.0 .0.priority() {
-                Priority::Exceptional => p0.push_back((kvp.0 .0, kvp.1)),
-                Priority::Immediate => p1.push_back((kvp.0 .0, kvp.1)),
-                Priority::Fast => p2.push_back((kvp.0 .0, kvp.1)),
-                Priority::High => p3.push_back((kvp.0 .0, kvp.1)),
-                Priority::Nominal => p4.push_back((kvp.0 .0, kvp.1)),
-                Priority::Low => p5.push_back((kvp.0 .0, kvp.1)),
-                Priority::Slow => p6.push_back((kvp.0 .0, kvp.1)),
-                Priority::Optional => p7.push_back((kvp.0 .0, kvp.1)),
+                Priority::Exceptional => p0.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Immediate => p1.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Fast => p2.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::High => p3.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Nominal => p4.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Low => p5.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Slow => p6.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Optional => p7.push_back((kvp.0 .0, kvp.1 .0)),
             }
         }
 
@@ -185,14 +254,14 @@ impl<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>> InboundQueue<PAYLOAD_SIZ
 
         while let Some(kvp) = self.complete_requests.pop_first() {
             match kvp.0 .0.priority() {
-                Priority::Exceptional => p0.push_back((kvp.0 .0, kvp.1)),
-                Priority::Immediate => p1.push_back((kvp.0 .0, kvp.1)),
-                Priority::Fast => p2.push_back((kvp.0 .0, kvp.1)),
-                Priority::High => p3.push_back((kvp.0 .0, kvp.1)),
-                Priority::Nominal => p4.push_back((kvp.0 .0, kvp.1)),
-                Priority::Low => p5.push_back((kvp.0 .0, kvp.1)),
-                Priority::Slow => p6.push_back((kvp.0 .0, kvp.1)),
-                Priority::Optional => p7.push_back((kvp.0 .0, kvp.1)),
+                Priority::Exceptional => p0.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Immediate => p1.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Fast => p2.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::High => p3.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Nominal => p4.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Low => p5.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Slow => p6.push_back((kvp.0 .0, kvp.1 .0)),
+                Priority::Optional => p7.push_back((kvp.0 .0, kvp.1 .0)),
             }
         }
 
@@ -210,13 +279,183 @@ impl<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>> InboundQueue<PAYLOAD_SIZ
         Some(result)
     }
 
-    pub fn get_response_frames(&mut self, transfer: CanTransferId) -> Option<VecDeque<F>> {
-        self.complete_responses.remove(&transfer)
+    /// Returns true if received messages or requests are waiting to be processed
+    pub fn has_incoming(&self) -> bool {
+        !self.complete_messages.is_empty() || !self.complete_requests.is_empty()
+    }
+
+    /// Keeps the response from `source` once it is received
+    pub fn expect_response(&mut self, service: ServiceId, source: NodeId, transfer: CanTransferId) {
+        self.expected_responses.insert((service, source, transfer));
+    }
+
+    /// Returns the frames of the expected response if it has been received, in which case it is no
+    /// longer expected
+    pub fn take_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        transfer: CanTransferId,
+    ) -> Option<VecDeque<F>> {
+        let key = (service, source, transfer);
+        let frames = self.complete_responses.remove(&key)?;
+        self.expected_responses.remove(&key);
+
+        Some(frames)
+    }
+
+    /// Stops expecting a response that hasn't been received
+    pub fn forget_response(&mut self, service: ServiceId, source: NodeId, transfer: CanTransferId) {
+        let key = (service, source, transfer);
+        self.expected_responses.remove(&key);
+        self.complete_responses.remove(&key);
     }
 }
 
-impl<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>> Default for InboundQueue<PAYLOAD_SIZE, F> {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod test {
+    use super::InboundQueue;
+    use crate::{
+        test::TestFrame, CanTransferId, Frame, MessageCanId, ServiceCanId, CLASSIC_PAYLOAD_SIZE,
+    };
+    use core::time::Duration;
+    use cyphal::Priority;
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    fn message_frame(source: Option<u16>, tail_byte: u8) -> TestFrame {
+        let id = MessageCanId::new(Priority::Nominal, 10, source).unwrap();
+        TestFrame::new(id, &[1, 2, 3, 4, 5, 6, 7, tail_byte]).unwrap()
+    }
+
+    fn message_count(queue: &mut InboundQueue<CLASSIC_PAYLOAD_SIZE, TestFrame>) -> usize {
+        queue.get_message_frames().map_or(0, |m| m.len())
+    }
+
+    #[test]
+    fn test_duplicate_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+
+        queue.push(message_frame(Some(1), 0xE3), Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 1);
+
+        // same transfer ID within the timeout
+        queue.push(message_frame(Some(1), 0xE3), Duration::from_millis(500));
+        assert_eq!(message_count(&mut queue), 0);
+
+        // same transfer ID from another source
+        queue.push(message_frame(Some(2), 0xE3), Duration::from_millis(500));
+        assert_eq!(message_count(&mut queue), 1);
+
+        // same transfer ID after the timeout
+        queue.push(message_frame(Some(1), 0xE3), Duration::from_millis(2500));
+        assert_eq!(message_count(&mut queue), 1);
+
+        // next transfer ID
+        queue.push(message_frame(Some(1), 0xE4), Duration::from_millis(2600));
+        assert_eq!(message_count(&mut queue), 1);
+    }
+
+    #[test]
+    fn test_anonymous_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+
+        queue.push(message_frame(None, 0xE3), Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 1);
+
+        queue.push(message_frame(None, 0xE3), Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 1);
+    }
+
+    #[test]
+    fn test_multi_frame_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+
+        queue.push(message_frame(Some(1), 0xA5), Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 0);
+
+        queue.push(message_frame(Some(1), 0x45), Duration::from_millis(10));
+        let messages = queue.get_message_frames().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].1.len(), 2);
+        assert_eq!(messages[0].1[0].transfer(), CanTransferId::new(5).unwrap());
+    }
+
+    #[test]
+    fn test_stale_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+
+        queue.push(message_frame(Some(1), 0xA5), Duration::ZERO);
+        assert_eq!(queue.sessions.len(), 1);
+
+        // the end of the transfer arrives too late
+        queue.push(message_frame(Some(1), 0x45), Duration::from_secs(3));
+        assert_eq!(message_count(&mut queue), 0);
+        assert!(queue.sessions.is_empty());
+    }
+
+    #[test]
+    fn test_restarted_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+
+        queue.push(message_frame(Some(1), 0xA5), Duration::ZERO);
+        queue.push(message_frame(Some(1), 0xA6), Duration::from_millis(10));
+
+        // the end of the abandoned transfer is dropped
+        queue.push(message_frame(Some(1), 0x45), Duration::from_millis(20));
+        assert_eq!(message_count(&mut queue), 0);
+
+        queue.push(message_frame(Some(1), 0x46), Duration::from_millis(30));
+        assert_eq!(message_count(&mut queue), 1);
+    }
+
+    #[test]
+    fn test_set_timeout() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        queue.set_timeout(Duration::from_millis(100));
+
+        queue.push(message_frame(Some(1), 0xE3), Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 1);
+
+        queue.push(message_frame(Some(1), 0xE3), Duration::from_millis(200));
+        assert_eq!(message_count(&mut queue), 1);
+    }
+
+    #[test]
+    fn test_stale_complete_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+
+        queue.push(message_frame(Some(1), 0xE3), Duration::ZERO);
+        assert!(queue.has_incoming());
+
+        // nobody took the message within the timeout
+        queue.push(message_frame(Some(2), 0xA3), Duration::from_secs(3));
+        assert!(!queue.has_incoming());
+    }
+
+    #[test]
+    fn test_expected_response() {
+        let mut queue: InboundQueue<CLASSIC_PAYLOAD_SIZE, TestFrame> = InboundQueue::new(TIMEOUT);
+        let id = ServiceCanId::new(Priority::Nominal, false, 1, 2, 3).unwrap();
+        let transfer = CanTransferId::new(1).unwrap();
+
+        // nobody is waiting for this response
+        queue.push(TestFrame::new(id, &[1, 0xE1]).unwrap(), Duration::ZERO);
+        queue.expect_response(1, 2, transfer);
+        assert!(queue.take_response(1, 2, transfer).is_none());
+
+        queue.push(
+            TestFrame::new(id, &[2, 0xE1]).unwrap(),
+            Duration::from_secs(3),
+        );
+        let frames = queue.take_response(1, 2, transfer).unwrap();
+        assert_eq!(frames[0].data()[0], 2);
+
+        // the response is no longer expected once it has been taken
+        queue.push(
+            TestFrame::new(id, &[3, 0xE1]).unwrap(),
+            Duration::from_secs(6),
+        );
+        assert!(queue.take_response(1, 2, transfer).is_none());
     }
 }
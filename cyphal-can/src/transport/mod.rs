@@ -4,33 +4,58 @@ use inbound_queue::InboundQueue;
 mod outbound_queue;
 use outbound_queue::OutboundQueue;
 
+mod session_table;
+use session_table::SessionTable;
+
 extern crate alloc;
 
 use crate::{
-    Can, CanId, CanTransferId, Frame, MessageCanId, ServiceCanId, CLASSIC_PAYLOAD_SIZE,
-    FD_PAYLOAD_SIZE,
+    Can, CanId, CanTransferId, Frame, MessageCanId, RejectedTransfers, ServiceCanId,
+    CLASSIC_PAYLOAD_SIZE, FD_PAYLOAD_SIZE,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    cmp::Ordering,
+    future::{poll_fn, Future},
+    mem,
+    pin::pin,
+    task::{Poll, Waker},
+    time::Duration,
 };
-use alloc::vec::Vec;
-use core::cmp::Ordering;
 use crc::Crc;
 use cyphal::{
-    CyphalError, CyphalResult, Message, NodeId, Request, Response, Router, ServiceId, SubjectId,
-    TransferId, Transport,
+    Clock, CyphalError, CyphalResult, Message, NodeId, Request, Response, Router, ServiceId,
+    Shared, SubjectId, TransferId, Transport,
 };
 
 const CRC16: Crc<u16> = Crc::<u16>::new(&crc::CRC_16_IBM_3740);
 
+/// Default transfer-ID timeout recommended by the Cyphal specification
+const DEFAULT_TRANSFER_ID_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Represents a CAN Transport
-pub struct CanTransport<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> {
+///
+/// The transport can be shared between tasks: frames received while a task is waiting on a
+/// response are made available to the task serving the incoming transfers, and vice versa.
+pub struct CanTransport<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>, K: Clock> {
     can: C,
-    transfer: CanTransferId,
-    inbound_queue: InboundQueue<PAYLOAD_SIZE, C::Frame>,
-    outbound_queue: OutboundQueue<PAYLOAD_SIZE, C::Frame>,
+    clock: K,
+    sessions: Shared<SessionTable>,
+    inbound_queue: Shared<InboundQueue<PAYLOAD_SIZE, C::Frame>>,
+    outbound_queue: Shared<OutboundQueue<PAYLOAD_SIZE, C::Frame>>,
+    rejected: Shared<RejectedTransfers>,
+    received: Shared<usize>,
+    waiters: Shared<Vec<Waker>>,
+    serving: Shared<usize>,
 }
 
-impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE, C> {
-    /// Constructs a new CAN transport
-    pub fn new(can: C) -> CyphalResult<Self> {
+impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>, K: Clock> CanTransport<PAYLOAD_SIZE, C, K> {
+    /// Constructs a new CAN transport.
+    ///
+    /// The clock timestamps the received frames.  Partially received transfers are discarded, and
+    /// repeated transfer IDs are ignored, for the duration of the transfer-ID timeout which
+    /// defaults to 2 seconds.
+    pub fn new(can: C, clock: K) -> CyphalResult<Self> {
         assert!(
             PAYLOAD_SIZE == CLASSIC_PAYLOAD_SIZE || PAYLOAD_SIZE == FD_PAYLOAD_SIZE,
             "Invalid PAYLOAD_SIZE value.  Must be 8 for CAN Classic or 64 for CAN FD"
@@ -38,20 +63,168 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
 
         Ok(Self {
             can,
-            transfer: CanTransferId::default(),
-            inbound_queue: InboundQueue::default(),
-            outbound_queue: OutboundQueue::default(),
+            clock,
+            sessions: Shared::default(),
+            inbound_queue: Shared::new(InboundQueue::new(DEFAULT_TRANSFER_ID_TIMEOUT)),
+            outbound_queue: Shared::default(),
+            rejected: Shared::default(),
+            received: Shared::new(0),
+            waiters: Shared::new(Vec::new()),
+            serving: Shared::new(0),
+        })
+    }
+
+    /// Sets the transfer-ID timeout
+    pub fn set_transfer_id_timeout(&self, timeout: Duration) {
+        self.inbound_queue.with(|queue| queue.set_timeout(timeout));
+    }
+
+    /// Returns the counters of the incoming transfers that have been rejected
+    pub fn rejected_transfers(&self) -> RejectedTransfers {
+        self.rejected.get()
+    }
+
+    fn push_frame(&self, frame: C::Frame) {
+        // a frame must at least contain a tail byte
+        if frame.dlc() == 0 {
+            self.count_rejected(CyphalError::MalformedTransfer);
+            return;
+        }
+
+        let now = self.clock.now();
+        self.inbound_queue.with(|queue| queue.push(frame, now));
+    }
+
+    fn count_rejected(&self, error: CyphalError) {
+        self.rejected.with(|rejected| rejected.count(error));
+    }
+
+    /// Counts that a frame was received and wakes the tasks waiting for it
+    fn notify(&self) {
+        // the count is incremented and the waiters taken at once, so that a task registering in
+        // the meantime sees the new count
+        let waiters = self.waiters.with(|waiters| {
+            self.received.with(|count| *count = count.wrapping_add(1));
+            mem::take(waiters)
+        });
+        waiters.into_iter().for_each(Waker::wake);
+    }
+
+    /// Registers the waker of a task until a frame is received, unless the count moved past
+    /// `received` in the meantime, in which case true is returned
+    fn wait(&self, received: usize, waker: &Waker) -> bool {
+        self.waiters.with(|waiters| {
+            if self.received.get() != received {
+                return true;
+            }
+            if !waiters.iter().any(|w| w.will_wake(waker)) {
+                waiters.push(waker.clone());
+            }
+
+            false
+        })
+    }
+
+    /// Waits until a frame has been added to the inbound queue.
+    ///
+    /// The inbound queue is shared by all the tasks using the transport, so this returns as soon
+    /// as a frame is received by this task or any other.  Frames rejected by `accept` are dropped
+    /// without being added to the queue.
+    async fn receive<A>(&self, accept: A) -> CyphalResult<()>
+    where
+        A: Fn(CanId) -> bool,
+    {
+        let received = self.received.get();
+        let mut receive = pin!(self.can.receive());
+
+        poll_fn(|cx| {
+            if self.received.get() != received {
+                return Poll::Ready(Ok(()));
+            }
+
+            match receive.as_mut().poll(cx) {
+                Poll::Ready(Ok(frame)) => {
+                    if accept(frame.id()) {
+                        self.push_frame(frame);
+                    }
+                    self.notify();
+
+                    Poll::Ready(Ok(()))
+                }
+                Poll::Ready(Err(error)) => Poll::Ready(Err(error.into())),
+                Poll::Pending => match self.wait(received, cx.waker()) {
+                    true => Poll::Ready(Ok(())),
+                    false => Poll::Pending,
+                },
+            }
         })
+        .await
     }
 
-    fn next_transfer(&mut self) -> CanTransferId {
-        self.transfer = self.transfer.next();
+    /// Receives frames until the expected response is complete or the deadline is reached.  Messages
+    /// and requests received in the meantime are only kept if a task is serving them.
+    async fn receive_response(
+        &self,
+        service: ServiceId,
+        source: NodeId,
+        transfer: CanTransferId,
+        deadline: Duration,
+    ) -> CyphalResult<(ServiceCanId, Vec<u8>)> {
+        let accept = |id: CanId| match id {
+            CanId::Service(id) if !id.is_request() => true,
+            _ => self.serving.get() > 0,
+        };
+
+        loop {
+            let frames = self
+                .inbound_queue
+                .with(|queue| queue.take_response(service, source, transfer));
+            if let Some(frames) = frames {
+                let id = match frames.front().map(|f| f.id()) {
+                    Some(CanId::Service(id)) if !id.is_request() => id,
+                    _ => return Err(CyphalError::MalformedTransfer),
+                };
+
+                if let Some(payload) = self.reassemble(frames) {
+                    return Ok((id, payload));
+                }
+            }
+
+            self.clock
+                .run_until(deadline, self.receive(accept))
+                .await??;
+        }
+    }
+
+    /// Returns the payload of the transfer, or `None` if the transfer is rejected, in which case the
+    /// rejection is counted
+    fn reassemble(&self, frames: VecDeque<C::Frame>) -> Option<Vec<u8>> {
+        match reassemble(frames) {
+            Ok(payload) => Some(payload),
+            Err(error) => {
+                self.count_rejected(error);
+                None
+            }
+        }
+    }
 
-        self.transfer
+    async fn transmit_frames(&self) -> CyphalResult<()> {
+        loop {
+            let frame = self.outbound_queue.with(|queue| queue.pop());
+            match frame {
+                Some(frame) => self.can.transmit(&frame).await?,
+                None => return Ok(()),
+            }
+        }
     }
 
-    fn enqueue_frames(&mut self, can_id: CanId, mut data: &[u8]) -> CyphalResult<CanTransferId> {
-        let transfer = self.next_transfer();
+    fn enqueue_frames(
+        &self,
+        can_id: CanId,
+        transfer: CanTransferId,
+        mut data: &[u8],
+    ) -> CyphalResult<()> {
+        let mut frames: Vec<C::Frame> = Vec::new();
 
         // is multiframe
         if data.len() > PAYLOAD_SIZE - 1 {
@@ -74,10 +247,7 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
                     payload[PAYLOAD_SIZE - 1] =
                         tail_byte(frame_count == 1, false, frame_count % 2 > 0, transfer);
 
-                    match Frame::new(can_id, &payload) {
-                        Ok(frame) => self.outbound_queue.push(frame),
-                        Err(_) => return Err(CyphalError::Transport),
-                    }
+                    frames.push(Frame::new(can_id, &payload)?);
                 } else {
                     let mut payload: [u8; PAYLOAD_SIZE] = [0; PAYLOAD_SIZE];
 
@@ -94,10 +264,8 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
                             payload[data.len() + 2] =
                                 tail_byte(false, true, frame_count % 2 > 0, transfer);
 
-                            match Frame::new(can_id, &payload[..(data.len() + 3)]) {
-                                Ok(frame) => self.outbound_queue.push(frame),
-                                Err(_) => return Err(CyphalError::Transport),
-                            }
+                            let frame = Frame::new(can_id, &payload[..(data.len() + 3)])?;
+                            frames.push(frame);
 
                             break;
                         }
@@ -109,10 +277,7 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
                             payload[PAYLOAD_SIZE - 1] =
                                 tail_byte(false, false, frame_count % 2 > 0, transfer);
 
-                            match Frame::new(can_id, &payload) {
-                                Ok(frame) => self.outbound_queue.push(frame),
-                                Err(_) => return Err(CyphalError::Transport),
-                            }
+                            frames.push(Frame::new(can_id, &payload)?);
 
                             frame_count += 1;
 
@@ -124,10 +289,7 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
                             // add the tail byte
                             payload[1] = tail_byte(false, true, frame_count % 2 > 0, transfer);
 
-                            match Frame::new(can_id, &payload) {
-                                Ok(frame) => self.outbound_queue.push(frame),
-                                Err(_) => return Err(CyphalError::Transport),
-                            }
+                            frames.push(Frame::new(can_id, &payload)?);
 
                             break;
                         }
@@ -138,10 +300,7 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
                             payload[PAYLOAD_SIZE - 1] =
                                 tail_byte(false, false, frame_count % 2 > 0, transfer);
 
-                            match Frame::new(can_id, &payload) {
-                                Ok(frame) => self.outbound_queue.push(frame),
-                                Err(_) => return Err(CyphalError::Transport),
-                            }
+                            frames.push(Frame::new(can_id, &payload)?);
 
                             frame_count += 1;
 
@@ -154,10 +313,7 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
                             // add the tail byte
                             payload[2] = tail_byte(false, true, frame_count % 2 > 0, transfer);
 
-                            match Frame::new(can_id, &payload) {
-                                Ok(frame) => self.outbound_queue.push(frame),
-                                Err(_) => return Err(CyphalError::Transport),
-                            }
+                            frames.push(Frame::new(can_id, &payload)?);
 
                             break;
                         }
@@ -175,17 +331,21 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> CanTransport<PAYLOAD_SIZE,
             // add the tail byte
             payload[PAYLOAD_SIZE - 1] = tail_byte(true, true, true, transfer);
 
-            match Frame::new(can_id, &payload) {
-                Ok(frame) => self.outbound_queue.push(frame),
-                Err(_) => return Err(CyphalError::Transport),
-            }
+            frames.push(Frame::new(can_id, &payload)?);
         }
 
-        Ok(transfer)
+        // the frames are queued at once, so that they aren't mixed with the frames of a transfer
+        // enqueued by another task
+        self.outbound_queue
+            .with(|queue| frames.into_iter().for_each(|f| queue.push(f)));
+
+        Ok(())
     }
 }
 
-impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> Transport for CanTransport<PAYLOAD_SIZE, C> {
+impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>, K: Clock> Transport
+    for CanTransport<PAYLOAD_SIZE, C, K>
+{
     /// Maximim Subject ID
     const MAX_SUBJECT_ID: SubjectId = 8191;
 
@@ -195,7 +355,7 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> Transport for CanTransport
     /// Maximim Subject ID
     const MAX_NODE_ID: NodeId = 127;
 
-    async fn publish<M>(&mut self, message: &M) -> CyphalResult<()>
+    async fn publish<M>(&self, message: &M) -> CyphalResult<()>
     where
         M: Message,
     {
@@ -205,21 +365,27 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> Transport for CanTransport
             return Err(CyphalError::OutOfRange);
         }
 
-        let id =
-            MessageCanId::new(message.priority(), message.subject(), message.source()).unwrap();
-
-        self.enqueue_frames(id.into(), message.data())?;
+        let id = match message.source() {
+            Some(source) => MessageCanId::new(message.priority(), message.subject(), Some(source)),
+            None => {
+                // anonymous transfers must fit in a single frame
+                if message.data().len() > PAYLOAD_SIZE - 1 {
+                    return Err(CyphalError::OutOfRange);
+                }
 
-        while let Some(frame) = self.outbound_queue.pop() {
-            match self.can.transmit(&frame).await {
-                Ok(()) => {}
-                Err(_) => return Err(CyphalError::Transport),
+                MessageCanId::new_anonymous(message.priority(), message.subject(), message.data())
             }
-        }
-        Ok(())
+        }?;
+
+        let transfer = self
+            .sessions
+            .with(|sessions| sessions.next_message_transfer(message.subject()));
+        self.enqueue_frames(id.into(), transfer, message.data())?;
+
+        self.transmit_frames().await
     }
 
-    async fn invoque<R>(&mut self, request: &R) -> CyphalResult<R::Response>
+    async fn invoque<R>(&self, request: &R, timeout: Duration) -> CyphalResult<R::Response>
     where
         R: Request,
     {
@@ -239,186 +405,103 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> Transport for CanTransport
         )
         .unwrap();
 
-        let transfer = self.enqueue_frames(id.into(), request.data())?;
-
-        while let Some(frame) = self.outbound_queue.pop() {
-            match self.can.transmit(&frame).await {
-                Ok(()) => {}
-                Err(_) => return Err(CyphalError::Transport),
-            }
-        }
-
-        while let Ok(frame) = self.can.receive().await {
-            self.inbound_queue.push(frame);
-
-            if let Some(mut queue) = self.inbound_queue.get_response_frames(transfer) {
-                let first_frame = queue.pop_front().unwrap();
-                let id = match first_frame.id() {
-                    CanId::Message(_) => return Err(CyphalError::Transport),
-                    CanId::Service(id) => id,
-                };
-
-                if id.is_request() {
-                    return Err(CyphalError::Transport);
-                }
-
-                let mut payload: Vec<u8> = Vec::new();
-
-                if first_frame.is_single_trame_transfer() {
-                    if R::Response::SIZE > PAYLOAD_SIZE - 1 {
-                        // something went wrong
-                        return Err(CyphalError::Transport);
-                    }
-
-                    let data = first_frame.data();
-                    for value in data.iter().take(R::Response::SIZE) {
-                        payload.push(*value);
-                    }
-
-                    return R::Response::new_raw(
-                        id.priority(),
-                        id.service(),
-                        id.source(),
-                        id.destination(),
-                        &payload,
-                    );
-                } else {
-                    if !first_frame.is_start_of_transfer() || !first_frame.is_toggle_bit_set() {
-                        // something went wrong
-                        return Err(CyphalError::Transport);
-                    }
-
-                    payload.extend_from_slice(&first_frame.data()[..first_frame.dlc() - 2]);
+        let transfer = self.sessions.with(|sessions| {
+            sessions.next_request_transfer(request.service(), request.destination())
+        });
+        self.enqueue_frames(id.into(), transfer, request.data())?;
 
-                    let mut toogle = false;
+        let deadline = self.clock.now() + timeout;
 
-                    while let Some(frame) = queue.pop_front() {
-                        if frame.is_start_of_transfer() || frame.is_toggle_bit_set() != toogle {
-                            // something went wrong
-                            return Err(CyphalError::Transport);
-                        }
-                        if frame.is_end_of_transfer() && !queue.is_empty() {
-                            // something went wrong
-                            return Err(CyphalError::Transport);
-                        }
-
-                        payload.extend_from_slice(&frame.data()[..frame.dlc() - 2]);
-                        toogle = !toogle
-                    }
+        self.inbound_queue.with(|queue| {
+            queue.expect_response(request.service(), request.destination(), transfer)
+        });
 
-                    if payload.len() != R::Response::SIZE {
-                        // something went wrong
-                        return Err(CyphalError::Transport);
-                    }
-
-                    return R::Response::new_raw(
-                        id.priority(),
-                        id.service(),
-                        id.source(),
-                        id.destination(),
-                        &payload,
-                    );
-                }
-            }
+        let response = async {
+            self.transmit_frames().await?;
+            self.receive_response(request.service(), request.destination(), transfer, deadline)
+                .await
         }
+        .await;
+
+        // a late response must not be kept forever
+        let (id, mut payload) = match response {
+            Ok(response) => response,
+            Err(error) => {
+                self.inbound_queue.with(|queue| {
+                    queue.forget_response(request.service(), request.destination(), transfer)
+                });
+                return Err(error);
+            }
+        };
 
-        Err(CyphalError::Transport)
+        // single frame transfers are padded up to the frame size
+        payload.truncate(R::Response::SIZE);
+
+        R::Response::new_raw(
+            id.priority(),
+            id.service(),
+            id.source(),
+            id.destination(),
+            &payload,
+        )
     }
 
-    async fn serve<R>(&mut self, router: R) -> CyphalResult<()>
+    async fn serve<R>(&self, router: R) -> CyphalResult<()>
     where
         R: Router,
     {
-        while let Ok(frame) = self.can.receive().await {
-            self.inbound_queue.push(frame);
-
-            if let Some(messages) = self.inbound_queue.get_message_frames() {
-                for kvp in messages {
-                    let mut queue = kvp.1;
-                    let mut payload: Vec<u8> = Vec::new();
-                    let first_frame = queue.pop_front().unwrap();
-                    let id = match first_frame.id() {
-                        CanId::Message(id) => id,
-                        CanId::Service(_) => return Err(CyphalError::Transport),
-                    };
-
-                    if first_frame.is_single_trame_transfer() {
-                        payload.extend_from_slice(first_frame.data());
-                    } else {
-                        if !first_frame.is_start_of_transfer() || !first_frame.is_toggle_bit_set() {
-                            // something went wrong
-                            return Err(CyphalError::Transport);
-                        }
+        // only the ports the router processes are reassembled
+        let accept = |id: CanId| match id {
+            CanId::Message(id) => router.accepts_message(id.subject()),
+            CanId::Service(id) if id.is_request() => router.accepts_request(id.service()),
+            CanId::Service(_) => true,
+        };
 
-                        payload.extend_from_slice(&first_frame.data()[..first_frame.dlc() - 2]);
+        // messages and requests received by the other tasks are kept for this one
+        let _serving = Serving::new(&self.serving);
 
-                        let mut toogle = false;
+        loop {
+            if !self.inbound_queue.with(|queue| queue.has_incoming()) {
+                if self.receive(accept).await.is_err() {
+                    break;
+                }
+                continue;
+            }
 
-                        while let Some(frame) = queue.pop_front() {
-                            if frame.is_start_of_transfer() || frame.is_toggle_bit_set() != toogle {
-                                // something went wrong
-                                return Err(CyphalError::Transport);
-                            }
-                            if frame.is_end_of_transfer() && !queue.is_empty() {
-                                // something went wrong
-                                return Err(CyphalError::Transport);
-                            }
+            let messages = self.inbound_queue.with(|queue| queue.get_message_frames());
+            if let Some(messages) = messages {
+                for (id, queue) in messages {
+                    let payload = match self.reassemble(queue) {
+                        Some(payload) => payload,
+                        None => continue,
+                    };
 
-                            payload.extend_from_slice(&frame.data()[..frame.dlc() - 2]);
-                            toogle = !toogle
-                        }
-                    }
+                    // anonymous messages carry a pseudo-ID instead of a source
+                    let source = (!id.is_anonymous()).then_some(id.source());
 
-                    //TODO: do something with result
-                    let _result = router
-                        .process_message(id.priority(), id.subject(), id.source(), &payload)
+                    // an error processing one transfer must not stop the transport from serving
+                    // the next ones, and the router is the one able to report it, so it is
+                    // ignored here
+                    let _ = router
+                        .process_message(id.priority(), id.subject(), source, &payload)
                         .await;
                 }
             }
 
-            if let Some(requests) = self.inbound_queue.get_request_frames() {
-                let mut payload: Vec<u8> = Vec::new();
-                for kvp in requests {
-                    let mut queue = kvp.1;
-                    let first_frame = queue.pop_front().unwrap();
-                    let id = match first_frame.id() {
-                        CanId::Message(_) => return Err(CyphalError::Transport),
-                        CanId::Service(id) => id,
+            let requests = self.inbound_queue.with(|queue| queue.get_request_frames());
+            if let Some(requests) = requests {
+                for (id, queue) in requests {
+                    let transfer = match queue.front() {
+                        Some(frame) => frame.transfer(),
+                        None => continue,
                     };
 
-                    if !id.is_request() {
-                        return Err(CyphalError::Transport);
-                    }
-
-                    if first_frame.is_single_trame_transfer() {
-                        payload.extend_from_slice(first_frame.data());
-                    } else {
-                        if !first_frame.is_start_of_transfer() || !first_frame.is_toggle_bit_set() {
-                            // something went wrong
-                            return Err(CyphalError::Transport);
-                        }
-
-                        payload.extend_from_slice(&first_frame.data()[..first_frame.dlc() - 2]);
-
-                        let mut toogle = false;
-
-                        while let Some(frame) = queue.pop_front() {
-                            if frame.is_start_of_transfer() || frame.is_toggle_bit_set() != toogle {
-                                // something went wrong
-                                return Err(CyphalError::Transport);
-                            }
-                            if frame.is_end_of_transfer() && !queue.is_empty() {
-                                // something went wrong
-                                return Err(CyphalError::Transport);
-                            }
-
-                            payload.extend_from_slice(&frame.data()[..frame.dlc() - 2]);
-                            toogle = !toogle
-                        }
-                    }
+                    let payload = match self.reassemble(queue) {
+                        Some(payload) => payload,
+                        None => continue,
+                    };
 
-                    //TODO: do something with result
-                    if let Ok(Some(response)) = router
+                    let response = router
                         .process_request(
                             id.priority(),
                             id.service(),
@@ -426,18 +509,21 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> Transport for CanTransport
                             id.destination(),
                             &payload,
                         )
-                        .await
-                    {
+                        .await;
+
+                    // as for messages, an error processing the request is left to the router
+                    if let Ok(Some(response)) = response {
                         let id = ServiceCanId::new(
                             response.priority(),
                             false,
                             response.service(),
-                            response.destination(),
                             response.source(),
-                        )
-                        .unwrap();
+                            response.destination(),
+                        )?;
 
-                        self.enqueue_frames(id.into(), response.data())?;
+                        // the response must use the same transfer ID as the request
+                        self.enqueue_frames(id.into(), transfer, response.data())?;
+                        self.transmit_frames().await?;
                     }
                 }
             }
@@ -447,6 +533,62 @@ impl<const PAYLOAD_SIZE: usize, C: Can<PAYLOAD_SIZE>> Transport for CanTransport
     }
 }
 
+/// Validates the tail bytes of the frames of a transfer and returns its payload.
+///
+/// The transfer CRC of multi-frame transfers is verified and removed from the payload.
+fn reassemble<const PAYLOAD_SIZE: usize, F: Frame<PAYLOAD_SIZE>>(
+    mut frames: VecDeque<F>,
+) -> CyphalResult<Vec<u8>> {
+    let first_frame = match frames.pop_front() {
+        Some(frame) => frame,
+        None => return Err(CyphalError::MalformedTransfer),
+    };
+
+    if first_frame.is_single_trame_transfer() {
+        if !frames.is_empty() {
+            return Err(CyphalError::MalformedTransfer);
+        }
+
+        return Ok(first_frame.data()[..first_frame.dlc() - 1].to_vec());
+    }
+
+    if !first_frame.is_start_of_transfer() || !first_frame.is_toggle_bit_set() {
+        return Err(CyphalError::MalformedTransfer);
+    }
+
+    let transfer = first_frame.transfer();
+    let mut payload: Vec<u8> = Vec::new();
+    payload.extend_from_slice(&first_frame.data()[..first_frame.dlc() - 1]);
+
+    let mut toggle = false;
+    let mut is_complete = false;
+    while let Some(frame) = frames.pop_front() {
+        if frame.is_start_of_transfer()
+            || frame.is_toggle_bit_set() != toggle
+            || frame.transfer() != transfer
+            || (frame.is_end_of_transfer() && !frames.is_empty())
+        {
+            return Err(CyphalError::MalformedTransfer);
+        }
+
+        payload.extend_from_slice(&frame.data()[..frame.dlc() - 1]);
+        is_complete = frame.is_end_of_transfer();
+        toggle = !toggle;
+    }
+
+    if !is_complete || payload.len() < 2 {
+        return Err(CyphalError::MalformedTransfer);
+    }
+
+    // the residue of the CRC computed over the payload followed by its CRC is zero
+    if CRC16.checksum(&payload) != 0 {
+        return Err(CyphalError::CrcMismatch);
+    }
+    payload.truncate(payload.len() - 2);
+
+    Ok(payload)
+}
+
 fn tail_byte(is_start: bool, is_end: bool, toggle: bool, transfer: CanTransferId) -> u8 {
     let mut tail_byte = transfer.value();
     if is_start {
@@ -462,6 +604,22 @@ fn tail_byte(is_start: bool, is_end: bool, toggle: bool, transfer: CanTransferId
     tail_byte
 }
 
+/// Counts a task serving the incoming transfers until it is dropped
+struct Serving<'a>(&'a Shared<usize>);
+
+impl<'a> Serving<'a> {
+    fn new(count: &'a Shared<usize>) -> Self {
+        count.with(|count| *count += 1);
+        Self(count)
+    }
+}
+
+impl Drop for Serving<'_> {
+    fn drop(&mut self) {
+        self.0.with(|count| *count -= 1);
+    }
+}
+
 #[cfg(test)]
 mod test {
     extern crate std;
@@ -469,172 +627,202 @@ mod test {
     use super::CRC16;
     use crate::{
         test::{
-            check_classic_frame, check_fd_frame, TestCan, TestCanFd, TestFrame, TestLargeMessage,
-            TestRequest, TestSmallMessage, LARGE_MESSAGE_SIZE, TEST_REQUEST_SIZE,
+            check_classic_frame, check_fd_frame, TestCan, TestCanFd, TestClock, TestFrame,
+            TestLargeMessage, TestRequest, TestRouter, TestSmallMessage, LARGE_MESSAGE_SIZE,
+            TEST_REQUEST_SIZE, TEST_RESPONSE_SIZE,
         },
-        CanError, CanTransport, Frame, ServiceCanId,
+        CanId, CanTransferId, CanTransport, Frame, MessageCanId, ServiceCanId,
+        CLASSIC_PAYLOAD_SIZE,
     };
-    use cyphal::{Priority, Response, Transport as _};
-    use std::vec::Vec;
+    use core::{
+        cell::{Cell, RefCell},
+        future::{poll_fn, Future},
+        pin::pin,
+        task::Poll,
+        time::Duration,
+    };
+    use cyphal::{CyphalError, Dispatcher, Message as _, Priority, Response, Transport as _};
+    use std::{collections::VecDeque, vec::Vec};
+
+    async fn large_message_frames(data: [u8; LARGE_MESSAGE_SIZE]) -> VecDeque<TestFrame> {
+        let can = TestCan::default();
+        let transport = CanTransport::new(can, TestClock::default()).unwrap();
+
+        let message = TestLargeMessage::new(Priority::Nominal, 1, Some(5), data).unwrap();
+        transport.publish(&message).await.unwrap();
+
+        transport.can.sent_frames.into_inner().into()
+    }
+
+    async fn serve(
+        frames: VecDeque<TestFrame>,
+    ) -> (
+        CanTransport<CLASSIC_PAYLOAD_SIZE, TestCan, TestClock>,
+        TestRouter,
+    ) {
+        let can = TestCan::new(frames);
+        let transport = CanTransport::new(can, TestClock::default()).unwrap();
+
+        let router = TestRouter::new();
+        transport.serve(&router).await.unwrap();
+
+        (transport, router)
+    }
 
     #[async_std::test]
     async fn transmit_small_message() {
-        let can = TestCan {
-            sent_frames: Vec::new(),
-            receive_fn: || Err(CanError::Other),
-        };
-        let mut transport = CanTransport::new(can).expect("Could not create transport");
+        let can = TestCan::default();
+        let transport =
+            CanTransport::new(can, TestClock::default()).expect("Could not create transport");
 
         let message =
             TestSmallMessage::new(Priority::Nominal, 1.try_into().unwrap(), None, [1, 2]).unwrap();
         transport.publish(&message).await.unwrap();
 
-        assert_eq!(transport.can.sent_frames.len(), 1);
+        assert_eq!(transport.can.sent_frames.borrow().len(), 1);
     }
 
     #[async_std::test]
     async fn transmit_large_message() {
-        let can = TestCan {
-            sent_frames: Vec::new(),
-            receive_fn: || Err(CanError::Other),
-        };
-        let mut transport = CanTransport::new(can).expect("Could not create transport");
+        let can = TestCan::default();
+        let transport =
+            CanTransport::new(can, TestClock::default()).expect("Could not create transport");
 
         let data: Vec<u8> = (0..LARGE_MESSAGE_SIZE as u8).collect();
         let data: [u8; LARGE_MESSAGE_SIZE] = data.try_into().unwrap();
         let checksum = CRC16.checksum(&data).to_be_bytes();
 
-        let message =
-            TestLargeMessage::new(Priority::Nominal, 1.try_into().unwrap(), None, data).unwrap();
+        let message = TestLargeMessage::new(Priority::Nominal, 1, Some(5), data).unwrap();
         transport.publish(&message).await.unwrap();
 
-        assert_eq!(transport.can.sent_frames.len(), 10);
+        assert_eq!(transport.can.sent_frames.borrow().len(), 10);
         check_classic_frame(
-            transport.can.sent_frames[0],
+            transport.can.sent_frames.borrow()[0],
             [0, 1, 2, 3, 4, 5, 6],
             true,
             false,
             true,
         );
         check_classic_frame(
-            transport.can.sent_frames[1],
+            transport.can.sent_frames.borrow()[1],
             [7, 8, 9, 10, 11, 12, 13],
             false,
             false,
             false,
         );
         check_classic_frame(
-            transport.can.sent_frames[2],
+            transport.can.sent_frames.borrow()[2],
             [14, 15, 16, 17, 18, 19, 20],
             false,
             false,
             true,
         );
         check_classic_frame(
-            transport.can.sent_frames[3],
+            transport.can.sent_frames.borrow()[3],
             [21, 22, 23, 24, 25, 26, 27],
             false,
             false,
             false,
         );
         check_classic_frame(
-            transport.can.sent_frames[4],
+            transport.can.sent_frames.borrow()[4],
             [28, 29, 30, 31, 32, 33, 34],
             false,
             false,
             true,
         );
         check_classic_frame(
-            transport.can.sent_frames[5],
+            transport.can.sent_frames.borrow()[5],
             [35, 36, 37, 38, 39, 40, 41],
             false,
             false,
             false,
         );
         check_classic_frame(
-            transport.can.sent_frames[6],
+            transport.can.sent_frames.borrow()[6],
             [42, 43, 44, 45, 46, 47, 48],
             false,
             false,
             true,
         );
         check_classic_frame(
-            transport.can.sent_frames[7],
+            transport.can.sent_frames.borrow()[7],
             [49, 50, 51, 52, 53, 54, 55],
             false,
             false,
             false,
         );
         check_classic_frame(
-            transport.can.sent_frames[8],
+            transport.can.sent_frames.borrow()[8],
             [56, 57, 58, 59, 60, 61, 62],
             false,
             false,
             true,
         );
 
-        assert_eq!(transport.can.sent_frames[9].dlc, 5);
-        assert_eq!(transport.can.sent_frames[9].data[0], 63);
-        assert_eq!(transport.can.sent_frames[9].data[1], 64);
-        assert_eq!(transport.can.sent_frames[9].data[2], checksum[0]);
-        assert_eq!(transport.can.sent_frames[9].data[3], checksum[1]);
+        assert_eq!(transport.can.sent_frames.borrow()[9].dlc, 5);
+        assert_eq!(transport.can.sent_frames.borrow()[9].data[0], 63);
+        assert_eq!(transport.can.sent_frames.borrow()[9].data[1], 64);
+        assert_eq!(transport.can.sent_frames.borrow()[9].data[2], checksum[0]);
+        assert_eq!(transport.can.sent_frames.borrow()[9].data[3], checksum[1]);
 
-        let tail_byte = transport.can.sent_frames[9].data[4];
-        assert_eq!(tail_byte & 0x80 > 0, false);
-        assert_eq!(tail_byte & 0x40 > 0, true);
-        assert_eq!(tail_byte & 0x20 > 0, false);
+        let tail_byte = transport.can.sent_frames.borrow()[9].data[4];
+        assert_eq!(tail_byte & 0x80, 0);
+        assert!(tail_byte & 0x40 > 0);
+        assert_eq!(tail_byte & 0x20, 0);
     }
 
     #[async_std::test]
     async fn transmit_large_message_fd() {
-        let can = TestCanFd {
-            sent_frames: Vec::new(),
-        };
-        let mut transport = CanTransport::new(can).expect("Could not create transport");
+        let can = TestCanFd::default();
+        let transport =
+            CanTransport::new(can, TestClock::default()).expect("Could not create transport");
 
         let data: Vec<u8> = (0..65).collect();
         let data: [u8; 65] = data.try_into().unwrap();
         let checksum = CRC16.checksum(&data).to_be_bytes();
 
-        let message =
-            TestLargeMessage::new(Priority::Nominal, 1.try_into().unwrap(), None, data).unwrap();
+        let message = TestLargeMessage::new(Priority::Nominal, 1, Some(5), data).unwrap();
         transport.publish(&message).await.unwrap();
 
-        assert_eq!(transport.can.sent_frames.len(), 2);
+        assert_eq!(transport.can.sent_frames.borrow().len(), 2);
 
         let data: Vec<u8> = (0..63).collect();
         let data: [u8; 63] = data.try_into().unwrap();
-        check_fd_frame(transport.can.sent_frames[0], data, true, false, true);
+        check_fd_frame(
+            transport.can.sent_frames.borrow()[0],
+            data,
+            true,
+            false,
+            true,
+        );
 
-        assert_eq!(transport.can.sent_frames[1].dlc, 5);
-        assert_eq!(transport.can.sent_frames[1].data[2], checksum[0]);
-        assert_eq!(transport.can.sent_frames[1].data[3], checksum[1]);
+        assert_eq!(transport.can.sent_frames.borrow()[1].dlc, 5);
+        assert_eq!(transport.can.sent_frames.borrow()[1].data[2], checksum[0]);
+        assert_eq!(transport.can.sent_frames.borrow()[1].data[3], checksum[1]);
 
-        let tail_byte = transport.can.sent_frames[1].data[4];
-        assert_eq!(tail_byte & 0x80 > 0, false);
-        assert_eq!(tail_byte & 0x40 > 0, true);
-        assert_eq!(tail_byte & 0x20 > 0, false);
+        let tail_byte = transport.can.sent_frames.borrow()[1].data[4];
+        assert_eq!(tail_byte & 0x80, 0);
+        assert!(tail_byte & 0x40 > 0);
+        assert_eq!(tail_byte & 0x20, 0);
     }
 
     #[async_std::test]
     async fn test_invoque() {
-        let can = TestCan {
-            sent_frames: Vec::new(),
-            receive_fn: || {
-                let id = ServiceCanId::new(
-                    Priority::Nominal,
-                    false,
-                    1.try_into().unwrap(),
-                    3.try_into().unwrap(),
-                    2.try_into().unwrap(),
-                )
-                .unwrap();
-                let frame = TestFrame::new(id, &[1, 2, 0, 0, 0, 0, 0, 0xE1]).unwrap();
-
-                Ok(frame)
-            },
-        };
-        let mut transport = CanTransport::new(can).expect("Could not create transport");
+        let can = TestCan::new(VecDeque::from([TestFrame::new(
+            ServiceCanId::new(
+                Priority::Nominal,
+                false,
+                1.try_into().unwrap(),
+                2.try_into().unwrap(),
+                3.try_into().unwrap(),
+            )
+            .unwrap(),
+            &[1, 2, 0, 0, 0, 0, 0, 0xE1],
+        )
+        .unwrap()]));
+        let transport =
+            CanTransport::new(can, TestClock::default()).expect("Could not create transport");
 
         let data: Vec<u8> = (0..TEST_REQUEST_SIZE as u8).collect();
         let data: [u8; TEST_REQUEST_SIZE] = data.try_into().unwrap();
@@ -647,10 +835,307 @@ mod test {
             data,
         )
         .unwrap();
-        let response = transport.invoque(&request).await.unwrap();
+        let response = transport
+            .invoque(&request, Duration::from_secs(1))
+            .await
+            .unwrap();
 
-        assert_eq!(transport.can.sent_frames.len(), 1);
+        assert_eq!(transport.can.sent_frames.borrow().len(), 1);
         assert_eq!(response.data()[0], 1);
         assert_eq!(response.data()[1], 2);
     }
+
+    #[async_std::test]
+    async fn receive_large_message() {
+        let data: Vec<u8> = (0..LARGE_MESSAGE_SIZE as u8).collect();
+        let data: [u8; LARGE_MESSAGE_SIZE] = data.try_into().unwrap();
+        let frames = large_message_frames(data).await;
+
+        let (transport, router) = serve(frames).await;
+
+        let messages = router.messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0], (1, Some(5), data.to_vec()));
+        assert_eq!(transport.rejected_transfers().total(), 0);
+    }
+
+    #[async_std::test]
+    async fn receive_single_frame_message() {
+        let id = MessageCanId::new(Priority::Nominal, 1, Some(5)).unwrap();
+        let frame = TestFrame::new(id, &[1, 2, 0xE0]).unwrap();
+
+        let (transport, router) = serve(VecDeque::from([frame])).await;
+
+        let messages = router.messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0], (1, Some(5), [1, 2].to_vec()));
+        assert_eq!(transport.rejected_transfers().total(), 0);
+    }
+
+    #[async_std::test]
+    async fn reject_crc_mismatch() {
+        let data: [u8; LARGE_MESSAGE_SIZE] = [7; LARGE_MESSAGE_SIZE];
+        let mut frames = large_message_frames(data).await;
+        frames[3].data[2] ^= 0x01;
+
+        let (transport, router) = serve(frames).await;
+
+        assert!(router.messages.borrow().is_empty());
+        assert_eq!(transport.rejected_transfers().crc_mismatch(), 1);
+        assert_eq!(transport.rejected_transfers().malformed(), 0);
+    }
+
+    #[async_std::test]
+    async fn reject_missing_frame() {
+        let data: [u8; LARGE_MESSAGE_SIZE] = [7; LARGE_MESSAGE_SIZE];
+        let mut frames = large_message_frames(data).await;
+        frames.remove(4);
+
+        let (transport, router) = serve(frames).await;
+
+        assert!(router.messages.borrow().is_empty());
+        assert_eq!(transport.rejected_transfers().malformed(), 1);
+    }
+
+    #[async_std::test]
+    async fn reject_empty_frame() {
+        let id = MessageCanId::new(Priority::Nominal, 1, Some(5)).unwrap();
+        let frame = TestFrame::new(id, &[]).unwrap();
+
+        let (transport, router) = serve(VecDeque::from([frame])).await;
+
+        assert!(router.messages.borrow().is_empty());
+        assert_eq!(transport.rejected_transfers().malformed(), 1);
+    }
+
+    #[async_std::test]
+    async fn respond_to_request() {
+        let id = ServiceCanId::new(Priority::High, true, 1, 3, 2).unwrap();
+        let frame = TestFrame::new(id, &[0xE9]).unwrap();
+
+        let (transport, router) = serve(VecDeque::from([frame])).await;
+
+        assert_eq!(router.requests.borrow().len(), 1);
+        assert_eq!(transport.can.sent_frames.borrow().len(), 1);
+
+        let frame = transport.can.sent_frames.borrow()[0];
+        match frame.id() {
+            CanId::Service(id) => {
+                assert!(!id.is_request());
+                assert_eq!(id.priority(), Priority::High);
+                assert_eq!(id.service(), 1);
+                assert_eq!(id.source(), 2);
+                assert_eq!(id.destination(), 3);
+            }
+            CanId::Message(_) => panic!("Expected a response"),
+        }
+        assert_eq!(&frame.data[..TEST_RESPONSE_SIZE], &[7; TEST_RESPONSE_SIZE]);
+        assert!(frame.is_single_trame_transfer());
+        assert_eq!(frame.transfer(), CanTransferId::new(9).unwrap());
+    }
+
+    #[async_std::test]
+    async fn transfer_per_subject() {
+        let can = TestCan::default();
+        let transport = CanTransport::new(can, TestClock::default()).unwrap();
+
+        for subject in [1, 2, 1] {
+            let message = TestSmallMessage::new(Priority::Nominal, subject, None, [1, 2]).unwrap();
+            transport.publish(&message).await.unwrap();
+        }
+
+        let transfers: Vec<CanTransferId> = transport
+            .can
+            .sent_frames
+            .borrow()
+            .iter()
+            .map(|f| f.transfer())
+            .collect();
+        assert_eq!(
+            transfers,
+            [1, 1, 2].map(|t| CanTransferId::new(t).unwrap()).to_vec()
+        );
+    }
+
+    #[async_std::test]
+    async fn invoque_timeout() {
+        // unrelated traffic keeps coming while the server doesn't answer
+        let id = MessageCanId::new(Priority::Nominal, 1, Some(5)).unwrap();
+        let frames: VecDeque<TestFrame> = (0..10)
+            .map(|t| TestFrame::new(id, &[1, 0xE0 | t]).unwrap())
+            .collect();
+
+        let can = TestCan::new(frames);
+        let clock = TestClock {
+            now: Cell::new(Duration::ZERO),
+            step: Duration::from_millis(400),
+        };
+        let transport = CanTransport::new(can, clock).unwrap();
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, []).unwrap();
+        let result = transport.invoque(&request, Duration::from_secs(1)).await;
+
+        assert_eq!(result.err(), Some(CyphalError::Timeout));
+        assert!(!transport.can.received_frames.borrow().is_empty());
+    }
+
+    #[async_std::test]
+    async fn invoque_ignores_other_responses() {
+        // response to another service with the same transfer ID
+        let other = ServiceCanId::new(Priority::Nominal, false, 4, 2, 3).unwrap();
+        let id = ServiceCanId::new(Priority::Nominal, false, 1, 2, 3).unwrap();
+        let frames = VecDeque::from([
+            TestFrame::new(other, &[9, 9, 0xE1]).unwrap(),
+            TestFrame::new(id, &[1, 2, 0xE1]).unwrap(),
+        ]);
+
+        let can = TestCan::new(frames);
+        let transport = CanTransport::new(can, TestClock::default()).unwrap();
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, []).unwrap();
+        let response = transport
+            .invoque(&request, Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data(), &[1, 2]);
+    }
+
+    #[async_std::test]
+    async fn invoque_drops_unserved_transfers() {
+        let message = MessageCanId::new(Priority::Nominal, 1, Some(5)).unwrap();
+        let request = ServiceCanId::new(Priority::Nominal, true, 1, 5, 2).unwrap();
+        let response = ServiceCanId::new(Priority::Nominal, false, 1, 2, 3).unwrap();
+        let frames = VecDeque::from([
+            TestFrame::new(message, &[3, 0xE0]).unwrap(),
+            TestFrame::new(request, &[4, 0xE0]).unwrap(),
+            TestFrame::new(response, &[1, 2, 0xE1]).unwrap(),
+        ]);
+
+        let transport = CanTransport::new(TestCan::new(frames), TestClock::default()).unwrap();
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, []).unwrap();
+        let response = transport
+            .invoque(&request, Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data(), &[1, 2]);
+        assert!(!transport.inbound_queue.with(|queue| queue.has_incoming()));
+    }
+
+    #[async_std::test]
+    async fn invoque_while_serving() {
+        let can = TestCan::default();
+        can.pending.set(true);
+        let transport = CanTransport::new(can, TestClock::default()).unwrap();
+        let router = TestRouter::new();
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, []).unwrap();
+        let mut serve = pin!(transport.serve(&router));
+        let mut invoque = pin!(transport.invoque(&request, Duration::from_secs(1)));
+
+        let result = poll_fn(|cx| {
+            assert!(serve.as_mut().poll(cx).is_pending());
+            assert!(invoque.as_mut().poll(cx).is_pending());
+
+            // the response and a message are both received by the serving task
+            let id = ServiceCanId::new(Priority::Nominal, false, 1, 2, 3).unwrap();
+            let message = MessageCanId::new(Priority::Nominal, 1, Some(5)).unwrap();
+            transport.can.received_frames.borrow_mut().extend([
+                TestFrame::new(id, &[1, 2, 0xE1]).unwrap(),
+                TestFrame::new(message, &[3, 0xE0]).unwrap(),
+            ]);
+            assert!(serve.as_mut().poll(cx).is_pending());
+
+            Poll::Ready(invoque.as_mut().poll(cx))
+        })
+        .await;
+
+        match result {
+            Poll::Ready(response) => assert_eq!(response.unwrap().data(), &[1, 2]),
+            Poll::Pending => panic!("The response should have been received by the serving task"),
+        }
+        assert_eq!(router.messages.borrow().len(), 1);
+        assert_eq!(transport.can.sent_frames.borrow().len(), 1);
+    }
+
+    #[async_std::test]
+    async fn serve_subscribed_subjects() {
+        // corrupted transfer on a subject nobody subscribed to
+        let mut frames = large_message_frames([7; LARGE_MESSAGE_SIZE]).await;
+        frames[3].data[2] ^= 0x01;
+
+        let id = MessageCanId::new(Priority::Nominal, 2, Some(5)).unwrap();
+        frames.push_back(TestFrame::new(id, &[1, 2, 0xE0]).unwrap());
+
+        let received: RefCell<Vec<TestSmallMessage>> = RefCell::new(Vec::new());
+        let dispatcher = Dispatcher::new().subscribe(2, |message: TestSmallMessage| {
+            received.borrow_mut().push(message);
+            async { Ok(()) }
+        });
+
+        let transport = CanTransport::new(TestCan::new(frames), TestClock::default()).unwrap();
+        transport.serve(&dispatcher).await.unwrap();
+
+        let received = received.borrow();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].subject(), 2);
+        assert_eq!(received[0].source(), Some(5));
+        assert_eq!(received[0].data(), &[1, 2]);
+        assert_eq!(transport.rejected_transfers().total(), 0);
+    }
+
+    #[async_std::test]
+    async fn publish_anonymous_message() {
+        let transport = CanTransport::new(TestCan::default(), TestClock::default()).unwrap();
+
+        let message = TestSmallMessage::new(Priority::Nominal, 1, None, [1, 2]).unwrap();
+        transport.publish(&message).await.unwrap();
+
+        let frame = transport.can.sent_frames.borrow()[0];
+        match frame.id() {
+            CanId::Message(id) => {
+                assert!(id.is_anonymous());
+                let pseudo_id = MessageCanId::new_anonymous(Priority::Nominal, 1, &[1, 2]);
+                assert_eq!(id, pseudo_id.unwrap());
+            }
+            CanId::Service(_) => panic!("Expected a message"),
+        }
+        assert!(frame.is_single_trame_transfer());
+    }
+
+    #[async_std::test]
+    async fn serve_anonymous_message() {
+        let transport = CanTransport::new(TestCan::default(), TestClock::default()).unwrap();
+        let message = TestSmallMessage::new(Priority::Nominal, 1, None, [1, 2]).unwrap();
+        transport.publish(&message).await.unwrap();
+
+        let received: RefCell<Vec<TestSmallMessage>> = RefCell::new(Vec::new());
+        let dispatcher = Dispatcher::new().subscribe(1, |message: TestSmallMessage| {
+            received.borrow_mut().push(message);
+            async { Ok(()) }
+        });
+
+        let frames = transport.can.sent_frames.into_inner().into();
+        let transport = CanTransport::new(TestCan::new(frames), TestClock::default()).unwrap();
+        transport.serve(&dispatcher).await.unwrap();
+
+        let received = received.borrow();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].source(), None);
+        assert_eq!(received[0].data(), &[1, 2]);
+    }
+
+    #[async_std::test]
+    async fn reject_anonymous_multi_frame_message() {
+        let transport = CanTransport::new(TestCan::default(), TestClock::default()).unwrap();
+
+        let message =
+            TestLargeMessage::new(Priority::Nominal, 1, None, [0; LARGE_MESSAGE_SIZE]).unwrap();
+        let result = transport.publish(&message).await;
+
+        assert_eq!(result, Err(CyphalError::OutOfRange));
+        assert!(transport.can.sent_frames.borrow().is_empty());
+    }
 }
@@ -0,0 +1,86 @@
+extern crate alloc;
+
+use crate::CanTransferId;
+use alloc::collections::BTreeMap;
+use cyphal::{NodeId, ServiceId, SubjectId, TransferId};
+
+/// Keeps the transfer ID counters of the outgoing sessions.
+///
+/// Each subject, and each service and destination pair, has its own counter so that receivers
+/// don't mistake mixed traffic for lost transfers.
+pub struct SessionTable {
+    messages: BTreeMap<SubjectId, CanTransferId>,
+    requests: BTreeMap<(ServiceId, NodeId), CanTransferId>,
+}
+
+impl SessionTable {
+    pub fn new() -> Self {
+        Self {
+            messages: BTreeMap::new(),
+            requests: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the transfer ID of the next message published on the subject
+    pub fn next_message_transfer(&mut self, subject: SubjectId) -> CanTransferId {
+        let transfer = self.messages.entry(subject).or_default();
+        *transfer = transfer.next();
+
+        *transfer
+    }
+
+    /// Returns the transfer ID of the next request sent to the service of the destination node
+    pub fn next_request_transfer(
+        &mut self,
+        service: ServiceId,
+        destination: NodeId,
+    ) -> CanTransferId {
+        let transfer = self.requests.entry((service, destination)).or_default();
+        *transfer = transfer.next();
+
+        *transfer
+    }
+}
+
+impl Default for SessionTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SessionTable;
+    use cyphal::TransferId;
+
+    #[test]
+    fn test_message_transfers() {
+        let mut table = SessionTable::new();
+
+        assert_eq!(table.next_message_transfer(1).value(), 1);
+        assert_eq!(table.next_message_transfer(1).value(), 2);
+        assert_eq!(table.next_message_transfer(2).value(), 1);
+        assert_eq!(table.next_message_transfer(1).value(), 3);
+    }
+
+    #[test]
+    fn test_request_transfers() {
+        let mut table = SessionTable::new();
+
+        assert_eq!(table.next_request_transfer(1, 10).value(), 1);
+        assert_eq!(table.next_request_transfer(1, 10).value(), 2);
+        assert_eq!(table.next_request_transfer(1, 11).value(), 1);
+        assert_eq!(table.next_request_transfer(2, 10).value(), 1);
+        assert_eq!(table.next_message_transfer(1).value(), 1);
+    }
+
+    #[test]
+    fn test_transfer_rollover() {
+        let mut table = SessionTable::new();
+
+        for _ in 0..31 {
+            table.next_message_transfer(1);
+        }
+        assert_eq!(table.next_message_transfer(1).value(), 0);
+    }
+}
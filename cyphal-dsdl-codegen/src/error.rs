@@ -0,0 +1,33 @@
+use cyphal_dsdl::{DsdlError, Span};
+use thiserror::Error;
+
+/// Represents a code generation error
+#[derive(Error, Debug)]
+pub enum CodegenError {
+    /// DSDL error
+    #[error("Could not parse `{0}`: {1}")]
+    Dsdl(String, DsdlError),
+
+    /// A DSDL name that can't be used in the generated Rust code
+    #[error("`{0}` can't be used in the generated code: {1}")]
+    InvalidName(String, String),
+
+    /// IO error
+    #[error("IO error: `{0}`")]
+    Io(#[from] std::io::Error),
+
+    /// A composite type refers to a type that could not be found
+    #[error("{1}: Unresolved reference to `{0}`")]
+    UnresolvedReference(String, Span),
+
+    /// The extent of a type depends on composite types that weren't resolved
+    #[error("The extent of `{0}` depends on types that weren't resolved")]
+    UnknownExtent(String),
+
+    /// An expression can't be translated to Rust
+    #[error("Unsupported expression: `{0}`")]
+    UnsupportedExpression(String),
+}
+
+/// Represents a code generation result
+pub type CodegenResult<T> = Result<T, CodegenError>;
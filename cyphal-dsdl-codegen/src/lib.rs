@@ -0,0 +1,14 @@
+#![doc = include_str!("../README.md")]
+#![forbid(missing_docs)]
+
+mod error;
+pub use error::{CodegenError, CodegenResult};
+
+mod namespace;
+pub use namespace::{build, NamespaceGenerator};
+
+#[cfg(test)]
+pub(crate) mod test;
+
+mod type_generator;
+pub use type_generator::{identifier, module_name, TypeGenerator};
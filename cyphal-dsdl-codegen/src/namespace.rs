@@ -0,0 +1,330 @@
+use crate::{identifier, module_name, CodegenError, CodegenResult, TypeGenerator};
+use cyphal_dsdl::{Diagnostic, DsdlError, File, Parser};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+const GENERATED_HEADER: &str = "// Generated by cyphal-dsdl-codegen, do not edit\n\n";
+const BUILD_FILE_NAME: &str = "dsdl.rs";
+
+/// Generates the Rust modules of one or more root namespaces
+pub struct NamespaceGenerator {
+    roots: Vec<PathBuf>,
+    diagnostics: RefCell<Vec<Diagnostic>>,
+}
+
+// The generated code of a type along with the module it belongs to
+struct GeneratedType {
+    namespace: Vec<String>,
+    module: String,
+    code: String,
+}
+
+impl NamespaceGenerator {
+    /// Constructs a generator for the given root namespace directories
+    pub fn new(roots: Vec<PathBuf>) -> CodegenResult<Self> {
+        Ok(Self {
+            roots,
+            diagnostics: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Returns the diagnostics reported while reading the DSDL files of the last generation, such
+    /// as deprecated types and the output of @print directives
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.borrow().clone()
+    }
+
+    /// Writes a module tree that mirrors the namespaces, with a `mod.rs` per level, in the output
+    /// directory and returns the DSDL files that were read
+    pub fn generate(&self, output: &Path) -> CodegenResult<Vec<PathBuf>> {
+        let (types, paths) = self.generate_types()?;
+
+        let mut modules: BTreeMap<Vec<String>, BTreeSet<String>> = BTreeMap::new();
+        for generated in types {
+            let directory = namespace_path(output, &generated.namespace);
+            fs::create_dir_all(&directory)?;
+            fs::write(
+                directory.join(format!("{}.rs", generated.module)),
+                generated.code,
+            )?;
+
+            add_module(&mut modules, &generated.namespace, generated.module);
+        }
+
+        for (namespace, children) in modules {
+            let mut code = GENERATED_HEADER.to_string();
+            for child in children {
+                code.push_str(&format!("pub mod {};\n", identifier(&child)));
+            }
+
+            fs::write(namespace_path(output, &namespace).join("mod.rs"), code)?;
+        }
+
+        Ok(paths)
+    }
+
+    /// Writes all the namespaces as inline modules of a single file, which can be included with
+    /// the `include!` macro, and returns the DSDL files that were read
+    pub fn generate_file(&self, output: &Path) -> CodegenResult<Vec<PathBuf>> {
+        let (types, paths) = self.generate_types()?;
+
+        let mut modules: BTreeMap<Vec<String>, BTreeSet<String>> = BTreeMap::new();
+        let mut codes: HashMap<(Vec<String>, String), String> = HashMap::new();
+        for generated in types {
+            add_module(&mut modules, &generated.namespace, generated.module.clone());
+            codes.insert((generated.namespace, generated.module), generated.code);
+        }
+
+        let mut code = GENERATED_HEADER.to_string();
+        write_inline_module(&mut code, &Vec::new(), &modules, &codes);
+
+        if let Some(directory) = output.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(output, code)?;
+
+        Ok(paths)
+    }
+
+    fn generate_types(&self) -> CodegenResult<(Vec<GeneratedType>, Vec<PathBuf>)> {
+        let mut parser = Parser::new().map_err(|e| CodegenError::Dsdl(String::new(), e))?;
+        for root in self.roots.iter() {
+            parser
+                .add_root(root)
+                .map_err(|e| CodegenError::Dsdl(root.display().to_string(), e))?;
+        }
+        parser.parse_roots().map_err(|e| match e {
+            DsdlError::UnresolvedType(name, span) => CodegenError::UnresolvedReference(name, span),
+            DsdlError::InFile(path, e) => CodegenError::Dsdl(path, *e),
+            DsdlError::InvalidStatement(span, message) => CodegenError::Dsdl(
+                span.path().to_string(),
+                DsdlError::InvalidStatement(span, message),
+            ),
+            e => CodegenError::Dsdl(String::new(), e),
+        })?;
+
+        let mut files: Vec<&File> = parser.files().collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        *self.diagnostics.borrow_mut() = files
+            .iter()
+            .flat_map(|f| f.diagnostics().iter().cloned())
+            .collect();
+
+        let mut types = Vec::new();
+        let mut paths = Vec::new();
+        let mut modules = HashSet::new();
+        for file in files {
+            let namespace = file.namespace().to_vec();
+            let module = module_name(file.name(), file.major(), file.minor());
+            // names differing only by their case or underscores, e.g. `FooBar` and `Foo_Bar`
+            if !modules.insert((namespace.clone(), module.clone())) {
+                return Err(CodegenError::InvalidName(
+                    file.full_name(),
+                    format!("another type of the namespace has the `{}` module", module),
+                ));
+            }
+
+            let generator = TypeGenerator::new(file, None, namespace.clone())?;
+            types.push(GeneratedType {
+                namespace,
+                module,
+                code: generator.generate_code()?,
+            });
+            paths.push(PathBuf::from(file.path()));
+        }
+
+        Ok((types, paths))
+    }
+}
+
+/// Generates the Rust code of the root namespaces from a build script.
+///
+/// The code is written to a `dsdl.rs` file in `out_dir`, whose path is returned, and cargo is
+/// asked to run the build script again whenever one of the DSDL files changes.
+pub fn build<P: AsRef<Path>>(roots: &[P], out_dir: &Path) -> CodegenResult<PathBuf> {
+    let roots = roots.iter().map(|r| r.as_ref().to_path_buf()).collect();
+    let generator = NamespaceGenerator::new(roots)?;
+    let output = out_dir.join(BUILD_FILE_NAME);
+
+    for root in generator.roots.iter() {
+        println!("cargo:rerun-if-changed={}", root.display());
+    }
+    for path in generator.generate_file(&output)? {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    for diagnostic in generator.diagnostics() {
+        println!("cargo:warning={}", diagnostic);
+    }
+
+    Ok(output)
+}
+
+fn namespace_path(output: &Path, namespace: &[String]) -> PathBuf {
+    let mut path = output.to_path_buf();
+    for component in namespace {
+        path.push(component);
+    }
+
+    path
+}
+
+// Adds a module to its namespace and the namespace to its parents
+fn add_module(
+    modules: &mut BTreeMap<Vec<String>, BTreeSet<String>>,
+    namespace: &[String],
+    module: String,
+) {
+    modules
+        .entry(namespace.to_vec())
+        .or_default()
+        .insert(module);
+    for i in 0..namespace.len() {
+        modules
+            .entry(namespace[..i].to_vec())
+            .or_default()
+            .insert(namespace[i].clone());
+    }
+}
+
+fn write_inline_module(
+    code: &mut String,
+    namespace: &Vec<String>,
+    modules: &BTreeMap<Vec<String>, BTreeSet<String>>,
+    codes: &HashMap<(Vec<String>, String), String>,
+) {
+    let indent = "    ".repeat(namespace.len());
+    for child in modules.get(namespace).into_iter().flatten() {
+        code.push_str(&format!("{}pub mod {} {{\n", indent, identifier(child)));
+
+        let key = (namespace.clone(), child.clone());
+        match codes.get(&key) {
+            Some(type_code) => {
+                for line in type_code.lines() {
+                    if line.is_empty() {
+                        code.push('\n');
+                    } else {
+                        code.push_str(&format!("{}    {}\n", indent, line));
+                    }
+                }
+            }
+            None => {
+                let mut namespace = namespace.clone();
+                namespace.push(child.clone());
+                write_inline_module(code, &namespace, modules, codes);
+            }
+        }
+
+        code.push_str(&format!("{}}}\n", indent));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{build, test::TestDir, CodegenError, NamespaceGenerator};
+    use std::fs;
+
+    #[test]
+    fn test_generate_namespace() {
+        let directory = TestDir::with_files(&[
+            ("uavcan/node/Health.1.0.dsdl", "uint2 value\n@sealed\n"),
+            (
+                "uavcan/node/7509.Heartbeat.1.0.dsdl",
+                "uint32 uptime\nHealth.1.0 health\n@extent 12 * 8\n",
+            ),
+            (
+                "uavcan/diagnostic/Record.1.0.dsdl",
+                "uavcan.node.Health.1.0 health\n@sealed\n",
+            ),
+        ]);
+        let output = directory.join("output");
+
+        let generator = NamespaceGenerator::new(vec![directory.join("uavcan")]).unwrap();
+        let files = generator.generate(&output).unwrap();
+
+        assert_eq!(files.len(), 3);
+        assert_eq!(
+            fs::read_to_string(output.join("mod.rs")).unwrap(),
+            "// Generated by cyphal-dsdl-codegen, do not edit\n\npub mod uavcan;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("uavcan/mod.rs")).unwrap(),
+            "// Generated by cyphal-dsdl-codegen, do not edit\n\npub mod diagnostic;\npub mod node;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("uavcan/node/mod.rs")).unwrap(),
+            "// Generated by cyphal-dsdl-codegen, do not edit\n\npub mod health_1_0;\npub mod heartbeat_1_0;\n"
+        );
+
+        let record = fs::read_to_string(output.join("uavcan/diagnostic/record_1_0.rs")).unwrap();
+        assert!(
+            record.contains("pub health: super::super::super::uavcan::node::health_1_0::Health,")
+        );
+        let heartbeat = fs::read_to_string(output.join("uavcan/node/heartbeat_1_0.rs")).unwrap();
+        assert!(heartbeat.contains("pub health: super::health_1_0::Health,"));
+    }
+
+    #[test]
+    fn test_unresolved_reference() {
+        let directory = TestDir::with_files(&[(
+            "uavcan/node/Status.1.0.dsdl",
+            "Health.1.0 health\n@sealed\n",
+        )]);
+
+        let generator = NamespaceGenerator::new(vec![directory.join("uavcan")]).unwrap();
+        let result = generator.generate(&directory.join("output"));
+
+        assert!(matches!(
+            result,
+            Err(CodegenError::UnresolvedReference(name, _)) if name == "uavcan.node.Health.1.0"
+        ));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let directory = TestDir::with_files(&[
+            (
+                "uavcan/node/Health.1.0.dsdl",
+                "@deprecated\nuint2 value\n@sealed\n",
+            ),
+            (
+                "uavcan/node/Mode.1.0.dsdl",
+                "uint3 value\n@print _offset_\n@sealed\n",
+            ),
+        ]);
+
+        let generator = NamespaceGenerator::new(vec![directory.join("uavcan")]).unwrap();
+        assert!(generator.generate(&directory.join("output")).is_ok());
+
+        let diagnostics = generator.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message(), "Health.1.0 is deprecated");
+        assert_eq!(diagnostics[1].message(), "{3}");
+    }
+
+    #[test]
+    fn test_build() {
+        let directory = TestDir::with_files(&[
+            ("uavcan/node/Health.1.0.dsdl", "uint2 value\n@sealed\n"),
+            ("uavcan/node/Mode.1.0.dsdl", "uint3 value\n@sealed\n"),
+        ]);
+        let out_dir = directory.join("out");
+
+        let result = build(&[directory.join("uavcan")], &out_dir);
+        assert!(result.is_ok());
+
+        let path = result.unwrap();
+        assert_eq!(path, out_dir.join("dsdl.rs"));
+
+        let code = fs::read_to_string(path).unwrap();
+        assert!(code.starts_with(
+            "// Generated by cyphal-dsdl-codegen, do not edit\n\npub mod uavcan {\n    pub mod node {\n        pub mod health_1_0 {\n"
+        ));
+        assert!(code.contains("\n            pub struct Health {\n"));
+        assert!(code.contains("\n        pub mod mode_1_0 {\n"));
+        assert!(code.ends_with("        }\n    }\n}\n"));
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Temporary directory holding the DSDL files of a test, which is removed when dropped
+pub struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    /// Creates an empty directory that no other test uses
+    pub fn new() -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let name = format!(
+            "cyphal-dsdl-test-{}-{}",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        Self { path }
+    }
+
+    /// Creates a directory holding the files, whose paths are relative to the directory
+    pub fn with_files(files: &[(&str, &str)]) -> Self {
+        let directory = Self::new();
+        for (path, source) in files {
+            directory.write(path, source);
+        }
+
+        directory
+    }
+
+    /// Writes a file, whose path is relative to the directory, and returns its full path
+    pub fn write(&self, path: &str, source: &str) -> PathBuf {
+        let path = self.path.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, source).unwrap();
+
+        path
+    }
+
+    /// Returns the full path of a file or directory inside of the directory
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,1292 @@
+use crate::{CodegenError, CodegenResult};
+use cyphal_dsdl::{
+    ArrayCapacity, ArrayElement, CastMode, Comment, Directive, File, Primitive, Statement,
+};
+use std::{collections::HashSet, path::Path};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+const RUST_RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+// The items the generated code imports or uses from the prelude, which a type can't be named after
+const USED_ITEMS: &[&str] = &[
+    "BitReader",
+    "BitWriter",
+    "CastMode",
+    "CyphalResult",
+    "DataType",
+    "Err",
+    "Message",
+    "NodeId",
+    "None",
+    "Ok",
+    "Option",
+    "Priority",
+    "Request",
+    "Response",
+    "RuntimeError",
+    "RuntimeResult",
+    "ServiceId",
+    "Some",
+    "SubjectId",
+];
+// The constants of the `DataType` trait, which the constants of a type would shadow
+const DATA_TYPE_CONSTANTS: &[&str] = &["EXTENT", "SEALED"];
+
+/// Generates the Rust code of a DSDL type
+pub struct TypeGenerator<'a> {
+    file: &'a File,
+    name: String,
+    namespace: Vec<String>,
+}
+
+enum FieldKind {
+    Bool,
+    Uint(u8, CastMode),
+    Int(u8),
+    Float(u8, CastMode),
+    Void(u8),
+    Composite(String),
+    Array(Box<FieldKind>, ArrayCapacity),
+}
+
+struct Field {
+    name: Option<String>,
+    kind: FieldKind,
+    doc: Vec<String>,
+}
+
+struct Constant {
+    name: String,
+    rust_type: &'static str,
+    value: String,
+    doc: Vec<String>,
+}
+
+enum DocTarget {
+    None,
+    Field(usize),
+    Constant(usize),
+}
+
+#[derive(Default)]
+struct Definition {
+    doc: Vec<String>,
+    fields: Vec<Field>,
+    constants: Vec<Constant>,
+    sealed: bool,
+    extent: u64,
+    union: bool,
+}
+
+impl<'a> TypeGenerator<'a> {
+    /// Constructs a generator for a file located in the given namespace
+    pub fn new(
+        file: &'a File,
+        name: Option<String>,
+        namespace: Vec<String>,
+    ) -> CodegenResult<Self> {
+        let name = match name {
+            Some(n) => n,
+            None => file.name().to_string(),
+        };
+
+        let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(CodegenError::InvalidName(
+                name,
+                "it isn't a Rust identifier".to_string(),
+            ));
+        }
+        if RUST_KEYWORDS.contains(&name.as_str()) || RUST_RESERVED.contains(&name.as_str()) {
+            return Err(CodegenError::InvalidName(
+                name,
+                "it is a Rust keyword".to_string(),
+            ));
+        }
+        if USED_ITEMS.contains(&name.as_str()) {
+            return Err(CodegenError::InvalidName(
+                name,
+                "it is already used by the generated code".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            file,
+            name,
+            namespace,
+        })
+    }
+
+    /// Generates the code of the type and, if it has a fixed port ID, of its message or of the
+    /// request and response of its service
+    pub fn generate_code(&self) -> CodegenResult<String> {
+        let definitions = match (
+            self.file.message(),
+            self.file.request(),
+            self.file.response(),
+        ) {
+            (Some(message), _, _) => vec![(self.name.clone(), self.definition(message)?)],
+            (None, Some(request), Some(response)) => vec![
+                (format!("{}Request", self.name), self.definition(request)?),
+                (format!("{}Response", self.name), self.definition(response)?),
+            ],
+            _ => Vec::new(),
+        };
+        for (_, definition) in definitions.iter() {
+            check_names(definition)?;
+        }
+        let uses_cast_mode = definitions
+            .iter()
+            .flat_map(|(_, d)| d.fields.iter())
+            .any(|f| uses_cast_mode(&f.kind));
+        let has_union = definitions.iter().any(|(_, d)| d.union);
+
+        let mut code = String::new();
+        let source = Path::new(self.file.path())
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        code.push_str(&format!(
+            "// Generated from {} by cyphal-dsdl-codegen, do not edit\n\n",
+            source
+        ));
+
+        if self.file.port().is_some() {
+            if self.file.is_service() {
+                code.push_str(
+                    "use cyphal::{CyphalResult, NodeId, Priority, Request, Response, ServiceId};\n",
+                );
+            } else {
+                code.push_str(
+                    "use cyphal::{CyphalResult, Message, NodeId, Priority, SubjectId};\n",
+                );
+            }
+        }
+        let mut imports = vec!["BitReader", "BitWriter"];
+        if uses_cast_mode {
+            imports.push("CastMode");
+        }
+        imports.push("DataType");
+        if has_union {
+            imports.push("RuntimeError");
+        }
+        imports.push("RuntimeResult");
+        code.push_str(&format!(
+            "use cyphal_dsdl_runtime::{{{}}};\n",
+            imports.join(", ")
+        ));
+
+        for (name, definition) in definitions.iter() {
+            self.generate_struct(name, definition, &mut code);
+            self.generate_constants(name, definition, &mut code);
+            self.generate_data_type(name, definition, &mut code);
+        }
+        if let Some(port) = self.file.port() {
+            let template = if self.file.is_service() {
+                SERVICE
+            } else {
+                MESSAGE
+            };
+            code.push_str(
+                &template
+                    .replace("{NAME}", &self.name)
+                    .replace("{PORT}", &port.to_string()),
+            );
+        }
+
+        Ok(code)
+    }
+
+    fn definition(&self, source: cyphal_dsdl::Definition) -> CodegenResult<Definition> {
+        // the extent computed by the parser is used, so that the buffers can't disagree with it
+        let extent = match source.extent_bits() {
+            Some(extent) => extent,
+            None => {
+                return Err(CodegenError::UnknownExtent(format!(
+                    "{}.{}.{}",
+                    self.file.full_name(),
+                    self.file.major(),
+                    self.file.minor()
+                )))
+            }
+        };
+        let mut definition = Definition {
+            extent,
+            ..Default::default()
+        };
+        let mut in_header = true;
+        let mut target = DocTarget::None;
+
+        for statement in source.statements() {
+            match statement {
+                Statement::Comment(text) => {
+                    let line = text.trim().to_string();
+                    if in_header {
+                        definition.doc.push(line);
+                    } else {
+                        match target {
+                            DocTarget::None => {}
+                            DocTarget::Field(i) => definition.fields[i].doc.push(line),
+                            DocTarget::Constant(i) => definition.constants[i].doc.push(line),
+                        }
+                    }
+                }
+                Statement::Empty => {
+                    if in_header {
+                        if !definition.doc.is_empty() {
+                            definition.doc.push(String::new());
+                        }
+                    } else {
+                        target = DocTarget::None;
+                    }
+                }
+                Statement::Primitive(primitive) => {
+                    in_header = false;
+                    target = match self.primitive(primitive) {
+                        Item::Field(field) => {
+                            definition.fields.push(field);
+                            DocTarget::Field(definition.fields.len() - 1)
+                        }
+                        Item::Constant(constant) => {
+                            definition.constants.push(constant);
+                            DocTarget::Constant(definition.constants.len() - 1)
+                        }
+                    };
+                }
+                Statement::Composite(composite) => {
+                    in_header = false;
+                    definition.fields.push(Field {
+                        name: Some(identifier(composite.name().text())),
+                        kind: FieldKind::Composite(self.composite_path(
+                            composite.namespace(),
+                            composite.ctype(),
+                            composite.major(),
+                            composite.minor(),
+                        )),
+                        doc: doc(composite.comment()),
+                    });
+                    target = DocTarget::Field(definition.fields.len() - 1);
+                }
+                Statement::Array(array) => {
+                    in_header = false;
+                    definition.fields.push(Field {
+                        name: Some(identifier(array.name().text())),
+                        kind: FieldKind::Array(
+                            Box::new(self.array_element(array.element())),
+                            array.capacity(),
+                        ),
+                        doc: doc(array.comment()),
+                    });
+                    target = DocTarget::Field(definition.fields.len() - 1);
+                }
+                Statement::Directive(directive) => {
+                    in_header = false;
+                    target = DocTarget::None;
+                    match directive {
+                        Directive::Assert(_) | Directive::Deprecated(_) | Directive::Print(_) => {}
+                        Directive::Extent(extent) => {
+                            if extent.bits().is_none() {
+                                return Err(CodegenError::UnsupportedExpression(
+                                    extent.expression().value().to_string(),
+                                ));
+                            }
+                        }
+                        Directive::Sealed(_) => definition.sealed = true,
+                        Directive::Union(_) => definition.union = true,
+                    }
+                }
+            }
+        }
+
+        while definition.doc.last().is_some_and(|l| l.is_empty()) {
+            definition.doc.pop();
+        }
+
+        Ok(definition)
+    }
+
+    fn primitive(&self, primitive: &Primitive) -> Item {
+        match primitive {
+            Primitive::Bool(p) => match p.value() {
+                Some(v) => Item::constant(p.name().text(), "bool", v.to_string(), p.comment()),
+                None => Item::field(p.name().text(), FieldKind::Bool, p.comment()),
+            },
+            Primitive::Uint(p) => match p.value() {
+                Some(v) => Item::constant(
+                    p.name().text(),
+                    uint_type(p.bits()),
+                    v.to_string(),
+                    p.comment(),
+                ),
+                None => Item::field(
+                    p.name().text(),
+                    FieldKind::Uint(p.bits(), p.cast_mode()),
+                    p.comment(),
+                ),
+            },
+            Primitive::Int(p) => match p.value() {
+                Some(v) => Item::constant(
+                    p.name().text(),
+                    int_type(p.bits()),
+                    v.to_string(),
+                    p.comment(),
+                ),
+                None => Item::field(p.name().text(), FieldKind::Int(p.bits()), p.comment()),
+            },
+            Primitive::Float(p) => match p.value() {
+                Some(v) => Item::constant(
+                    p.name().text(),
+                    float_type(p.bits()),
+                    format!("{:?}", v),
+                    p.comment(),
+                ),
+                None => Item::field(
+                    p.name().text(),
+                    FieldKind::Float(p.bits(), p.cast_mode()),
+                    p.comment(),
+                ),
+            },
+            Primitive::Void(p) => Item::Field(Field {
+                name: None,
+                kind: FieldKind::Void(p.bits()),
+                doc: doc(p.comment()),
+            }),
+        }
+    }
+
+    fn array_element(&self, element: &ArrayElement) -> FieldKind {
+        match element {
+            ArrayElement::Bool => FieldKind::Bool,
+            ArrayElement::Int { bits } => FieldKind::Int(*bits),
+            ArrayElement::Uint { cast_mode, bits } => FieldKind::Uint(*bits, *cast_mode),
+            ArrayElement::Float { cast_mode, bits } => FieldKind::Float(*bits, *cast_mode),
+            ArrayElement::Composite {
+                namespace,
+                ctype,
+                major,
+                minor,
+            } => FieldKind::Composite(self.composite_path(namespace, ctype, *major, *minor)),
+        }
+    }
+
+    // Paths are relative to the generated module, which lives in a module named after the type
+    // inside of its namespace module
+    fn composite_path(&self, namespace: &[String], ctype: &str, major: u8, minor: u8) -> String {
+        let module = module_name(ctype, major, minor);
+        if namespace.is_empty() {
+            return format!("super::{}::{}", module, ctype);
+        }
+
+        let mut path = "super::".repeat(self.namespace.len() + 1);
+        for component in namespace {
+            path.push_str(&identifier(component));
+            path.push_str("::");
+        }
+
+        format!("{}{}::{}", path, module, ctype)
+    }
+
+    fn generate_struct(&self, name: &str, definition: &Definition, code: &mut String) {
+        code.push('\n');
+        push_doc(code, "", &definition.doc);
+        code.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+
+        // the variants of a union become the variants of an enum
+        if definition.union {
+            code.push_str(&format!("pub enum {} {{\n", name));
+            for field in definition.fields.iter() {
+                push_doc(code, "    ", &field.doc);
+                code.push_str(&format!(
+                    "    {}({}),\n",
+                    variant_name(field),
+                    rust_type(&field.kind)
+                ));
+            }
+            code.push_str("}\n");
+            return;
+        }
+
+        let fields: Vec<&Field> = definition
+            .fields
+            .iter()
+            .filter(|f| f.name.is_some())
+            .collect();
+        if fields.is_empty() {
+            code.push_str(&format!("pub struct {} {{}}\n", name));
+            return;
+        }
+
+        code.push_str(&format!("pub struct {} {{\n", name));
+        for field in fields {
+            push_doc(code, "    ", &field.doc);
+            code.push_str(&format!(
+                "    pub {}: {},\n",
+                field.name.as_ref().unwrap(),
+                rust_type(&field.kind)
+            ));
+        }
+        code.push_str("}\n");
+    }
+
+    fn generate_constants(&self, name: &str, definition: &Definition, code: &mut String) {
+        if definition.constants.is_empty() {
+            return;
+        }
+
+        code.push_str(&format!("\nimpl {} {{\n", name));
+        for (i, constant) in definition.constants.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            push_doc(code, "    ", &constant.doc);
+            code.push_str(&format!(
+                "    pub const {}: {} = {};\n",
+                constant.name, constant.rust_type, constant.value
+            ));
+        }
+        code.push_str("}\n");
+    }
+
+    fn generate_data_type(&self, name: &str, definition: &Definition, code: &mut String) {
+        let tag_bits = definition
+            .union
+            .then(|| length_prefix_bits(definition.fields.len() as u64 - 1));
+        let extent = definition.extent / 8;
+
+        code.push_str(&format!("\nimpl DataType for {} {{\n", name));
+        code.push_str(&format!(
+            "    const SEALED: bool = {};\n",
+            definition.sealed
+        ));
+        code.push_str(&format!("    const EXTENT: usize = {};\n\n", extent));
+
+        if let Some(tag_bits) = tag_bits {
+            generate_union_serialization(&definition.fields, tag_bits, code);
+            return;
+        }
+
+        let (writer, reader) = if definition.fields.is_empty() {
+            ("_writer", "_reader")
+        } else {
+            ("writer", "reader")
+        };
+
+        code.push_str(&format!(
+            "    fn serialize(&self, {}: &mut BitWriter) -> RuntimeResult<()> {{\n",
+            writer
+        ));
+        for field in definition.fields.iter() {
+            code.push_str(&format!("        {}?;\n", serialize_field(field)));
+        }
+        code.push_str("        Ok(())\n    }\n\n");
+
+        code.push_str(&format!(
+            "    fn deserialize({}: &mut BitReader) -> RuntimeResult<Self> {{\n",
+            reader
+        ));
+        let mut names = Vec::new();
+        for field in definition.fields.iter() {
+            let value = deserialize_field(field);
+            match &field.name {
+                Some(name) => {
+                    code.push_str(&format!("        let {} = {};\n", name, value));
+                    names.push(name.as_str());
+                }
+                None => code.push_str(&format!("        {};\n", value)),
+            }
+        }
+        if names.is_empty() {
+            code.push_str("        Ok(Self {})\n    }\n}\n");
+        } else {
+            code.push_str(&format!(
+                "        Ok(Self {{ {} }})\n    }}\n}}\n",
+                names.join(", ")
+            ));
+        }
+    }
+}
+
+const MESSAGE: &str = r#"
+/// A `{NAME}` published on its fixed subject ID
+pub struct {NAME}Message {
+    priority: Priority,
+    source: Option<NodeId>,
+    value: {NAME},
+    payload: [u8; {NAME}::EXTENT],
+    size: usize,
+}
+
+impl {NAME}Message {
+    /// Fixed subject ID
+    pub const PORT_ID: SubjectId = {PORT};
+
+    /// Constructs a new message
+    pub fn new(priority: Priority, source: Option<NodeId>, value: {NAME}) -> CyphalResult<Self> {
+        let mut payload = [0; {NAME}::EXTENT];
+        let size = value.serialize_to(&mut payload)?;
+
+        Ok(Self {
+            priority,
+            source,
+            value,
+            payload,
+            size,
+        })
+    }
+
+    /// Returns the content of the message
+    pub fn value(&self) -> &{NAME} {
+        &self.value
+    }
+}
+
+impl Message for {NAME}Message {
+    const SIZE: usize = {NAME}::EXTENT;
+
+    fn new_raw(
+        priority: Priority,
+        _subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let value = {NAME}::deserialize_from(data)?;
+
+        Self::new(priority, source, value)
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn subject(&self) -> SubjectId {
+        Self::PORT_ID
+    }
+
+    fn source(&self) -> Option<NodeId> {
+        self.source
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.payload[..self.size]
+    }
+}
+"#;
+
+const SERVICE: &str = r#"
+/// A `{NAME}Request` sent to its fixed service ID
+pub struct {NAME}ServiceRequest {
+    priority: Priority,
+    source: NodeId,
+    destination: NodeId,
+    value: {NAME}Request,
+    payload: [u8; {NAME}Request::EXTENT],
+    size: usize,
+}
+
+impl {NAME}ServiceRequest {
+    /// Fixed service ID
+    pub const PORT_ID: ServiceId = {PORT};
+
+    /// Constructs a new request
+    pub fn new(
+        priority: Priority,
+        source: NodeId,
+        destination: NodeId,
+        value: {NAME}Request,
+    ) -> CyphalResult<Self> {
+        let mut payload = [0; {NAME}Request::EXTENT];
+        let size = value.serialize_to(&mut payload)?;
+
+        Ok(Self {
+            priority,
+            source,
+            destination,
+            value,
+            payload,
+            size,
+        })
+    }
+
+    /// Returns the content of the request
+    pub fn value(&self) -> &{NAME}Request {
+        &self.value
+    }
+}
+
+impl Request for {NAME}ServiceRequest {
+    const SIZE: usize = {NAME}Request::EXTENT;
+
+    type Response = {NAME}ServiceResponse;
+
+    fn new_raw(
+        priority: Priority,
+        _service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let value = {NAME}Request::deserialize_from(data)?;
+
+        Self::new(priority, source, destination, value)
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn service(&self) -> ServiceId {
+        Self::PORT_ID
+    }
+
+    fn destination(&self) -> NodeId {
+        self.destination
+    }
+
+    fn source(&self) -> NodeId {
+        self.source
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.payload[..self.size]
+    }
+}
+
+/// A `{NAME}Response` returned by its fixed service ID
+pub struct {NAME}ServiceResponse {
+    priority: Priority,
+    source: NodeId,
+    destination: NodeId,
+    value: {NAME}Response,
+    payload: [u8; {NAME}Response::EXTENT],
+    size: usize,
+}
+
+impl {NAME}ServiceResponse {
+    /// Fixed service ID
+    pub const PORT_ID: ServiceId = {PORT};
+
+    /// Constructs a new response
+    pub fn new(
+        priority: Priority,
+        source: NodeId,
+        destination: NodeId,
+        value: {NAME}Response,
+    ) -> CyphalResult<Self> {
+        let mut payload = [0; {NAME}Response::EXTENT];
+        let size = value.serialize_to(&mut payload)?;
+
+        Ok(Self {
+            priority,
+            source,
+            destination,
+            value,
+            payload,
+            size,
+        })
+    }
+
+    /// Returns the content of the response
+    pub fn value(&self) -> &{NAME}Response {
+        &self.value
+    }
+}
+
+impl Response for {NAME}ServiceResponse {
+    const SIZE: usize = {NAME}Response::EXTENT;
+
+    fn new_raw(
+        priority: Priority,
+        _service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let value = {NAME}Response::deserialize_from(data)?;
+
+        Self::new(priority, source, destination, value)
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn service(&self) -> ServiceId {
+        Self::PORT_ID
+    }
+
+    fn destination(&self) -> NodeId {
+        self.destination
+    }
+
+    fn source(&self) -> NodeId {
+        self.source
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.payload[..self.size]
+    }
+}
+"#;
+
+enum Item {
+    Field(Field),
+    Constant(Constant),
+}
+
+impl Item {
+    fn field(name: &str, kind: FieldKind, comment: Option<&Comment>) -> Self {
+        Item::Field(Field {
+            name: Some(identifier(name)),
+            kind,
+            doc: doc(comment),
+        })
+    }
+
+    fn constant(
+        name: &str,
+        rust_type: &'static str,
+        value: String,
+        comment: Option<&Comment>,
+    ) -> Self {
+        Item::Constant(Constant {
+            name: identifier(name),
+            rust_type,
+            value,
+            doc: doc(comment),
+        })
+    }
+}
+
+fn doc(comment: Option<&Comment>) -> Vec<String> {
+    match comment {
+        Some(c) => vec![c.text().trim().to_string()],
+        None => Vec::new(),
+    }
+}
+
+fn push_doc(code: &mut String, indent: &str, doc: &[String]) {
+    for line in doc {
+        if line.is_empty() {
+            code.push_str(&format!("{}///\n", indent));
+        } else {
+            code.push_str(&format!("{}/// {}\n", indent, line));
+        }
+    }
+}
+
+// Checks that the escaped names of the fields, and of the variants of a union, don't clash with
+// each other and that the constants don't shadow the constants of the `DataType` trait
+fn check_names(definition: &Definition) -> CodegenResult<()> {
+    let mut names = HashSet::new();
+    for field in definition.fields.iter() {
+        let name = match field.name.as_deref() {
+            Some(_) if definition.union => variant_name(field),
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        if !names.insert(name.clone()) {
+            return Err(CodegenError::InvalidName(
+                name,
+                "another field has the same name in Rust".to_string(),
+            ));
+        }
+    }
+
+    for constant in definition.constants.iter() {
+        if DATA_TYPE_CONSTANTS.contains(&constant.name.as_str()) {
+            return Err(CodegenError::InvalidName(
+                constant.name.clone(),
+                "it would shadow a constant of the `DataType` trait".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn serialize_field(field: &Field) -> String {
+    let name = field.name.as_deref().unwrap_or_default();
+    serialize_kind(
+        &field.kind,
+        &format!("&self.{}", name),
+        &format!("self.{}", name),
+    )
+}
+
+// Nested types are serialized from a reference to the value, scalars from a copy of it
+fn serialize_kind(kind: &FieldKind, reference: &str, value: &str) -> String {
+    match kind {
+        FieldKind::Composite(_) => format!("writer.write_composite({})", reference),
+        FieldKind::Array(element, capacity)
+            if capacity.is_variable() && matches!(element.as_ref(), FieldKind::Composite(_)) =>
+        {
+            format!("writer.write_composite_vec({})", reference)
+        }
+        FieldKind::Array(element, capacity) => {
+            let method = if capacity.is_variable() {
+                "write_vec"
+            } else {
+                "write_array"
+            };
+            // scalars are copied out of the array, composite types are borrowed
+            let item = match element.as_ref() {
+                FieldKind::Composite(_) => "item",
+                _ => "&item",
+            };
+            format!(
+                "writer.{}({}, |writer, {}| {})",
+                method,
+                reference,
+                item,
+                serialize_value(element, "item")
+            )
+        }
+        kind => serialize_value(kind, value),
+    }
+}
+
+fn serialize_value(kind: &FieldKind, value: &str) -> String {
+    match kind {
+        FieldKind::Bool => format!("writer.write_bool({})", value),
+        FieldKind::Uint(bits, cast_mode) => format!(
+            "writer.write_uint({}{}, {}, {})",
+            value,
+            cast(uint_type(*bits), "u64"),
+            bits,
+            cast_mode_path(*cast_mode)
+        ),
+        FieldKind::Int(bits) => format!(
+            "writer.write_int({}{}, {})",
+            value,
+            cast(int_type(*bits), "i64"),
+            bits
+        ),
+        FieldKind::Float(16, cast_mode) => {
+            format!(
+                "writer.write_f16({}, {})",
+                value,
+                cast_mode_path(*cast_mode)
+            )
+        }
+        FieldKind::Float(bits, _) => format!("writer.write_f{}({})", bits, value),
+        FieldKind::Void(bits) => format!("writer.write_void({})", bits),
+        FieldKind::Composite(_) => format!("writer.write_composite({})", value),
+        FieldKind::Array(..) => unreachable!("arrays can't hold other arrays"),
+    }
+}
+
+fn deserialize_field(field: &Field) -> String {
+    match &field.kind {
+        FieldKind::Composite(_) => "reader.read_composite()?".to_string(),
+        FieldKind::Array(element, capacity)
+            if capacity.is_variable() && matches!(element.as_ref(), FieldKind::Composite(_)) =>
+        {
+            "reader.read_composite_vec()?".to_string()
+        }
+        FieldKind::Array(element, capacity) => {
+            let method = if capacity.is_variable() {
+                "read_vec"
+            } else {
+                "read_array"
+            };
+            let value = match element.as_ref() {
+                FieldKind::Composite(_) => "reader.read_composite()".to_string(),
+                kind => format!("Ok({})", deserialize_value(kind)),
+            };
+            format!("reader.{}(|reader| {})?", method, value)
+        }
+        kind => deserialize_value(kind),
+    }
+}
+
+fn deserialize_value(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::Bool => "reader.read_bool()".to_string(),
+        FieldKind::Uint(bits, _) => format!(
+            "reader.read_uint({}){}",
+            bits,
+            cast("u64", uint_type(*bits))
+        ),
+        FieldKind::Int(bits) => {
+            format!("reader.read_int({}){}", bits, cast("i64", int_type(*bits)))
+        }
+        FieldKind::Float(bits, _) => format!("reader.read_f{}()", bits),
+        FieldKind::Void(bits) => format!("reader.skip({})", bits),
+        FieldKind::Composite(_) | FieldKind::Array(..) => {
+            unreachable!("nested types are deserialized by deserialize_field")
+        }
+    }
+}
+
+// A union is serialized as the index of its variant, followed by the value of the variant
+fn generate_union_serialization(fields: &[Field], tag_bits: u8, code: &mut String) {
+    code.push_str("    fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {\n");
+    code.push_str("        match self {\n");
+    for (tag, field) in fields.iter().enumerate() {
+        code.push_str(&format!(
+            "            Self::{}(value) => {{\n",
+            variant_name(field)
+        ));
+        code.push_str(&format!(
+            "                writer.write_bits({}, {})?;\n",
+            tag, tag_bits
+        ));
+        code.push_str(&format!(
+            "                {}\n",
+            serialize_kind(&field.kind, "value", "*value")
+        ));
+        code.push_str("            }\n");
+    }
+    code.push_str("        }\n    }\n\n");
+
+    code.push_str("    fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {\n");
+    code.push_str(&format!(
+        "        match reader.read_uint({}) {{\n",
+        tag_bits
+    ));
+    for (tag, field) in fields.iter().enumerate() {
+        code.push_str(&format!(
+            "            {} => Ok(Self::{}({})),\n",
+            tag,
+            variant_name(field),
+            deserialize_field(field)
+        ));
+    }
+    code.push_str("            _ => Err(RuntimeError::InvalidTag),\n");
+    code.push_str("        }\n    }\n}\n");
+}
+
+// The variant of a union is named after its field, e.g. `natural16` becomes `Natural16`
+fn variant_name(field: &Field) -> String {
+    let name = field.name.as_deref().unwrap_or_default();
+    let name = name.trim_start_matches("r#").trim_end_matches('_');
+
+    let mut variant = String::new();
+    for word in name.split('_').filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            variant.extend(first.to_uppercase());
+            variant.push_str(chars.as_str());
+        }
+    }
+
+    if variant == "Self" {
+        variant.push('_');
+    }
+
+    variant
+}
+
+// The cast mode is only needed by unsigned integers and float16, including array elements
+fn uses_cast_mode(kind: &FieldKind) -> bool {
+    match kind {
+        FieldKind::Uint(..) | FieldKind::Float(16, _) => true,
+        FieldKind::Array(element, _) => uses_cast_mode(element),
+        _ => false,
+    }
+}
+
+// Variable-length arrays are prefixed by the smallest standard unsigned integer holding their
+// capacity
+fn length_prefix_bits(capacity: u64) -> u8 {
+    match capacity {
+        0..=0xFF => 8,
+        0x100..=0xFFFF => 16,
+        0x1_0000..=0xFFFF_FFFF => 32,
+        _ => 64,
+    }
+}
+
+fn cast_mode_path(cast_mode: CastMode) -> &'static str {
+    match cast_mode {
+        CastMode::Saturated => "CastMode::Saturated",
+        CastMode::Truncated => "CastMode::Truncated",
+    }
+}
+
+fn cast(from: &str, to: &str) -> String {
+    if from == to {
+        String::new()
+    } else {
+        format!(" as {}", to)
+    }
+}
+
+fn rust_type(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::Bool => "bool".to_string(),
+        FieldKind::Uint(bits, _) => uint_type(*bits).to_string(),
+        FieldKind::Int(bits) => int_type(*bits).to_string(),
+        FieldKind::Float(bits, _) => float_type(*bits).to_string(),
+        FieldKind::Void(_) => "()".to_string(),
+        FieldKind::Composite(path) => path.clone(),
+        FieldKind::Array(element, ArrayCapacity::Fixed(n)) => {
+            format!("[{}; {}]", rust_type(element), n)
+        }
+        FieldKind::Array(element, capacity) => {
+            // not imported, as a DSDL type could be named `Vec` as well
+            format!(
+                "cyphal_dsdl_runtime::Vec<{}, {}>",
+                rust_type(element),
+                capacity.max()
+            )
+        }
+    }
+}
+
+fn uint_type(bits: u8) -> &'static str {
+    match bits {
+        0..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        _ => "u64",
+    }
+}
+
+fn int_type(bits: u8) -> &'static str {
+    match bits {
+        0..=8 => "i8",
+        9..=16 => "i16",
+        17..=32 => "i32",
+        _ => "i64",
+    }
+}
+
+// Rust has no stable 16 bits floating point type, so float16 is held in a f32
+fn float_type(bits: u8) -> &'static str {
+    if bits == 64 {
+        "f64"
+    } else {
+        "f32"
+    }
+}
+
+/// Returns the name of the module holding the given version of a type, e.g. `get_info_1_0`
+pub fn module_name(name: &str, major: u8, minor: u8) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut module = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                module.push('_');
+            }
+        }
+        module.extend(c.to_lowercase());
+    }
+
+    format!("{}_{}_{}", module, major, minor)
+}
+
+/// Escapes a DSDL name that isn't a valid Rust identifier
+pub fn identifier(name: &str) -> String {
+    if RUST_RESERVED.contains(&name) {
+        format!("{}_", name)
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{identifier, module_name, test::TestDir, CodegenError, TypeGenerator};
+    use cyphal_dsdl::Parser;
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(module_name("Heartbeat", 1, 0), "heartbeat_1_0");
+        assert_eq!(module_name("GetInfo", 0, 1), "get_info_0_1");
+        assert_eq!(module_name("CANFrame", 2, 3), "can_frame_2_3");
+        assert_eq!(module_name("Real16", 1, 0), "real16_1_0");
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(identifier("uptime"), "uptime");
+        assert_eq!(identifier("type"), "r#type");
+        assert_eq!(identifier("self"), "self_");
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let directory = TestDir::new();
+        let definitions = [
+            ("Clash.1.0.dsdl", "@union\nuint8 fooBar\nuint16 foo_bar\n"),
+            ("Shadow.1.0.dsdl", "uint8 EXTENT = 1\nuint8 value\n"),
+            ("Option.1.0.dsdl", "uint8 value\n"),
+        ];
+
+        for (name, definition) in definitions {
+            let path = directory.write(name, definition);
+
+            let mut parser = Parser::new().unwrap();
+            let file = parser.parse_dsdl(&path).unwrap();
+            let result = TypeGenerator::new(file, None, Vec::new()).and_then(|g| g.generate_code());
+
+            assert!(
+                matches!(result, Err(CodegenError::InvalidName(..))),
+                "`{}` should be rejected",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_extent() {
+        let directory = TestDir::new();
+        let path = directory.write("Status.1.0.dsdl", "Health.1.0 health\n");
+
+        let mut parser = Parser::new().unwrap();
+        let file = parser.parse_dsdl(&path).unwrap();
+        let result = TypeGenerator::new(file, None, Vec::new()).and_then(|g| g.generate_code());
+
+        assert!(matches!(
+            result,
+            Err(CodegenError::UnknownExtent(name)) if name.ends_with("Status.1.0")
+        ));
+    }
+
+    #[test]
+    fn test_generate_message() {
+        let directory = TestDir::new();
+        let path = directory.write("7509.Heartbeat.1.0.dsdl", "# Abstract node status information.\n\nuint16 MAX_PUBLICATION_PERIOD = 1   # [second]\n\nuint32 uptime\nHealth.1.0 health\nvoid3\n\n@extent 12 * 8\n");
+
+        let mut parser = Parser::new().unwrap();
+        let file = parser.parse_dsdl(&path).unwrap();
+        let generator = TypeGenerator::new(file, None, Vec::new()).unwrap();
+        let code = generator.generate_code().unwrap();
+
+        assert!(code.contains("/// Abstract node status information.\n"));
+        assert!(code.contains("pub struct Heartbeat {\n"));
+        assert!(code.contains("    pub uptime: u32,\n"));
+        assert!(code.contains("    pub health: super::health_1_0::Health,\n"));
+        assert!(code.contains("    pub const MAX_PUBLICATION_PERIOD: u16 = 1;\n"));
+        assert!(code.contains("    const SEALED: bool = false;\n"));
+        assert!(code.contains("    const EXTENT: usize = 12;\n"));
+        assert!(code.contains("writer.write_uint(self.uptime as u64, 32, CastMode::Saturated)?;"));
+        assert!(code.contains("writer.write_void(3)?;"));
+        assert!(code.contains("reader.skip(3);"));
+        assert!(code.contains("impl Message for HeartbeatMessage {\n"));
+        assert!(code.contains("    pub const PORT_ID: SubjectId = 7509;\n"));
+    }
+
+    #[test]
+    fn test_generate_sealed_type() {
+        let directory = TestDir::new();
+        let path = directory.write(
+            "Health.1.0.dsdl",
+            "uint2 value\nuint2 NOMINAL = 0\ntruncated float16 ratio\nfloat64 PI = 3.14\n@sealed\n",
+        );
+
+        let mut parser = Parser::new().unwrap();
+        let file = parser.parse_dsdl(&path).unwrap();
+        let generator = TypeGenerator::new(file, Some("Status".to_string()), Vec::new()).unwrap();
+        let code = generator.generate_code().unwrap();
+
+        assert!(code.contains("pub struct Status {\n"));
+        assert!(code.contains("    const SEALED: bool = true;\n"));
+        assert!(code.contains("    const EXTENT: usize = 3;\n"));
+        assert!(code.contains("    pub ratio: f32,\n"));
+        assert!(code.contains("    pub const PI: f64 = 3.14;\n"));
+        assert!(code.contains("writer.write_f16(self.ratio, CastMode::Truncated)?;"));
+        assert!(code.contains("let ratio = reader.read_f16();"));
+        assert!(!code.contains("Message"));
+    }
+
+    #[test]
+    fn test_generate_arrays() {
+        let directory = TestDir::new();
+        directory.write("demo/Health.1.0.dsdl", "uint2 value\n@sealed\n");
+        let path = directory.write("demo/Arrays.1.0.dsdl", "float32[3] xyz\nuint8[<=50] name\nbool[<9] flags\nHealth.1.0[2] health\nbool[2] pair\nHealth.1.0[<=4] history\n@sealed\n");
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("demo")).unwrap();
+        let file = parser.parse_dsdl(&path).unwrap();
+        let generator = TypeGenerator::new(file, None, Vec::new()).unwrap();
+        let code = generator.generate_code().unwrap();
+
+        assert!(code.contains("    pub xyz: [f32; 3],\n"));
+        assert!(code.contains("    pub name: cyphal_dsdl_runtime::Vec<u8, 50>,\n"));
+        assert!(code.contains("    pub flags: cyphal_dsdl_runtime::Vec<bool, 8>,\n"));
+        assert!(code.contains("    pub health: [super::health_1_0::Health; 2],\n"));
+        assert!(code.contains("    const EXTENT: usize = 73;\n"));
+        assert!(code.contains(
+            "writer.write_vec(&self.name, |writer, &item| writer.write_uint(item as u64, 8, CastMode::Saturated))?;"
+        ));
+        assert!(code.contains(
+            "writer.write_array(&self.health, |writer, item| writer.write_composite(item))?;"
+        ));
+        assert!(code.contains("let xyz = reader.read_array(|reader| Ok(reader.read_f32()))?;"));
+        assert!(
+            code.contains("let name = reader.read_vec(|reader| Ok(reader.read_uint(8) as u8))?;")
+        );
+        assert!(code.contains("let health = reader.read_array(|reader| reader.read_composite())?;"));
+        assert!(code.contains("writer.write_composite_vec(&self.history)?;"));
+        assert!(code.contains("let history = reader.read_composite_vec()?;"));
+    }
+
+    #[test]
+    fn test_generate_service() {
+        let directory = TestDir::new();
+        let path = directory.write("430.GetInfo.1.0.dsdl", "# Full node info request.\n@sealed\n---\n# Node info.\nuint8[<=50] name\n@extent 448 * 8\n");
+
+        let mut parser = Parser::new().unwrap();
+        let file = parser.parse_dsdl(&path).unwrap();
+        let generator = TypeGenerator::new(file, None, Vec::new()).unwrap();
+        let code = generator.generate_code().unwrap();
+
+        assert!(code.contains("/// Full node info request.\n#[derive(Debug, Clone, PartialEq)]\npub struct GetInfoRequest {}\n"));
+        assert!(code.contains(
+            "/// Node info.\n#[derive(Debug, Clone, PartialEq)]\npub struct GetInfoResponse {\n"
+        ));
+        assert!(
+            code.contains("impl DataType for GetInfoRequest {\n    const SEALED: bool = true;\n")
+        );
+        assert!(code.contains("impl DataType for GetInfoResponse {\n    const SEALED: bool = false;\n    const EXTENT: usize = 448;\n"));
+        assert!(code.contains("impl Request for GetInfoServiceRequest {\n"));
+        assert!(code.contains("    type Response = GetInfoServiceResponse;\n"));
+        assert!(code.contains("impl Response for GetInfoServiceResponse {\n"));
+        assert!(code.contains("    pub const PORT_ID: ServiceId = 430;\n"));
+        assert!(!code.contains("Message"));
+    }
+
+    #[test]
+    fn test_generate_union() {
+        let directory = TestDir::new();
+        directory.write("demo/Health.1.0.dsdl", "uint2 value\n@sealed\n");
+        let path = directory.write("demo/Value.1.0.dsdl", "# Tagged union.\n@union\nHealth.1.0 health\nbool[<=8] bit\nuint16 natural_16  # number\n");
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("demo")).unwrap();
+        let file = parser.parse_dsdl(&path).unwrap();
+        let generator = TypeGenerator::new(file, None, Vec::new()).unwrap();
+        let code = generator.generate_code().unwrap();
+
+        assert!(code.contains(
+            "use cyphal_dsdl_runtime::{BitReader, BitWriter, CastMode, DataType, RuntimeError, RuntimeResult};\n"
+        ));
+        assert!(code.contains("/// Tagged union.\n#[derive(Debug, Clone, PartialEq)]\npub enum Value {\n    Health(super::health_1_0::Health),\n    Bit(cyphal_dsdl_runtime::Vec<bool, 8>),\n    /// number\n    Natural16(u16),\n}\n"));
+        assert!(code.contains("    const EXTENT: usize = 5;\n"));
+        assert!(code.contains(
+            "            Self::Natural16(value) => {\n                writer.write_bits(2, 8)?;\n                writer.write_uint(*value as u64, 16, CastMode::Saturated)\n            }\n"
+        ));
+        assert!(code.contains(
+            "                writer.write_vec(value, |writer, &item| writer.write_bool(item))\n"
+        ));
+        assert!(code.contains("        match reader.read_uint(8) {\n"));
+        assert!(code.contains("            0 => Ok(Self::Health(reader.read_composite()?)),\n"));
+        assert!(code.contains("            _ => Err(RuntimeError::InvalidTag),\n"));
+    }
+}
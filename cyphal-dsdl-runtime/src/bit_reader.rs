@@ -0,0 +1,232 @@
+use crate::{
+    float16::f16_to_f32, length_prefix_bits, DataType, RuntimeError, RuntimeResult, Vec,
+    DELIMITER_HEADER_BITS,
+};
+
+/// Deserializes values from a buffer following the DSDL bit-level, little-endian rules.
+///
+/// Reading past the end of the data yields zeros, which implements the DSDL implicit zero
+/// extension rule.
+#[derive(Debug)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Constructs a new bit reader starting at the beginning of the data
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Returns the number of bits read so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads `bits` bits, least significant bit first
+    pub fn read_bits(&mut self, bits: u8) -> u64 {
+        let bits = bits as usize;
+        let mut value = 0;
+        let mut position = 0;
+        while position < bits {
+            let shift = self.offset % 8;
+            let count = (bits - position).min(8 - shift);
+            let byte = self.data.get(self.offset / 8).copied().unwrap_or(0);
+            let chunk = (byte >> shift) & ((1_u16 << count) - 1) as u8;
+
+            value |= (chunk as u64) << position;
+
+            position += count;
+            self.offset += count;
+        }
+
+        value
+    }
+
+    /// Reads a `bool`
+    pub fn read_bool(&mut self) -> bool {
+        self.read_bits(1) != 0
+    }
+
+    /// Reads an unsigned integer of `bits` bits
+    pub fn read_uint(&mut self, bits: u8) -> u64 {
+        self.read_bits(bits)
+    }
+
+    /// Reads a signed integer of `bits` bits
+    pub fn read_int(&mut self, bits: u8) -> i64 {
+        let value = self.read_bits(bits);
+        if bits < 64 {
+            let shift = 64 - bits;
+            ((value << shift) as i64) >> shift
+        } else {
+            value as i64
+        }
+    }
+
+    /// Reads a 16 bits floating point number
+    pub fn read_f16(&mut self) -> f32 {
+        f16_to_f32(self.read_bits(16) as u16)
+    }
+
+    /// Reads a 32 bits floating point number
+    pub fn read_f32(&mut self) -> f32 {
+        f32::from_bits(self.read_bits(32) as u32)
+    }
+
+    /// Reads a 64 bits floating point number
+    pub fn read_f64(&mut self) -> f64 {
+        f64::from_bits(self.read_bits(64))
+    }
+
+    /// Skips `bits` bits of padding
+    pub fn skip(&mut self, bits: usize) {
+        self.offset += bits;
+    }
+
+    /// Skips up to the next multiple of `alignment` bits
+    pub fn align(&mut self, alignment: usize) {
+        self.offset += (alignment - self.offset % alignment) % alignment;
+    }
+
+    /// Reads the length prefix of a variable-length array with the given capacity
+    pub fn read_length(&mut self, capacity: usize) -> RuntimeResult<usize> {
+        let length = self.read_bits(length_prefix_bits(capacity));
+        if length > capacity as u64 {
+            return Err(RuntimeError::InvalidLength);
+        }
+
+        Ok(length as usize)
+    }
+
+    /// Reads the elements of a fixed-length array
+    pub fn read_array<T, F, const N: usize>(&mut self, mut read: F) -> RuntimeResult<[T; N]>
+    where
+        F: FnMut(&mut Self) -> RuntimeResult<T>,
+    {
+        let mut items: [Option<T>; N] = core::array::from_fn(|_| None);
+        for item in items.iter_mut() {
+            *item = Some(read(self)?);
+        }
+
+        Ok(items.map(|i| i.unwrap()))
+    }
+
+    /// Reads a variable-length array, which is its length prefix followed by its elements
+    pub fn read_vec<T, F, const N: usize>(&mut self, mut read: F) -> RuntimeResult<Vec<T, N>>
+    where
+        F: FnMut(&mut Self) -> RuntimeResult<T>,
+    {
+        let length = self.read_length(N)?;
+        let mut items = Vec::new();
+        for _ in 0..length {
+            // the length can't exceed the capacity, so there is always room left
+            let _ = items.push(read(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Reads a variable-length array of composite types, which starts on a byte like its elements
+    pub fn read_composite_vec<T: DataType, const N: usize>(&mut self) -> RuntimeResult<Vec<T, N>> {
+        self.align(8);
+        self.read_vec(|reader| reader.read_composite())
+    }
+
+    /// Reads a nested composite type.  Non-sealed types are read from the number of bytes given by
+    /// their delimiter header, so that fields added by newer minor versions are skipped.
+    pub fn read_composite<T: DataType>(&mut self) -> RuntimeResult<T> {
+        self.align(8);
+
+        if T::SEALED {
+            let value = T::deserialize(self)?;
+            self.align(8);
+            return Ok(value);
+        }
+
+        let size = self.read_bits(DELIMITER_HEADER_BITS) as usize;
+        let start = self.offset / 8;
+        if self.data.len().saturating_sub(start) < size {
+            return Err(RuntimeError::InvalidDelimiter);
+        }
+
+        let end = start + size;
+        let mut reader = BitReader::new(self.data.get(start..end).unwrap_or(&[]));
+        let value = T::deserialize(&mut reader)?;
+        self.offset = end * 8;
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BitReader, RuntimeError, Vec};
+
+    #[test]
+    fn test_read_bits() {
+        let data = [0xA5, 0x91, 0x08];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_bits(3), 0b101);
+        assert_eq!(reader.read_bits(16), 0x1234);
+        assert!(reader.read_bool());
+        assert_eq!(reader.offset(), 20);
+    }
+
+    #[test]
+    fn test_read_little_endian() {
+        let data = [0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x80, 0x3F];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_uint(32), 0x12345678);
+        assert_eq!(reader.read_f32(), 1.0);
+    }
+
+    #[test]
+    fn test_read_int() {
+        let data = [0x80, 0x7F, 0xFE, 0x0F];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_int(8), -128);
+        assert_eq!(reader.read_int(8), 127);
+        assert_eq!(reader.read_int(8), -2);
+        assert_eq!(reader.read_int(4), -1);
+        assert_eq!(reader.read_int(4), 0);
+    }
+
+    #[test]
+    fn test_implicit_zero_extension() {
+        let data = [0xFF];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_uint(16), 0xFF);
+        assert_eq!(reader.read_f64(), 0.0);
+        assert_eq!(reader.offset(), 80);
+    }
+
+    #[test]
+    fn test_read_length() {
+        let data = [3, 4];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_length(3), Ok(3));
+        assert_eq!(reader.read_length(3), Err(RuntimeError::InvalidLength));
+    }
+
+    #[test]
+    fn test_read_arrays() {
+        let data = [0b101, 2, 1, 2, 5, 1, 2, 3, 4, 5];
+        let mut reader = BitReader::new(&data);
+
+        let bits: [bool; 3] = reader.read_array(|r| Ok(r.read_bool())).unwrap();
+        reader.align(8);
+        let items: Vec<u8, 4> = reader.read_vec(|r| Ok(r.read_uint(8) as u8)).unwrap();
+        let result: Result<Vec<u8, 4>, _> = reader.read_vec(|r| Ok(r.read_uint(8) as u8));
+
+        assert_eq!(bits, [true, false, true]);
+        assert_eq!(items, [1, 2]);
+        assert_eq!(result, Err(RuntimeError::InvalidLength));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    float16::{f32_to_f16, F16_MAX},
+    length_prefix_bits, CastMode, DataType, RuntimeError, RuntimeResult, Vec,
+    DELIMITER_HEADER_BITS,
+};
+
+/// Serializes values into a buffer following the DSDL bit-level, little-endian rules
+#[derive(Debug)]
+pub struct BitWriter<'a> {
+    buffer: &'a mut [u8],
+    offset: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    /// Constructs a new bit writer starting at the beginning of the buffer
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, offset: 0 }
+    }
+
+    /// Returns the number of bits written so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of bytes written so far, including a partially written last byte
+    pub fn size(&self) -> usize {
+        self.offset.div_ceil(8)
+    }
+
+    /// Writes the `bits` least significant bits of the value, least significant bit first
+    pub fn write_bits(&mut self, value: u64, bits: u8) -> RuntimeResult<()> {
+        let bits = bits as usize;
+        if self.offset + bits > self.buffer.len() * 8 {
+            return Err(RuntimeError::BufferOverflow);
+        }
+
+        let mut value = value;
+        let mut remaining = bits;
+        while remaining > 0 {
+            let index = self.offset / 8;
+            let shift = self.offset % 8;
+            let count = remaining.min(8 - shift);
+            let mask = (((1_u16 << count) - 1) as u8) << shift;
+
+            self.buffer[index] = (self.buffer[index] & !mask) | (((value as u8) << shift) & mask);
+
+            value >>= count;
+            remaining -= count;
+            self.offset += count;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a `bool`
+    pub fn write_bool(&mut self, value: bool) -> RuntimeResult<()> {
+        self.write_bits(value as u64, 1)
+    }
+
+    /// Writes an unsigned integer of `bits` bits
+    pub fn write_uint(&mut self, value: u64, bits: u8, mode: CastMode) -> RuntimeResult<()> {
+        let value = match mode {
+            CastMode::Saturated if bits < 64 => value.min((1 << bits) - 1),
+            _ => value,
+        };
+
+        self.write_bits(value, bits)
+    }
+
+    /// Writes a signed integer of `bits` bits.  Signed integers are always saturated.
+    pub fn write_int(&mut self, value: i64, bits: u8) -> RuntimeResult<()> {
+        let value = if bits < 64 {
+            let max = (1_i64 << (bits - 1)) - 1;
+            value.clamp(-max - 1, max)
+        } else {
+            value
+        };
+
+        self.write_bits(value as u64, bits)
+    }
+
+    /// Writes a 16 bits floating point number
+    pub fn write_f16(&mut self, value: f32, mode: CastMode) -> RuntimeResult<()> {
+        let value = match mode {
+            CastMode::Saturated if value.is_finite() => value.clamp(-F16_MAX, F16_MAX),
+            _ => value,
+        };
+
+        self.write_bits(f32_to_f16(value) as u64, 16)
+    }
+
+    /// Writes a 32 bits floating point number
+    pub fn write_f32(&mut self, value: f32) -> RuntimeResult<()> {
+        self.write_bits(value.to_bits() as u64, 32)
+    }
+
+    /// Writes a 64 bits floating point number
+    pub fn write_f64(&mut self, value: f64) -> RuntimeResult<()> {
+        self.write_bits(value.to_bits(), 64)
+    }
+
+    /// Writes `bits` bits of zero padding, as required by `void` fields
+    pub fn write_void(&mut self, bits: u8) -> RuntimeResult<()> {
+        self.write_bits(0, bits)
+    }
+
+    /// Pads with zeros up to the next multiple of `alignment` bits
+    pub fn align(&mut self, alignment: usize) -> RuntimeResult<()> {
+        let mut padding = (alignment - self.offset % alignment) % alignment;
+        while padding > 0 {
+            let bits = padding.min(64);
+            self.write_bits(0, bits as u8)?;
+            padding -= bits;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the length prefix of a variable-length array with the given capacity
+    pub fn write_length(&mut self, length: usize, capacity: usize) -> RuntimeResult<()> {
+        if length > capacity {
+            return Err(RuntimeError::InvalidLength);
+        }
+
+        self.write_bits(length as u64, length_prefix_bits(capacity))
+    }
+
+    /// Writes the elements of a fixed-length array
+    pub fn write_array<T, F>(&mut self, items: &[T], mut write: F) -> RuntimeResult<()>
+    where
+        F: FnMut(&mut Self, &T) -> RuntimeResult<()>,
+    {
+        for item in items {
+            write(self, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a variable-length array, which is its length prefix followed by its elements
+    pub fn write_vec<T, F, const N: usize>(
+        &mut self,
+        items: &Vec<T, N>,
+        write: F,
+    ) -> RuntimeResult<()>
+    where
+        F: FnMut(&mut Self, &T) -> RuntimeResult<()>,
+    {
+        self.write_length(items.len(), N)?;
+        self.write_array(items, write)
+    }
+
+    /// Writes a variable-length array of composite types, which starts on a byte like its
+    /// elements so that the length prefix is aligned as well
+    pub fn write_composite_vec<T: DataType, const N: usize>(
+        &mut self,
+        items: &Vec<T, N>,
+    ) -> RuntimeResult<()> {
+        self.align(8)?;
+        self.write_vec(items, |writer, item| writer.write_composite(item))
+    }
+
+    /// Writes a nested composite type.  Composite types are byte aligned and non-sealed types are
+    /// preceded by a delimiter header holding their size in bytes.
+    pub fn write_composite<T: DataType>(&mut self, value: &T) -> RuntimeResult<()> {
+        self.align(8)?;
+
+        if T::SEALED {
+            value.serialize(self)?;
+            return self.align(8);
+        }
+
+        let header = self.offset / 8;
+        self.write_bits(0, DELIMITER_HEADER_BITS)?;
+        let start = self.offset;
+        value.serialize(self)?;
+        self.align(8)?;
+
+        let size = ((self.offset - start) / 8) as u32;
+        self.buffer[header..header + 4].copy_from_slice(&size.to_le_bytes());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BitWriter, CastMode, RuntimeError, Vec};
+
+    #[test]
+    fn test_write_bits() {
+        let mut buffer = [0xFF; 4];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_bits(0b101, 3).unwrap();
+        writer.write_bits(0x1234, 16).unwrap();
+        writer.write_bool(true).unwrap();
+
+        assert_eq!(writer.offset(), 20);
+        assert_eq!(writer.size(), 3);
+
+        writer.align(8).unwrap();
+        assert_eq!(writer.offset(), 24);
+        assert_eq!(buffer[..3], [0xA5, 0x91, 0x08]);
+    }
+
+    #[test]
+    fn test_write_little_endian() {
+        let mut buffer = [0; 8];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer
+            .write_uint(0x12345678, 32, CastMode::Saturated)
+            .unwrap();
+        writer.write_f32(1.0).unwrap();
+
+        assert_eq!(buffer, [0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x80, 0x3F]);
+    }
+
+    #[test]
+    fn test_write_uint_cast_mode() {
+        let mut buffer = [0; 2];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_uint(300, 8, CastMode::Saturated).unwrap();
+        writer.write_uint(300, 8, CastMode::Truncated).unwrap();
+
+        assert_eq!(buffer, [0xFF, 0x2C]);
+    }
+
+    #[test]
+    fn test_write_int_saturated() {
+        let mut buffer = [0; 3];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_int(-200, 8).unwrap();
+        writer.write_int(200, 8).unwrap();
+        writer.write_int(-2, 8).unwrap();
+
+        assert_eq!(buffer, [0x80, 0x7F, 0xFE]);
+    }
+
+    #[test]
+    fn test_write_f16_cast_mode() {
+        let mut buffer = [0; 4];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_f16(1e6, CastMode::Saturated).unwrap();
+        writer.write_f16(1e6, CastMode::Truncated).unwrap();
+
+        assert_eq!(buffer, [0xFF, 0x7B, 0x00, 0x7C]);
+    }
+
+    #[test]
+    fn test_write_length() {
+        let mut buffer = [0; 3];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_length(3, 255).unwrap();
+        writer.write_length(3, 256).unwrap();
+
+        assert_eq!(writer.write_length(4, 3), Err(RuntimeError::InvalidLength));
+        assert_eq!(writer.offset(), 24);
+        assert_eq!(buffer, [3, 3, 0]);
+    }
+
+    #[test]
+    fn test_buffer_overflow() {
+        let mut buffer = [0; 1];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_bits(0, 5).unwrap();
+
+        assert_eq!(writer.write_bits(0, 4), Err(RuntimeError::BufferOverflow));
+        assert_eq!(writer.offset(), 5);
+    }
+
+    #[test]
+    fn test_write_arrays() {
+        let mut buffer = [0; 5];
+        let mut writer = BitWriter::new(&mut buffer);
+        let items: Vec<u8, 4> = Vec::from_slice(&[1, 2]).unwrap();
+
+        writer
+            .write_array(&[true, false, true], |w, &b| w.write_bool(b))
+            .unwrap();
+        writer.align(8).unwrap();
+        writer
+            .write_vec(&items, |w, &i| {
+                w.write_uint(i as u64, 8, CastMode::Saturated)
+            })
+            .unwrap();
+
+        assert_eq!(writer.offset(), 32);
+        assert_eq!(buffer[..4], [0b101, 2, 1, 2]);
+    }
+}
@@ -0,0 +1,12 @@
+/// Represents how a value that doesn't fit in its field is serialized
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum CastMode {
+    /// Out of range values are clamped to the nearest representable value.  This is the default
+    /// cast mode in DSDL.
+    #[default]
+    Saturated,
+
+    /// Out of range values have their most significant bits discarded.  Floating point values that
+    /// are out of range become infinities.
+    Truncated,
+}
@@ -0,0 +1,228 @@
+use crate::{BitReader, BitWriter, RuntimeResult};
+
+/// Number of bits of the delimiter header that precedes nested non-sealed composite types
+pub const DELIMITER_HEADER_BITS: u8 = 32;
+
+/// Trait representing a DSDL composite type
+pub trait DataType: Sized {
+    /// `true` if the type is `@sealed`, in which case it is nested without a delimiter header
+    const SEALED: bool;
+
+    /// Maximum serialized size in bytes of this type and any of its future minor versions
+    const EXTENT: usize;
+
+    /// Serializes the fields of the type
+    fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()>;
+
+    /// Deserializes the fields of the type
+    fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self>;
+
+    /// Serializes the type as a top level object and returns the number of bytes written
+    fn serialize_to(&self, buffer: &mut [u8]) -> RuntimeResult<usize> {
+        let mut writer = BitWriter::new(buffer);
+        self.serialize(&mut writer)?;
+        writer.align(8)?;
+
+        Ok(writer.size())
+    }
+
+    /// Deserializes the type from a top level object
+    fn deserialize_from(data: &[u8]) -> RuntimeResult<Self> {
+        let mut reader = BitReader::new(data);
+        Self::deserialize(&mut reader)
+    }
+}
+
+/// Returns the number of bits of the length prefix of a variable-length array with the given
+/// capacity, which is the smallest standard bit length able to hold the capacity
+pub const fn length_prefix_bits(capacity: usize) -> u8 {
+    let bits = usize::BITS - capacity.leading_zeros();
+    if bits <= 8 {
+        8
+    } else if bits <= 16 {
+        16
+    } else if bits <= 32 {
+        32
+    } else {
+        64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        length_prefix_bits, BitReader, BitWriter, CastMode, DataType, RuntimeError, RuntimeResult,
+        Vec,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: u8,
+        y: i8,
+    }
+
+    impl DataType for Point {
+        const SEALED: bool = true;
+        const EXTENT: usize = 2;
+
+        fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+            writer.write_uint(self.x as u64, 8, CastMode::Saturated)?;
+            writer.write_int(self.y as i64, 4)
+        }
+
+        fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+            Ok(Self {
+                x: reader.read_uint(8) as u8,
+                y: reader.read_int(4) as i8,
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Status {
+        code: u16,
+    }
+
+    impl DataType for Status {
+        const SEALED: bool = false;
+        const EXTENT: usize = 8;
+
+        fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+            writer.write_uint(self.code as u64, 16, CastMode::Saturated)
+        }
+
+        fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+            Ok(Self {
+                code: reader.read_uint(16) as u16,
+            })
+        }
+    }
+
+    // a newer minor version of `Status` with an extra field
+    struct StatusExtended {
+        code: u16,
+        extra: u8,
+    }
+
+    impl DataType for StatusExtended {
+        const SEALED: bool = false;
+        const EXTENT: usize = 8;
+
+        fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+            writer.write_uint(self.code as u64, 16, CastMode::Saturated)?;
+            writer.write_uint(self.extra as u64, 8, CastMode::Saturated)
+        }
+
+        fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+            Ok(Self {
+                code: reader.read_uint(16) as u16,
+                extra: reader.read_uint(8) as u8,
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Outer {
+        flag: bool,
+        point: Point,
+        status: Status,
+        tail: u8,
+    }
+
+    impl DataType for Outer {
+        const SEALED: bool = false;
+        const EXTENT: usize = 16;
+
+        fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+            writer.write_bool(self.flag)?;
+            writer.write_composite(&self.point)?;
+            writer.write_composite(&self.status)?;
+            writer.write_uint(self.tail as u64, 8, CastMode::Saturated)
+        }
+
+        fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+            Ok(Self {
+                flag: reader.read_bool(),
+                point: reader.read_composite()?,
+                status: reader.read_composite()?,
+                tail: reader.read_uint(8) as u8,
+            })
+        }
+    }
+
+    #[test]
+    fn test_length_prefix_bits() {
+        assert_eq!(length_prefix_bits(0), 8);
+        assert_eq!(length_prefix_bits(255), 8);
+        assert_eq!(length_prefix_bits(256), 16);
+        assert_eq!(length_prefix_bits(65535), 16);
+        assert_eq!(length_prefix_bits(65536), 32);
+    }
+
+    #[test]
+    fn test_nested_composites() {
+        let outer = Outer {
+            flag: true,
+            point: Point { x: 5, y: -1 },
+            status: Status { code: 0x1234 },
+            tail: 0xAB,
+        };
+        let mut buffer = [0xFF; Outer::EXTENT];
+
+        let size = outer.serialize_to(&mut buffer).unwrap();
+
+        assert_eq!(size, 10);
+        assert_eq!(
+            buffer[..size],
+            [0x01, 0x05, 0x0F, 0x02, 0x00, 0x00, 0x00, 0x34, 0x12, 0xAB]
+        );
+        assert_eq!(Outer::deserialize_from(&buffer[..size]), Ok(outer));
+    }
+
+    #[test]
+    fn test_delimited_composite_extension() {
+        let mut buffer = [0; 8];
+        let mut writer = BitWriter::new(&mut buffer);
+        writer
+            .write_composite(&StatusExtended {
+                code: 0x1234,
+                extra: 0x56,
+            })
+            .unwrap();
+        writer.write_uint(0xAB, 8, CastMode::Saturated).unwrap();
+
+        let mut reader = BitReader::new(&buffer);
+
+        assert_eq!(reader.read_composite(), Ok(Status { code: 0x1234 }));
+        assert_eq!(reader.read_uint(8), 0xAB);
+    }
+
+    #[test]
+    fn test_composite_vec() {
+        let mut points: Vec<Point, 2> = Vec::new();
+        points.push(Point { x: 1, y: 2 }).unwrap();
+        let mut buffer = [0xFF; 4];
+        let mut writer = BitWriter::new(&mut buffer);
+
+        writer.write_bool(true).unwrap();
+        writer.write_composite_vec(&points).unwrap();
+
+        // the length prefix starts on a byte, like the points
+        assert_eq!(writer.offset(), 32);
+        assert_eq!(buffer, [0x01, 0x01, 0x01, 0x02]);
+
+        let mut reader = BitReader::new(&buffer);
+        assert!(reader.read_bool());
+        assert_eq!(reader.read_composite_vec(), Ok(points));
+    }
+
+    #[test]
+    fn test_invalid_delimiter() {
+        let data = [0x0A, 0x00, 0x00, 0x00, 0x34, 0x12];
+        let mut reader = BitReader::new(&data);
+
+        let result: RuntimeResult<Status> = reader.read_composite();
+
+        assert_eq!(result, Err(RuntimeError::InvalidDelimiter));
+    }
+}
@@ -0,0 +1,44 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use cyphal::CyphalError;
+
+/// Represents a DSDL serialization Error
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum RuntimeError {
+    /// The buffer is too small to hold the serialized value
+    BufferOverflow,
+
+    /// A variable-length array is longer than its capacity
+    InvalidLength,
+
+    /// A delimiter header points past the end of the serialized data
+    InvalidDelimiter,
+
+    /// The tag of a union doesn't match any of its variants
+    InvalidTag,
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::BufferOverflow => write!(f, "The buffer is too small for the serialized value"),
+            Self::InvalidLength => write!(f, "The array length exceeds its capacity"),
+            Self::InvalidDelimiter => write!(f, "The delimiter header exceeds the available data"),
+            Self::InvalidTag => write!(f, "The union tag doesn't match any of its variants"),
+        }
+    }
+}
+
+impl From<RuntimeError> for CyphalError {
+    fn from(value: RuntimeError) -> Self {
+        match value {
+            RuntimeError::BufferOverflow => CyphalError::BufferFull,
+            RuntimeError::InvalidLength => CyphalError::OutOfRange,
+            RuntimeError::InvalidDelimiter | RuntimeError::InvalidTag => {
+                CyphalError::MalformedTransfer
+            }
+        }
+    }
+}
+
+/// The result of a DSDL serialization operation.  On failure, a `RuntimeError` will be included.
+pub type RuntimeResult<T> = Result<T, RuntimeError>;
@@ -0,0 +1,105 @@
+// Conversions between `f32` and the IEEE 754 binary16 format, which has no stable Rust type yet
+// -> https://github.com/rust-lang/rust/issues/116909
+
+/// Largest finite binary16 value
+pub(crate) const F16_MAX: f32 = 65504.0;
+
+pub(crate) fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x7F_FFFF;
+
+    // infinities and NaNs
+    if exponent == 0xFF {
+        let nan = if mantissa != 0 { 0x0200 } else { 0 };
+        return sign | 0x7C00 | nan;
+    }
+
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1F {
+        return sign | 0x7C00;
+    }
+
+    // subnormals
+    if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        return sign | round(mantissa, shift) as u16;
+    }
+
+    // a mantissa overflow caused by the rounding carries into the exponent, which is correct
+    let value = ((exponent as u32) << 23) | mantissa;
+    sign | round(value, 13) as u16
+}
+
+pub(crate) fn f16_to_f32(value: u16) -> f32 {
+    let sign = ((value & 0x8000) as u32) << 16;
+    let exponent = ((value >> 10) & 0x1F) as u32;
+    let mantissa = (value & 0x3FF) as u32;
+
+    let bits = if exponent == 0x1F {
+        sign | 0x7F80_0000 | (mantissa << 13)
+    } else if exponent != 0 {
+        sign | ((exponent + 112) << 23) | (mantissa << 13)
+    } else if mantissa == 0 {
+        sign
+    } else {
+        // normalize the subnormal
+        let mut exponent = 113;
+        let mut mantissa = mantissa;
+        while mantissa & 0x400 == 0 {
+            mantissa <<= 1;
+            exponent -= 1;
+        }
+        sign | (exponent << 23) | ((mantissa & 0x3FF) << 13)
+    };
+
+    f32::from_bits(bits)
+}
+
+// shifts right, rounding half to even
+fn round(value: u32, shift: u32) -> u32 {
+    let result = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if remainder > half || (remainder == half && result & 1 == 1) {
+        result + 1
+    } else {
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{f16_to_f32, f32_to_f16, F16_MAX};
+
+    #[test]
+    fn test_f32_to_f16() {
+        assert_eq!(f32_to_f16(0.0), 0x0000);
+        assert_eq!(f32_to_f16(-0.0), 0x8000);
+        assert_eq!(f32_to_f16(1.0), 0x3C00);
+        assert_eq!(f32_to_f16(-2.0), 0xC000);
+        assert_eq!(f32_to_f16(0.333_333_34), 0x3555);
+        assert_eq!(f32_to_f16(F16_MAX), 0x7BFF);
+        assert_eq!(f32_to_f16(65520.0), 0x7C00);
+        assert_eq!(f32_to_f16(f32::NEG_INFINITY), 0xFC00);
+        assert_eq!(f32_to_f16(5.960_464_5e-8), 0x0001);
+        assert_eq!(f32_to_f16(6.097_555e-5), 0x03FF);
+        assert!(f32_to_f16(f32::NAN) & 0x7C00 == 0x7C00 && f32_to_f16(f32::NAN) & 0x03FF != 0);
+    }
+
+    #[test]
+    fn test_f16_to_f32() {
+        assert_eq!(f16_to_f32(0x3C00), 1.0);
+        assert_eq!(f16_to_f32(0xC000), -2.0);
+        assert_eq!(f16_to_f32(0x7BFF), F16_MAX);
+        assert_eq!(f16_to_f32(0x0001), 5.960_464_5e-8);
+        assert_eq!(f16_to_f32(0x03FF), 6.097_555e-5);
+        assert_eq!(f16_to_f32(0xFC00), f32::NEG_INFINITY);
+        assert!(f16_to_f32(0x7E00).is_nan());
+    }
+}
@@ -0,0 +1,23 @@
+#![no_std]
+#![doc = include_str!("../README.md")]
+#![forbid(missing_docs)]
+
+mod bit_reader;
+pub use bit_reader::BitReader;
+
+mod bit_writer;
+pub use bit_writer::BitWriter;
+
+mod cast_mode;
+pub use cast_mode::CastMode;
+
+mod data_type;
+pub use data_type::{length_prefix_bits, DataType, DELIMITER_HEADER_BITS};
+
+mod error;
+pub use error::{RuntimeError, RuntimeResult};
+
+mod float16;
+
+/// Fixed capacity vector holding the variable-length arrays of DSDL types
+pub use heapless::Vec;
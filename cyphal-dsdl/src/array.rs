@@ -0,0 +1,337 @@
+use crate::{
+    composite::parse_type, primitive::parse_bits, CastMode, Comment, DsdlError, DsdlResult,
+    Expression, Name, Scope,
+};
+
+/// Represents the capacity of an array
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ArrayCapacity {
+    /// Fixed-length array, e.g. `float32[3]`
+    Fixed(u64),
+
+    /// Variable-length array with an inclusive capacity, e.g. `uint8[<=50]`
+    Inclusive(u64),
+
+    /// Variable-length array with an exclusive capacity, e.g. `uint8[<50]`
+    Exclusive(u64),
+}
+
+impl ArrayCapacity {
+    /// Returns the maximum number of elements of the array
+    pub fn max(&self) -> u64 {
+        match self {
+            Self::Fixed(n) | Self::Inclusive(n) => *n,
+            Self::Exclusive(n) => n - 1,
+        }
+    }
+
+    /// Returns `true` if the length of the array is variable
+    pub fn is_variable(&self) -> bool {
+        !matches!(self, Self::Fixed(_))
+    }
+}
+
+/// Represents the type of the elements of an array
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayElement {
+    /// bool
+    Bool,
+
+    /// int
+    Int {
+        /// The number of bits
+        bits: u8,
+    },
+
+    /// unsigned int
+    Uint {
+        /// The cast mode
+        cast_mode: CastMode,
+        /// The number of bits
+        bits: u8,
+    },
+
+    /// floating point
+    Float {
+        /// The cast mode
+        cast_mode: CastMode,
+        /// The number of bits
+        bits: u8,
+    },
+
+    /// composite type
+    Composite {
+        /// The namespace
+        namespace: Vec<String>,
+        /// The type
+        ctype: String,
+        /// The major version number
+        major: u8,
+        /// The minor version number
+        minor: u8,
+    },
+}
+
+/// Represents an array field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Array {
+    element: ArrayElement,
+    capacity: ArrayCapacity,
+    name: Name,
+    comment: Option<Comment>,
+}
+
+impl Array {
+    /// Constructs a new array
+    pub fn new(
+        element: ArrayElement,
+        capacity: ArrayCapacity,
+        name: Name,
+        comment: Option<Comment>,
+    ) -> DsdlResult<Self> {
+        let valid = match capacity {
+            ArrayCapacity::Fixed(n) | ArrayCapacity::Inclusive(n) => n > 0,
+            ArrayCapacity::Exclusive(n) => n > 1,
+        };
+        if !valid {
+            return Err(DsdlError::OutOfRange(
+                "An array must be able to hold at least one element".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            element,
+            capacity,
+            name,
+            comment,
+        })
+    }
+
+    /// Returns the type of the elements
+    pub fn element(&self) -> &ArrayElement {
+        &self.element
+    }
+
+    /// Returns the capacity
+    pub fn capacity(&self) -> ArrayCapacity {
+        self.capacity
+    }
+
+    /// Returns the name of the array
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// Returns the comment if it has one
+    pub fn comment(&self) -> Option<&Comment> {
+        self.comment.as_ref()
+    }
+
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Self> {
+        let (cast_mode, line) = CastMode::parse(line)?;
+
+        let (element, rest) = match line.split_once('[') {
+            Some(r) => r,
+            None => {
+                return Err(DsdlError::Parse(
+                    "Expected a '[' after the array element type".to_string(),
+                ))
+            }
+        };
+        let (capacity, rest) = match rest.split_once(']') {
+            Some(r) => r,
+            None => {
+                return Err(DsdlError::Parse(
+                    "Expected a ']' after the array capacity".to_string(),
+                ))
+            }
+        };
+
+        let element = parse_element(element.trim_end(), cast_mode)?;
+        let capacity = parse_capacity(capacity, scope)?;
+
+        let result = Name::parse(rest)?;
+        let name = result.0;
+        let comment = match result.1 {
+            Some(s) => Comment::parse(s)?,
+            None => None,
+        };
+
+        Array::new(element, capacity, name, comment)
+    }
+}
+
+fn parse_element(text: &str, cast_mode: Option<CastMode>) -> DsdlResult<ArrayElement> {
+    let element = if text == "bool" {
+        ArrayElement::Bool
+    } else if let Some(bits) = primitive_bits(text, "int")? {
+        ArrayElement::Int { bits }
+    } else if let Some(bits) = primitive_bits(text, "uint")? {
+        ArrayElement::Uint {
+            cast_mode: cast_mode.unwrap_or_default(),
+            bits,
+        }
+    } else if let Some(bits) = primitive_bits(text, "float")? {
+        if bits != 16 && bits != 32 && bits != 64 {
+            return Err(DsdlError::OutOfRange(
+                "A floating point number must have 16, 32 or 64 bits".to_string(),
+            ));
+        }
+        ArrayElement::Float {
+            cast_mode: cast_mode.unwrap_or_default(),
+            bits,
+        }
+    } else if text.starts_with("void") {
+        return Err(DsdlError::Parse(
+            "Arrays of void are not allowed".to_string(),
+        ));
+    } else {
+        if cast_mode.is_some() {
+            return Err(DsdlError::Parse(
+                "Composite types can't have a cast mode".to_string(),
+            ));
+        }
+        let (namespace, ctype, major, minor) = parse_type(text)?;
+        return Ok(ArrayElement::Composite {
+            namespace,
+            ctype,
+            major,
+            minor,
+        });
+    };
+
+    let saturated_only = matches!(element, ArrayElement::Bool | ArrayElement::Int { .. });
+    if saturated_only && cast_mode == Some(CastMode::Truncated) {
+        return Err(DsdlError::Parse(
+            "Booleans and signed integers can only be saturated".to_string(),
+        ));
+    }
+
+    Ok(element)
+}
+
+// Returns the bits of a primitive type with the given prefix, e.g. `uint8`
+fn primitive_bits(text: &str, prefix: &str) -> DsdlResult<Option<u8>> {
+    match text.strip_prefix(prefix) {
+        Some(s) if s.starts_with(|c: char| c.is_ascii_digit()) => {
+            let (bits, rest) = parse_bits(s)?;
+            if rest.is_some() || bits == 0 || bits > 64 {
+                return Err(DsdlError::OutOfRange(format!(
+                    "Invalid primitive type `{}`",
+                    text
+                )));
+            }
+            Ok(Some(bits))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_capacity(text: &str, scope: &dyn Scope) -> DsdlResult<ArrayCapacity> {
+    let text = text.trim();
+    let (text, capacity): (&str, fn(u64) -> ArrayCapacity) =
+        if let Some(s) = text.strip_prefix("<=") {
+            (s, ArrayCapacity::Inclusive)
+        } else if let Some(s) = text.strip_prefix('<') {
+            (s, ArrayCapacity::Exclusive)
+        } else {
+            (text, ArrayCapacity::Fixed)
+        };
+
+    let value = Expression::new(text.trim().to_string())?.evaluate(scope)?;
+    let value = match value.as_rational().and_then(|r| r.to_integer()) {
+        Some(v) => u64::try_from(v).map_err(|_| {
+            DsdlError::OutOfRange("The capacity of an array can't be negative".to_string())
+        })?,
+        None => {
+            return Err(DsdlError::Expression(format!(
+                "The capacity of an array must be an integer but found `{}`",
+                value
+            )))
+        }
+    };
+
+    Ok(capacity(value))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, ArrayCapacity, ArrayElement, CastMode};
+
+    #[test]
+    fn test_fixed_array() {
+        let result = Array::parse("float32[3] xyz   # [meter]", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(
+            target.element(),
+            &ArrayElement::Float {
+                cast_mode: CastMode::Saturated,
+                bits: 32
+            }
+        );
+        assert_eq!(target.capacity(), ArrayCapacity::Fixed(3));
+        assert!(!target.capacity().is_variable());
+        assert_eq!(target.name().text(), "xyz");
+        assert!(target.comment().is_some_and(|c| c.text() == " [meter]"));
+    }
+
+    #[test]
+    fn test_inclusive_array() {
+        let result = Array::parse("truncated uint8[<= 50] name", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(
+            target.element(),
+            &ArrayElement::Uint {
+                cast_mode: CastMode::Truncated,
+                bits: 8
+            }
+        );
+        assert_eq!(target.capacity(), ArrayCapacity::Inclusive(50));
+        assert_eq!(target.capacity().max(), 50);
+        assert!(target.capacity().is_variable());
+    }
+
+    #[test]
+    fn test_exclusive_array() {
+        let result = Array::parse("bool[<64] bits", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.element(), &ArrayElement::Bool);
+        assert_eq!(target.capacity(), ArrayCapacity::Exclusive(64));
+        assert_eq!(target.capacity().max(), 63);
+    }
+
+    #[test]
+    fn test_composite_array() {
+        let result = Array::parse("uavcan.primitive.String.1.0[<=4] tags", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(
+            target.element(),
+            &ArrayElement::Composite {
+                namespace: vec!["uavcan".to_string(), "primitive".to_string()],
+                ctype: "String".to_string(),
+                major: 1,
+                minor: 0,
+            }
+        );
+        assert_eq!(target.capacity(), ArrayCapacity::Inclusive(4));
+        assert_eq!(target.name().text(), "tags");
+    }
+
+    #[test]
+    fn test_invalid_arrays() {
+        assert!(Array::parse("void8[4] padding", &()).is_err());
+        assert!(Array::parse("uint8[0] empty", &()).is_err());
+        assert!(Array::parse("uint8[<1] empty", &()).is_err());
+        assert!(Array::parse("uint8[4 name", &()).is_err());
+        assert!(Array::parse("truncated int8[4] name", &()).is_err());
+        assert!(Array::parse("uint8[4]", &()).is_err());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{ArrayCapacity, Rational, Value};
+use std::collections::BTreeSet;
+
+// Sets with more lengths than this are approximated by evenly spaced lengths, as combining them
+// length by length gets too slow for large arrays of composite types
+const MAX_LENGTHS: u64 = 1024;
+
+/// Represents the set of the possible lengths in bits of serialized data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitLengthSet {
+    lengths: Lengths,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Lengths {
+    Exact(BTreeSet<u64>),
+    // every `step` bits from `min` to `max`, of which there are more than `MAX_LENGTHS`
+    Spaced { min: u64, max: u64, step: u64 },
+}
+
+impl BitLengthSet {
+    /// Constructs a set holding a single length
+    pub fn new(bits: u64) -> Self {
+        Self {
+            lengths: Lengths::Exact(BTreeSet::from([bits])),
+        }
+    }
+
+    // Constructs the set of every `step` bits from `min` to `max`
+    pub(crate) fn range(min: u64, max: u64, step: u64) -> Self {
+        if step == 0 || max <= min {
+            return Self::new(min);
+        }
+
+        let max = min + (max - min) / step * step;
+        if (max - min) / step < MAX_LENGTHS {
+            return Self {
+                lengths: Lengths::Exact((min..=max).step_by(step as usize).collect()),
+            };
+        }
+
+        Self {
+            lengths: Lengths::Spaced { min, max, step },
+        }
+    }
+
+    fn exact(values: BTreeSet<u64>) -> Self {
+        if values.len() as u64 <= MAX_LENGTHS {
+            return Self {
+                lengths: Lengths::Exact(values),
+            };
+        }
+
+        let set = Self {
+            lengths: Lengths::Exact(values),
+        };
+        Self::range(set.min(), set.max(), set.step())
+    }
+
+    /// Returns the shortest length
+    pub fn min(&self) -> u64 {
+        match &self.lengths {
+            Lengths::Exact(values) => values.first().copied().unwrap_or_default(),
+            Lengths::Spaced { min, .. } => *min,
+        }
+    }
+
+    /// Returns the longest length
+    pub fn max(&self) -> u64 {
+        match &self.lengths {
+            Lengths::Exact(values) => values.last().copied().unwrap_or_default(),
+            Lengths::Spaced { max, .. } => *max,
+        }
+    }
+
+    /// Returns the largest power of two, up to 64 bits, that divides every length
+    pub fn alignment(&self) -> u64 {
+        match gcd(self.min(), self.step()) {
+            0 => 64,
+            divisor => (divisor & divisor.wrapping_neg()).min(64),
+        }
+    }
+
+    /// Returns `true` if every length is a multiple of `bits`
+    pub fn is_aligned_at(&self, bits: u64) -> bool {
+        bits > 0 && self.min() % bits == 0 && self.step() % bits == 0
+    }
+
+    /// Returns `true` if the data always has the same length
+    pub fn is_fixed(&self) -> bool {
+        self.min() == self.max()
+    }
+
+    /// Returns the lengths in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        let (exact, spaced) = match &self.lengths {
+            Lengths::Exact(values) => (Some(values.iter().copied()), None),
+            Lengths::Spaced { min, max, step } => {
+                (None, Some((*min..=*max).step_by(*step as usize)))
+            }
+        };
+
+        exact
+            .into_iter()
+            .flatten()
+            .chain(spaced.into_iter().flatten())
+    }
+
+    // The greatest common divisor of the differences between the lengths
+    fn step(&self) -> u64 {
+        match &self.lengths {
+            Lengths::Exact(values) => {
+                let min = self.min();
+                values.iter().fold(0, |step, v| gcd(step, v - min))
+            }
+            Lengths::Spaced { step, .. } => *step,
+        }
+    }
+
+    /// Returns the lengths of this data followed by the other data
+    pub(crate) fn concat(&self, other: &Self) -> Self {
+        match (&self.lengths, &other.lengths) {
+            (Lengths::Exact(a), Lengths::Exact(b))
+                if (a.len() as u64) * (b.len() as u64) <= MAX_LENGTHS * MAX_LENGTHS / 16 =>
+            {
+                let mut values = BTreeSet::new();
+                for a in a.iter() {
+                    for b in b.iter() {
+                        values.insert(a + b);
+                    }
+                }
+
+                Self::exact(values)
+            }
+            _ => Self::range(
+                self.min() + other.min(),
+                self.max() + other.max(),
+                gcd(self.step(), other.step()),
+            ),
+        }
+    }
+
+    /// Returns the lengths of either this data or the other data
+    pub(crate) fn union(&self, other: &Self) -> Self {
+        match (&self.lengths, &other.lengths) {
+            (Lengths::Exact(a), Lengths::Exact(b)) => Self::exact(a.union(b).copied().collect()),
+            _ => Self::range(
+                self.min().min(other.min()),
+                self.max().max(other.max()),
+                gcd(
+                    gcd(self.step(), other.step()),
+                    self.min().abs_diff(other.min()),
+                ),
+            ),
+        }
+    }
+
+    /// Returns the lengths of a fixed-length array of this data
+    pub(crate) fn repeat(&self, count: u64) -> Self {
+        let mut result = Self::new(0);
+        let mut power = self.clone();
+        let mut count = count;
+        while count > 0 {
+            if count & 1 == 1 {
+                result = result.concat(&power);
+            }
+            count >>= 1;
+            if count > 0 {
+                power = power.concat(&power);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the lengths of a variable-length array of this data, without its length prefix
+    pub(crate) fn repeat_up_to(&self, capacity: u64) -> Self {
+        if capacity > MAX_LENGTHS {
+            // every length of an array is a multiple of the shortest element plus a multiple of
+            // the step between the elements
+            return Self::range(
+                0,
+                capacity.saturating_mul(self.max()),
+                gcd(self.min(), self.step()),
+            );
+        }
+
+        let mut array = Self::new(0);
+        let mut result = array.clone();
+        for _ in 0..capacity {
+            array = array.concat(self);
+            result = result.union(&array);
+        }
+
+        result
+    }
+
+    /// Returns the lengths of an array of this data, including the length prefix if it has one
+    pub(crate) fn array(&self, capacity: ArrayCapacity) -> Self {
+        match capacity {
+            ArrayCapacity::Fixed(n) => self.repeat(n),
+            _ => Self::new(length_prefix_bits(capacity.max()))
+                .concat(&self.repeat_up_to(capacity.max())),
+        }
+    }
+
+    /// Returns the lengths padded up to the next multiple of `bits`
+    pub(crate) fn pad_to_alignment(&self, bits: u64) -> Self {
+        let pad = |v: u64| v.div_ceil(bits) * bits;
+        match &self.lengths {
+            Lengths::Exact(values) => Self::exact(values.iter().map(|v| pad(*v)).collect()),
+            Lengths::Spaced { min, max, step } if step % bits == 0 => {
+                Self::range(pad(*min), max + pad(*min) - min, *step)
+            }
+            Lengths::Spaced { min, max, .. } => Self::range(pad(*min), pad(*max), bits),
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        Value::Set(
+            self.iter()
+                .map(|v| Value::Rational(Rational::from_integer(v as i128)))
+                .collect(),
+        )
+    }
+}
+
+// The length prefix is the smallest standard unsigned integer that can hold the capacity
+pub(crate) fn length_prefix_bits(capacity: u64) -> u64 {
+    match capacity {
+        0..=0xFF => 8,
+        0x100..=0xFFFF => 16,
+        0x1_0000..=0xFFFF_FFFF => 32,
+        _ => 64,
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BitLengthSet;
+    use crate::ArrayCapacity;
+
+    fn lengths(set: &BitLengthSet) -> Vec<u64> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_concat() {
+        let target = BitLengthSet::new(8).concat(&BitLengthSet::new(3));
+
+        assert_eq!(lengths(&target), [11]);
+    }
+
+    #[test]
+    fn test_union() {
+        let target = BitLengthSet::new(8).union(&BitLengthSet::new(3));
+
+        assert_eq!(lengths(&target), [3, 8]);
+        assert_eq!(target.min(), 3);
+        assert_eq!(target.max(), 8);
+    }
+
+    #[test]
+    fn test_arrays() {
+        let element = BitLengthSet::new(8);
+
+        assert_eq!(lengths(&element.repeat(3)), [24]);
+        assert_eq!(
+            lengths(&BitLengthSet::new(8).concat(&element.repeat_up_to(2))),
+            [8, 16, 24]
+        );
+    }
+
+    #[test]
+    fn test_array_capacity() {
+        let element = BitLengthSet::new(8);
+
+        assert_eq!(lengths(&element.array(ArrayCapacity::Fixed(2))), [16]);
+        assert_eq!(
+            lengths(&element.array(ArrayCapacity::Exclusive(3))),
+            [8, 16, 24]
+        );
+        assert_eq!(element.array(ArrayCapacity::Inclusive(256)).min(), 16);
+    }
+
+    #[test]
+    fn test_alignment() {
+        let target = BitLengthSet::new(3).concat(&BitLengthSet::new(8).repeat_up_to(2));
+
+        assert_eq!(target.alignment(), 1);
+        assert!(!target.is_aligned_at(8));
+
+        let padded = target.pad_to_alignment(8);
+        assert_eq!(lengths(&padded), [8, 16, 24]);
+        assert_eq!(padded.alignment(), 8);
+        assert!(padded.is_aligned_at(8));
+        assert_eq!(BitLengthSet::new(0).alignment(), 64);
+    }
+
+    #[test]
+    fn test_large_arrays() {
+        // an array of delimited types, which can be any number of bytes up to their extent
+        let element = BitLengthSet::new(32).concat(&BitLengthSet::range(0, 2048, 8));
+        let target = element.array(ArrayCapacity::Inclusive(255));
+
+        assert_eq!(target.min(), 8);
+        assert_eq!(target.max(), 8 + 255 * (32 + 2048));
+        assert!(target.is_aligned_at(8));
+        assert_eq!(target.iter().count() as u64, 255 * (32 + 2048) / 8 + 1);
+        assert!(!target.is_fixed());
+    }
+}
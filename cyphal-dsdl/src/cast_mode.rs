@@ -0,0 +1,56 @@
+use crate::{DsdlError, DsdlResult};
+
+/// Represents how a value that doesn't fit in a primitive field is serialized
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum CastMode {
+    /// Out of range values are clamped to the nearest representable value, which is the default
+    #[default]
+    Saturated,
+
+    /// Out of range values have their most significant bits discarded
+    Truncated,
+}
+
+impl CastMode {
+    // Splits an optional cast mode prefix from the rest of the line
+    pub(crate) fn parse(line: &str) -> DsdlResult<(Option<CastMode>, &str)> {
+        let (cast_mode, rest) = if let Some(s) = line.strip_prefix("saturated") {
+            (CastMode::Saturated, s)
+        } else if let Some(s) = line.strip_prefix("truncated") {
+            (CastMode::Truncated, s)
+        } else {
+            return Ok((None, line));
+        };
+
+        if !rest.starts_with(' ') {
+            return Err(DsdlError::Parse(
+                "Expected a space after the cast mode".to_string(),
+            ));
+        }
+
+        Ok((Some(cast_mode), rest.trim_start()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::CastMode;
+
+    #[test]
+    fn test_cast_mode() {
+        assert_eq!(
+            CastMode::parse("saturated uint8 x").unwrap(),
+            (Some(CastMode::Saturated), "uint8 x")
+        );
+        assert_eq!(
+            CastMode::parse("truncated  float16 x").unwrap(),
+            (Some(CastMode::Truncated), "float16 x")
+        );
+        assert_eq!(CastMode::parse("uint8 x").unwrap(), (None, "uint8 x"));
+    }
+
+    #[test]
+    fn test_cast_mode_without_space() {
+        assert!(CastMode::parse("truncateduint8 x").is_err());
+    }
+}
@@ -40,7 +40,7 @@ mod test {
     #[test]
     fn test_comment() {
         let comment = "# this is a comment";
-        let result = Comment::parse(&comment);
+        let result = Comment::parse(comment);
         assert!(result.is_ok());
 
         let option = result.unwrap();
@@ -53,7 +53,7 @@ mod test {
     #[test]
     fn test_comment_with_indent() {
         let comment = "#   this is a comment with indent";
-        let result = Comment::parse(&comment);
+        let result = Comment::parse(comment);
         assert!(result.is_ok());
 
         let option = result.unwrap();
@@ -66,7 +66,7 @@ mod test {
     #[test]
     fn test_comment_with_trailing_spaces() {
         let comment = "# this is a comment with trailing spaces    ";
-        let result = Comment::parse(&comment);
+        let result = Comment::parse(comment);
         assert!(result.is_ok());
 
         let option = result.unwrap();
@@ -79,7 +79,7 @@ mod test {
     #[test]
     fn test_comment_with_leading_spaces() {
         let comment = "  # this is a comment";
-        let result = Comment::parse(&comment);
+        let result = Comment::parse(comment);
         assert!(result.is_ok());
 
         let option = result.unwrap();
@@ -92,7 +92,7 @@ mod test {
     #[test]
     fn test_no_comment() {
         let comment = "  ";
-        let result = Comment::parse(&comment);
+        let result = Comment::parse(comment);
         assert!(result.is_ok());
 
         let option = result.unwrap();
@@ -102,7 +102,7 @@ mod test {
     #[test]
     fn test_invalid_comment() {
         let comment = "this is a bad comment";
-        let result = Comment::parse(&comment);
+        let result = Comment::parse(comment);
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,622 @@
+use crate::{ArrayCapacity, ArrayElement, Definition, File, Primitive, Statement};
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Represents a part of a type that has its own layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// The message of a message type
+    Message,
+
+    /// The request of a service type
+    Request,
+
+    /// The response of a service type
+    Response,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Section::Message => write!(f, "message"),
+            Section::Request => write!(f, "request"),
+            Section::Response => write!(f, "response"),
+        }
+    }
+}
+
+/// Represents a difference between two versions of a type that affects their compatibility
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The type changed from a message type to a service type or the other way around
+    Kind {
+        /// `true` if the old version is a service type
+        old: bool,
+        /// `true` if the new version is a service type
+        new: bool,
+    },
+
+    /// The fixed port ID was added, removed or changed
+    PortId {
+        /// The fixed port ID of the old version
+        old: Option<u16>,
+        /// The fixed port ID of the new version
+        new: Option<u16>,
+    },
+
+    /// The extent in bits changed
+    Extent {
+        /// The changed section
+        section: Section,
+        /// The extent of the old version
+        old: u64,
+        /// The extent of the new version
+        new: u64,
+    },
+
+    /// The @sealed directive was added or removed
+    Sealed {
+        /// The changed section
+        section: Section,
+        /// `true` if the old version is sealed
+        old: bool,
+        /// `true` if the new version is sealed
+        new: bool,
+    },
+
+    /// The @union directive was added or removed
+    Union {
+        /// The changed section
+        section: Section,
+        /// `true` if the old version is a union
+        old: bool,
+        /// `true` if the new version is a union
+        new: bool,
+    },
+
+    /// The number of bits of the tag of a union changed
+    Tag {
+        /// The changed section
+        section: Section,
+        /// The tag bits of the old version
+        old: u8,
+        /// The tag bits of the new version
+        new: u8,
+    },
+
+    /// A field was added or removed, or its serialized form changed
+    Field {
+        /// The changed section
+        section: Section,
+        /// The position of the field among the fields of the section, starting at 0
+        index: usize,
+        /// The old field, e.g. `uint8 value`, unless it was added
+        old: Option<String>,
+        /// The new field, unless it was removed
+        new: Option<String>,
+        /// `true` if the section is sealed, so that added fields change its length
+        sealed: bool,
+    },
+}
+
+impl Change {
+    /// Returns `true` if data serialized by one version can't be read by the other version
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            // a fixed port ID can be added but not changed or removed
+            Change::PortId { old, .. } => old.is_some(),
+            // delimited types can be extended with fields, which older readers ignore
+            Change::Field {
+                old: None, sealed, ..
+            } => *sealed,
+            _ => true,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = |service: &bool| match service {
+            true => "service",
+            false => "message",
+        };
+
+        match self {
+            Change::Kind { old, new } => write!(
+                f,
+                "The type changed from a {} type to a {} type",
+                kind(old),
+                kind(new)
+            ),
+            Change::PortId { old, new } => match (old, new) {
+                (Some(old), Some(new)) => {
+                    write!(f, "The fixed port ID changed from {} to {}", old, new)
+                }
+                (Some(old), None) => write!(f, "The fixed port ID {} was removed", old),
+                (None, Some(new)) => write!(f, "The fixed port ID {} was added", new),
+                (None, None) => write!(f, "The fixed port ID is unchanged"),
+            },
+            Change::Extent { section, old, new } => write!(
+                f,
+                "The extent of the {} changed from {} to {} bits",
+                section, old, new
+            ),
+            Change::Sealed { section, new, .. } => match new {
+                true => write!(f, "The {} became sealed", section),
+                false => write!(f, "The {} is no longer sealed", section),
+            },
+            Change::Union { section, new, .. } => match new {
+                true => write!(f, "The {} became a union", section),
+                false => write!(f, "The {} is no longer a union", section),
+            },
+            Change::Tag { section, old, new } => write!(
+                f,
+                "The union tag of the {} changed from {} to {} bits",
+                section, old, new
+            ),
+            Change::Field {
+                section,
+                index,
+                old,
+                new,
+                ..
+            } => match (old, new) {
+                (Some(old), Some(new)) => write!(
+                    f,
+                    "Field {} of the {} changed from `{}` to `{}`",
+                    index, section, old, new
+                ),
+                (Some(old), None) => write!(f, "Field `{}` was removed from the {}", old, section),
+                (None, Some(new)) => write!(f, "Field `{}` was added to the {}", new, section),
+                (None, None) => write!(f, "Field {} of the {} is unchanged", index, section),
+            },
+        }
+    }
+}
+
+/// Represents the changes between two versions of a type that affect their compatibility
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    old_name: String,
+    old_version: (u8, u8),
+    new_name: String,
+    new_version: (u8, u8),
+    changes: Vec<Change>,
+}
+
+impl Compatibility {
+    /// Compares an old version of a type with a new version.  Renaming a field doesn't change its
+    /// serialized form, so only changes to the bits sent on the wire are reported.
+    pub fn new(old: &File, new: &File) -> Self {
+        let mut changes = Vec::new();
+
+        if old.port() != new.port() {
+            changes.push(Change::PortId {
+                old: old.port().copied(),
+                new: new.port().copied(),
+            });
+        }
+
+        match (old.message(), new.message()) {
+            (Some(o), Some(n)) => compare(Section::Message, &o, &n, old, new, &mut changes),
+            (None, None) => {
+                if let (Some(o), Some(n)) = (old.request(), new.request()) {
+                    compare(Section::Request, &o, &n, old, new, &mut changes);
+                }
+                if let (Some(o), Some(n)) = (old.response(), new.response()) {
+                    compare(Section::Response, &o, &n, old, new, &mut changes);
+                }
+            }
+            _ => changes.push(Change::Kind {
+                old: old.is_service(),
+                new: new.is_service(),
+            }),
+        }
+
+        Self {
+            old_name: old.full_name(),
+            old_version: (old.major(), old.minor()),
+            new_name: new.full_name(),
+            new_version: (new.major(), new.minor()),
+            changes,
+        }
+    }
+
+    /// Returns the full name of the old version, e.g. `uavcan.node.Heartbeat`
+    pub fn old_name(&self) -> &str {
+        &self.old_name
+    }
+
+    /// Returns the major and minor version numbers of the old version
+    pub fn old_version(&self) -> (u8, u8) {
+        self.old_version
+    }
+
+    /// Returns the full name of the new version
+    pub fn new_name(&self) -> &str {
+        &self.new_name
+    }
+
+    /// Returns the major and minor version numbers of the new version
+    pub fn new_version(&self) -> (u8, u8) {
+        self.new_version
+    }
+
+    /// Returns the changes, in the order of the sections and fields they affect
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns `true` if the new version can exchange data with the old version
+    pub fn is_compatible(&self) -> bool {
+        !self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// Returns `true` if the new version is a later minor version of the old version
+    pub fn is_minor_bump(&self) -> bool {
+        self.old_version.0 == self.new_version.0 && self.old_version.1 < self.new_version.1
+    }
+
+    /// Returns `true` if the new version is a later minor version that isn't compatible with the
+    /// old version, which the specification forbids
+    pub fn is_breaking_minor_bump(&self) -> bool {
+        self.is_minor_bump() && !self.is_compatible()
+    }
+}
+
+/// Compares the consecutive minor versions of every major version of the types among the files,
+/// e.g. the files of a root namespace returned by [`crate::Parser::files`]
+pub fn compare_minor_versions<'a>(files: impl IntoIterator<Item = &'a File>) -> Vec<Compatibility> {
+    let mut versions: BTreeMap<(String, u8), Vec<&File>> = BTreeMap::new();
+    for file in files {
+        versions
+            .entry((file.full_name(), file.major()))
+            .or_default()
+            .push(file);
+    }
+
+    let mut result = Vec::new();
+    for files in versions.values_mut() {
+        files.sort_by_key(|f| f.minor());
+        for pair in files.windows(2) {
+            result.push(Compatibility::new(pair[0], pair[1]));
+        }
+    }
+
+    result
+}
+
+fn compare(
+    section: Section,
+    old: &Definition,
+    new: &Definition,
+    old_file: &File,
+    new_file: &File,
+    changes: &mut Vec<Change>,
+) {
+    // the extent is unknown when a nested type couldn't be resolved
+    if let (Some(o), Some(n)) = (old.extent_bits(), new.extent_bits()) {
+        if o != n {
+            changes.push(Change::Extent {
+                section,
+                old: o,
+                new: n,
+            });
+        }
+    }
+    if old.is_sealed() != new.is_sealed() {
+        changes.push(Change::Sealed {
+            section,
+            old: old.is_sealed(),
+            new: new.is_sealed(),
+        });
+    }
+    if old.is_union() != new.is_union() {
+        changes.push(Change::Union {
+            section,
+            old: old.is_union(),
+            new: new.is_union(),
+        });
+        // the fields of a union don't follow each other, so comparing them makes no sense
+        return;
+    }
+    if let (Some(o), Some(n)) = (old.tag_bits(), new.tag_bits()) {
+        if o != n {
+            changes.push(Change::Tag {
+                section,
+                old: o,
+                new: n,
+            });
+        }
+    }
+
+    let old_fields: Vec<_> = fields(old, old_file).collect();
+    let new_fields: Vec<_> = fields(new, new_file).collect();
+    for index in 0..old_fields.len().max(new_fields.len()) {
+        let (o, n) = (old_fields.get(index), new_fields.get(index));
+        if let (Some((o, _)), Some((n, _))) = (o, n) {
+            if o.is_compatible(n) {
+                continue;
+            }
+        }
+
+        changes.push(Change::Field {
+            section,
+            index,
+            old: o.map(|(_, s)| s.clone()),
+            new: n.map(|(_, s)| s.clone()),
+            sealed: new.is_sealed(),
+        });
+    }
+}
+
+// The serialized form of a field, which doesn't depend on its name or cast mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldLayout {
+    Bool,
+    Int(u8),
+    Uint(u8),
+    Float(u8),
+    Void(u8),
+    // the full namespace, type and major version, as minor versions are compatible
+    Composite(Vec<String>, String, u8),
+    // the element, whether the length is variable and the maximum length
+    Array(Box<FieldLayout>, bool, u64),
+}
+
+impl FieldLayout {
+    fn bits(&self) -> Option<u8> {
+        match self {
+            FieldLayout::Bool => Some(1),
+            FieldLayout::Int(bits)
+            | FieldLayout::Uint(bits)
+            | FieldLayout::Float(bits)
+            | FieldLayout::Void(bits) => Some(*bits),
+            _ => None,
+        }
+    }
+
+    // Padding can be turned into a field of the same length and back, as readers of the padding
+    // ignore its value
+    fn is_compatible(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FieldLayout::Void(_), _) | (_, FieldLayout::Void(_)) => self.bits() == other.bits(),
+            _ => self == other,
+        }
+    }
+}
+
+// Returns the layout and the description of the fields of a definition
+fn fields<'a>(
+    definition: &Definition<'a>,
+    file: &'a File,
+) -> impl Iterator<Item = (FieldLayout, String)> + 'a {
+    let namespace = |n: &[String]| match n.is_empty() {
+        true => file.namespace().to_vec(),
+        false => n.to_vec(),
+    };
+
+    definition
+        .statements()
+        .iter()
+        .filter(|s| s.is_field())
+        .filter_map(move |s| match s {
+            Statement::Primitive(p) => Some(match p {
+                Primitive::Bool(p) => (FieldLayout::Bool, format!("bool {}", p.name().text())),
+                Primitive::Int(p) => (
+                    FieldLayout::Int(p.bits()),
+                    format!("int{} {}", p.bits(), p.name().text()),
+                ),
+                Primitive::Uint(p) => (
+                    FieldLayout::Uint(p.bits()),
+                    format!("uint{} {}", p.bits(), p.name().text()),
+                ),
+                Primitive::Float(p) => (
+                    FieldLayout::Float(p.bits()),
+                    format!("float{} {}", p.bits(), p.name().text()),
+                ),
+                Primitive::Void(p) => (FieldLayout::Void(p.bits()), format!("void{}", p.bits())),
+            }),
+            Statement::Composite(c) => Some((
+                FieldLayout::Composite(namespace(c.namespace()), c.ctype().to_string(), c.major()),
+                format!(
+                    "{} {}",
+                    type_name(c.namespace(), c.ctype(), c.major(), c.minor()),
+                    c.name().text()
+                ),
+            )),
+            Statement::Array(a) => {
+                let (element, name) = match a.element() {
+                    ArrayElement::Bool => (FieldLayout::Bool, "bool".to_string()),
+                    ArrayElement::Int { bits } => (FieldLayout::Int(*bits), format!("int{}", bits)),
+                    ArrayElement::Uint { bits, .. } => {
+                        (FieldLayout::Uint(*bits), format!("uint{}", bits))
+                    }
+                    ArrayElement::Float { bits, .. } => {
+                        (FieldLayout::Float(*bits), format!("float{}", bits))
+                    }
+                    ArrayElement::Composite {
+                        namespace: n,
+                        ctype,
+                        major,
+                        minor,
+                    } => (
+                        FieldLayout::Composite(namespace(n), ctype.clone(), *major),
+                        type_name(n, ctype, *major, *minor),
+                    ),
+                };
+                let capacity = a.capacity();
+                let length = match capacity {
+                    ArrayCapacity::Fixed(n) => format!("{}", n),
+                    ArrayCapacity::Inclusive(n) => format!("<={}", n),
+                    ArrayCapacity::Exclusive(n) => format!("<{}", n),
+                };
+
+                Some((
+                    FieldLayout::Array(Box::new(element), capacity.is_variable(), capacity.max()),
+                    format!("{}[{}] {}", name, length, a.name().text()),
+                ))
+            }
+            _ => None,
+        })
+}
+
+fn type_name(namespace: &[String], ctype: &str, major: u8, minor: u8) -> String {
+    let mut components = namespace.to_vec();
+    components.push(format!("{}.{}.{}", ctype, major, minor));
+
+    components.join(".")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compare_minor_versions, test::TestDir, Change, Compatibility, File, Section};
+
+    fn parse(name: &str, source: &str) -> File {
+        let directory = TestDir::new();
+        let path = directory.write(name, source);
+
+        File::parse(&path, vec!["demo".to_string()], &()).unwrap()
+    }
+
+    #[test]
+    fn test_compatible_minor_version() {
+        let old = parse("Status.1.0.dsdl", "uint8 mode\nvoid8\n@extent 64\n");
+        // padding became a field, a field was renamed and another was appended
+        let new = parse(
+            "100.Status.1.1.dsdl",
+            "saturated uint8 state\nuint8 level\nbool ok\n@extent 64\n",
+        );
+
+        let target = Compatibility::new(&old, &new);
+
+        assert_eq!(target.old_name(), "demo.Status");
+        assert_eq!(target.old_version(), (1, 0));
+        assert_eq!(target.new_version(), (1, 1));
+        assert_eq!(
+            target.changes(),
+            [
+                Change::PortId {
+                    old: None,
+                    new: Some(100)
+                },
+                Change::Field {
+                    section: Section::Message,
+                    index: 2,
+                    old: None,
+                    new: Some("bool ok".to_string()),
+                    sealed: false,
+                },
+            ]
+        );
+        assert!(target.is_compatible());
+        assert!(target.is_minor_bump());
+        assert!(!target.is_breaking_minor_bump());
+    }
+
+    #[test]
+    fn test_breaking_minor_version() {
+        let old = parse("7.Mode.1.0.dsdl", "uint8 mode\nuint16[<=4] values\n");
+        let new = parse(
+            "8.Mode.1.1.dsdl",
+            "uint8 mode\nuint16[<=5] values\n@sealed\n",
+        );
+
+        let target = Compatibility::new(&old, &new);
+        let changes: Vec<_> = target.changes().iter().map(|c| c.to_string()).collect();
+
+        assert_eq!(
+            changes,
+            [
+                "The fixed port ID changed from 7 to 8",
+                "The extent of the message changed from 120 to 96 bits",
+                "The message became sealed",
+                "Field 1 of the message changed from `uint16[<=4] values` to `uint16[<=5] values`",
+            ]
+        );
+        assert!(target.changes().iter().all(Change::is_breaking));
+        assert!(target.is_breaking_minor_bump());
+    }
+
+    #[test]
+    fn test_services() {
+        let old = parse("Get.1.0.dsdl", "uint8 id\n@sealed\n---\nbool ok\n");
+        let new = parse(
+            "Get.1.1.dsdl",
+            "uint8 id\nbool all\n@sealed\n---\nbool ok\n",
+        );
+        let message = parse("Get.2.0.dsdl", "uint8 id\n");
+
+        let target = Compatibility::new(&old, &new);
+
+        assert_eq!(
+            target.changes(),
+            [
+                Change::Extent {
+                    section: Section::Request,
+                    old: 8,
+                    new: 16
+                },
+                Change::Field {
+                    section: Section::Request,
+                    index: 1,
+                    old: None,
+                    new: Some("bool all".to_string()),
+                    sealed: true,
+                },
+            ]
+        );
+        assert!(target.changes().iter().all(Change::is_breaking));
+
+        // a new major version can break compatibility
+        let target = Compatibility::new(&new, &message);
+        assert_eq!(
+            target.changes(),
+            [Change::Kind {
+                old: true,
+                new: false
+            }]
+        );
+        assert!(!target.is_minor_bump());
+        assert!(!target.is_breaking_minor_bump());
+    }
+
+    #[test]
+    fn test_unions() {
+        let old = parse("Value.1.0.dsdl", "@union\nuint8 a\nbool b\n");
+        let new = parse("Value.1.1.dsdl", "@union\nuint8 a\nbool b\nint8 c\n");
+        let large = parse(
+            "Value.1.2.dsdl",
+            &(0..300).fold("@union\n".to_string(), |s, i| {
+                format!("{}uint8 a{}\n", s, i)
+            }),
+        );
+
+        assert!(Compatibility::new(&old, &new).is_compatible());
+
+        let target = Compatibility::new(&new, &large);
+        assert!(target.changes().contains(&Change::Tag {
+            section: Section::Message,
+            old: 8,
+            new: 16
+        }));
+        assert!(target.is_breaking_minor_bump());
+    }
+
+    #[test]
+    fn test_compare_minor_versions() {
+        let files = [
+            parse("Point.1.1.dsdl", "float32 x\nfloat32 y\n@extent 128\n"),
+            parse("Point.1.0.dsdl", "float32 x\n@extent 128\n"),
+            parse("Point.2.0.dsdl", "float64 x\n"),
+            parse("Point.1.2.dsdl", "float32 x\nfloat16 y\n@extent 128\n"),
+        ];
+
+        let target = compare_minor_versions(&files);
+        let versions: Vec<_> = target
+            .iter()
+            .map(|c| (c.old_version(), c.new_version(), c.is_compatible()))
+            .collect();
+
+        assert_eq!(versions, [((1, 0), (1, 1), true), ((1, 1), (1, 2), false)]);
+    }
+}
@@ -71,17 +71,7 @@ impl Composite {
             Some(r) => r,
         };
 
-        let mut namespace: Vec<String> = result.0.split('.').map(|s| s.to_string()).collect();
-        if namespace.len() < 3 {
-            return Err(DsdlError::Parse(
-                "Composite type declaration is expected to be in the {TYPE}.{MAJOR}.{MINOR} format"
-                    .to_string(),
-            ));
-        }
-        let mut parts = namespace.split_off(namespace.len() - 3);
-        let minor = parts.pop().unwrap().parse::<u8>()?;
-        let major = parts.pop().unwrap().parse::<u8>()?;
-        let ctype = parts.pop().unwrap().to_string();
+        let (namespace, ctype, major, minor) = parse_type(result.0)?;
 
         let result = Name::parse(result.1)?;
         let name = result.0;
@@ -95,6 +85,26 @@ impl Composite {
     }
 }
 
+/// The namespace, type name, major and minor versions of a composite type
+pub(crate) type TypeReference = (Vec<String>, String, u8, u8);
+
+// Splits a composite type reference into its namespace, type name, major and minor versions
+pub(crate) fn parse_type(text: &str) -> DsdlResult<TypeReference> {
+    let mut namespace: Vec<String> = text.split('.').map(|s| s.to_string()).collect();
+    if namespace.len() < 3 {
+        return Err(DsdlError::Parse(
+            "Composite type declaration is expected to be in the {TYPE}.{MAJOR}.{MINOR} format"
+                .to_string(),
+        ));
+    }
+    let mut parts = namespace.split_off(namespace.len() - 3);
+    let minor = parts.pop().unwrap().parse::<u8>()?;
+    let major = parts.pop().unwrap().parse::<u8>()?;
+    let ctype = parts.pop().unwrap().to_string();
+
+    Ok((namespace, ctype, major, minor))
+}
+
 #[cfg(test)]
 mod test {
     use crate::Composite;
@@ -103,7 +113,7 @@ mod test {
     fn test_composite() {
         let ctype = "Mode.1.0 mode";
 
-        let result = Composite::parse(&ctype);
+        let result = Composite::parse(ctype);
         assert!(result.is_ok());
 
         let target = result.unwrap();
@@ -119,7 +129,7 @@ mod test {
     fn test_composite_with_namespace() {
         let ctype = "uavcan.node.Heartbeat.1.2 heartbeat   # some comment ";
 
-        let result = Composite::parse(&ctype);
+        let result = Composite::parse(ctype);
         assert!(result.is_ok());
 
         let target = result.unwrap();
@@ -132,7 +142,6 @@ mod test {
         assert_eq!(target.name().text(), "heartbeat");
         assert!(target
             .comment()
-            .clone()
             .is_some_and(|c| c.text() == " some comment"));
     }
 
@@ -140,7 +149,7 @@ mod test {
     fn test_composite_missing_name() {
         let ctype = "uavcan.node.Heartbeat.1.2 # some comment";
 
-        let result = Composite::parse(&ctype);
+        let result = Composite::parse(ctype);
         assert!(result.is_err());
     }
 
@@ -148,7 +157,7 @@ mod test {
     fn test_composite_missing_version() {
         let ctype = "Heartbeat.1 # some comment";
 
-        let result = Composite::parse(&ctype);
+        let result = Composite::parse(ctype);
         assert!(result.is_err());
     }
 
@@ -156,7 +165,7 @@ mod test {
     fn test_compositewith_namespace_missing_version() {
         let ctype = "uavcan.node.Heartbeat # some comment";
 
-        let result = Composite::parse(&ctype);
+        let result = Composite::parse(ctype);
         assert!(result.is_err());
     }
 }
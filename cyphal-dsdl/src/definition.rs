@@ -0,0 +1,75 @@
+use crate::{
+    bit_length_set::length_prefix_bits, BitLengthSet, Directive, ExtentDirective, Statement,
+};
+
+/// Represents the statements defining a message, or the request or response of a service
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Definition<'a> {
+    statements: &'a [Statement],
+    layout: &'a Layout,
+}
+
+// The serialized lengths and extent of a definition, which are unknown while the composite types
+// it references aren't resolved
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Layout {
+    pub(crate) bit_length_set: Option<BitLengthSet>,
+    pub(crate) extent: Option<u64>,
+}
+
+impl<'a> Definition<'a> {
+    pub(crate) fn new(statements: &'a [Statement], layout: &'a Layout) -> Self {
+        Self { statements, layout }
+    }
+
+    /// Returns the DSDL statements
+    pub fn statements(&self) -> &'a [Statement] {
+        self.statements
+    }
+
+    /// Returns the @extent directive if one exists
+    pub fn extent(&self) -> Option<&'a ExtentDirective> {
+        self.statements.iter().find_map(|s| match s {
+            Statement::Directive(Directive::Extent(extent)) => Some(extent),
+            _ => None,
+        })
+    }
+
+    /// Returns the possible serialized lengths in bits, padded to a byte, if the composite types
+    /// it references were resolved
+    pub fn bit_length_set(&self) -> Option<&'a BitLengthSet> {
+        self.layout.bit_length_set.as_ref()
+    }
+
+    /// Returns the extent in bits, which is the size of the buffer needed to deserialize any
+    /// version of the type.  It is set by the @extent directive, is the maximum length of sealed
+    /// types and defaults to one and a half times the maximum length of the other types.
+    pub fn extent_bits(&self) -> Option<u64> {
+        self.layout.extent
+    }
+
+    /// Returns `true` if the definition has a @sealed directive
+    pub fn is_sealed(&self) -> bool {
+        self.statements
+            .iter()
+            .any(|s| matches!(s, Statement::Directive(Directive::Sealed(_))))
+    }
+
+    /// Returns `true` if the definition has a @union directive
+    pub fn is_union(&self) -> bool {
+        self.statements
+            .iter()
+            .any(|s| matches!(s, Statement::Directive(Directive::Union(_))))
+    }
+
+    /// Returns the number of bits of the tag preceding the variant of a union, which is the
+    /// smallest standard unsigned integer that can hold the index of its last variant
+    pub fn tag_bits(&self) -> Option<u8> {
+        if !self.is_union() {
+            return None;
+        }
+
+        let variants = self.statements.iter().filter(|s| s.is_field()).count() as u64;
+        Some(length_prefix_bits(variants.saturating_sub(1)) as u8)
+    }
+}
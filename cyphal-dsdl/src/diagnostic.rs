@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// Represents the severity of a diagnostic
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Severity {
+    /// Informational output, e.g. from a @print directive
+    Info,
+
+    /// A problem that doesn't prevent the use of the type, e.g. a deprecated type
+    Warning,
+}
+
+/// Represents a message reported while parsing a DSDL file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    path: String,
+    line: u32,
+    severity: Severity,
+    message: String,
+}
+
+impl Diagnostic {
+    /// Constructs a new diagnostic
+    pub fn new(path: String, line: u32, severity: Severity, message: String) -> Self {
+        Self {
+            path,
+            line,
+            severity,
+            message,
+        }
+    }
+
+    /// Returns the path of the file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the line number of the statement that reported the diagnostic
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the severity
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+        };
+
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.path, self.line, severity, self.message
+        )
+    }
+}
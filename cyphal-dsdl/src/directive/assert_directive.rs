@@ -1,4 +1,4 @@
-use crate::{Comment, DsdlError, DsdlResult, Expression};
+use crate::{Comment, DsdlError, DsdlResult, Expression, Scope, Value};
 
 /// Represents an Assert Directive
 #[derive(Debug, Clone, PartialEq)]
@@ -26,7 +26,7 @@ impl AssertDirective {
         self.comment.as_ref()
     }
 
-    pub(crate) fn parse(line: &str) -> DsdlResult<Self> {
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Self> {
         if let Some(line) = line.strip_prefix("@assert") {
             let result = Expression::parse(line)?;
             let expression = result.0;
@@ -34,6 +34,23 @@ impl AssertDirective {
                 Some(s) => Comment::parse(&s)?,
                 None => None,
             };
+
+            match expression.evaluate(scope) {
+                Ok(Value::Boolean(true)) => {}
+                Ok(Value::Boolean(false)) => {
+                    return Err(DsdlError::AssertionFailed(expression.value().to_string()))
+                }
+                Ok(value) => {
+                    return Err(DsdlError::Expression(format!(
+                        "An assertion must evaluate to a boolean but found `{}`",
+                        value
+                    )))
+                }
+                // assertions depending on other types are checked once they are resolved
+                Err(DsdlError::Unresolved(_)) => {}
+                Err(e) => return Err(e),
+            }
+
             Ok(AssertDirective::new(expression, comment)?)
         } else {
             Err(DsdlError::OutOfRange("Unrecognized directive".to_string()))
@@ -43,12 +60,12 @@ impl AssertDirective {
 
 #[cfg(test)]
 mod test {
-    use crate::AssertDirective;
+    use crate::{AssertDirective, DsdlError};
 
     #[test]
     fn test_assert() {
         let assert = "@assert _offset_ % 8 == {0}";
-        let result = AssertDirective::parse(&assert);
+        let result = AssertDirective::parse(assert, &());
         assert!(result.is_ok());
 
         let target = result.unwrap();
@@ -59,14 +76,23 @@ mod test {
     #[test]
     fn test_assert_with_comment() {
         let assert = "@assert _offset_ == {56}  # Fits into a single-frame Classic CAN transfer";
-        let result = AssertDirective::parse(&assert);
+        let result = AssertDirective::parse(assert, &());
         assert!(result.is_ok());
 
         let target = result.unwrap();
         assert_eq!(target.expression.value(), "_offset_ == {56}");
         assert!(target
             .comment()
-            .clone()
             .is_some_and(|c| c.text() == " Fits into a single-frame Classic CAN transfer"))
     }
+
+    #[test]
+    fn test_assert_evaluation() {
+        assert!(AssertDirective::parse("@assert 2 ** 3 == 8", &()).is_ok());
+        assert!(matches!(
+            AssertDirective::parse("@assert 1 > 2", &()),
+            Err(DsdlError::AssertionFailed(_))
+        ));
+        assert!(AssertDirective::parse("@assert 1 + 2", &()).is_err());
+    }
 }
@@ -1,17 +1,29 @@
-use crate::{Comment, DsdlError, DsdlResult, Expression};
+use crate::{Comment, DsdlError, DsdlResult, Expression, Scope};
 
 /// Represents an Extent Directive
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExtentDirective {
     expression: Expression,
+    bits: Option<u64>,
     comment: Option<Comment>,
 }
 
 impl ExtentDirective {
     /// Constructs a new Extent Directive
-    pub fn new(expression: Expression, comment: Option<Comment>) -> DsdlResult<Self> {
+    pub fn new(
+        expression: Expression,
+        bits: Option<u64>,
+        comment: Option<Comment>,
+    ) -> DsdlResult<Self> {
+        if bits.is_some_and(|b| b % 8 != 0) {
+            return Err(DsdlError::OutOfRange(
+                "The extent must be a multiple of 8 bits".to_string(),
+            ));
+        }
+
         Ok(Self {
             expression,
+            bits,
             comment,
         })
     }
@@ -21,12 +33,17 @@ impl ExtentDirective {
         &self.expression
     }
 
+    /// Returns the extent in bits, if the expression could be evaluated
+    pub fn bits(&self) -> Option<u64> {
+        self.bits
+    }
+
     /// Returns the comment if it has one
     pub fn comment(&self) -> Option<&Comment> {
         self.comment.as_ref()
     }
 
-    pub(crate) fn parse(line: &str) -> DsdlResult<Self> {
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Self> {
         if let Some(line) = line.strip_prefix("@extent") {
             let result = Expression::parse(line)?;
             let expression = result.0;
@@ -35,7 +52,24 @@ impl ExtentDirective {
                 None => None,
             };
 
-            ExtentDirective::new(expression, comment)
+            let bits = match expression.evaluate(scope) {
+                Ok(value) => match value.as_rational().and_then(|r| r.to_integer()) {
+                    Some(v) => Some(u64::try_from(v).map_err(|_| {
+                        DsdlError::OutOfRange("The extent can't be negative".to_string())
+                    })?),
+                    None => {
+                        return Err(DsdlError::Expression(format!(
+                            "The extent must be an integer but found `{}`",
+                            value
+                        )))
+                    }
+                },
+                // the extent can depend on other types, which are resolved later on
+                Err(DsdlError::Unresolved(_)) => None,
+                Err(e) => return Err(e),
+            };
+
+            ExtentDirective::new(expression, bits, comment)
         } else {
             Err(DsdlError::OutOfRange("Unrecognized directive".to_string()))
         }
@@ -49,11 +83,26 @@ mod test {
     #[test]
     fn test_extent() {
         let extent = "@extent 12 * 8";
-        let result = ExtentDirective::parse(&extent);
+        let result = ExtentDirective::parse(extent, &());
         assert!(result.is_ok());
 
         let target = result.unwrap();
         assert_eq!(target.expression.value(), "12 * 8");
+        assert_eq!(target.bits(), Some(96));
         assert!(target.comment().is_none())
     }
+
+    #[test]
+    fn test_invalid_extent() {
+        assert!(ExtentDirective::parse("@extent 12", &()).is_err());
+        assert!(ExtentDirective::parse("@extent -8", &()).is_err());
+        assert!(ExtentDirective::parse("@extent true", &()).is_err());
+    }
+
+    #[test]
+    fn test_unresolved_extent() {
+        let result = ExtentDirective::parse("@extent Other.1.0._extent_", &());
+        assert!(result.is_ok());
+        assert!(result.unwrap().bits().is_none());
+    }
 }
@@ -4,23 +4,35 @@ pub use assert_directive::AssertDirective;
 mod extent_directive;
 pub use extent_directive::ExtentDirective;
 
-use crate::{Comment, DsdlError, DsdlResult};
+mod print_directive;
+pub use print_directive::PrintDirective;
+
+use crate::{Comment, DsdlError, DsdlResult, Scope};
 
 /// Represents a directive
 #[derive(Debug, Clone, PartialEq)]
 pub enum Directive {
-    /// Represents the @sealed directive and contains an optional comment
+    /// Represents the @assert directive
     Assert(AssertDirective),
 
-    /// Represents the @sealed directive and contains an optional comment
+    /// Represents the @deprecated directive and contains an optional comment
+    Deprecated(Option<Comment>),
+
+    /// Represents the @extent directive
     Extent(ExtentDirective),
 
+    /// Represents the @print directive
+    Print(PrintDirective),
+
     /// Represents the @sealed directive and contains an optional comment
     Sealed(Option<Comment>),
+
+    /// Represents the @union directive and contains an optional comment
+    Union(Option<Comment>),
 }
 
 impl Directive {
-    pub(crate) fn parse(line: &str) -> DsdlResult<Directive> {
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Directive> {
         if !line.starts_with('@') {
             return Err(DsdlError::Parse(
                 "Directives should start with an '@' symbol".to_string(),
@@ -28,23 +40,23 @@ impl Directive {
         }
 
         if line.starts_with("@assert") {
-            let directive = AssertDirective::parse(line)?;
+            let directive = AssertDirective::parse(line, scope)?;
             Ok(Directive::Assert(directive))
-        } else if line.starts_with("@deprecated") {
-            //TODO: implement @deprecated directive
-            Err(DsdlError::NotImplemented)
+        } else if let Some(line) = line.strip_prefix("@deprecated") {
+            let comment = Comment::parse(line)?;
+            Ok(Directive::Deprecated(comment))
         } else if line.starts_with("@extent") {
-            let directive = ExtentDirective::parse(line)?;
+            let directive = ExtentDirective::parse(line, scope)?;
             Ok(Directive::Extent(directive))
         } else if line.starts_with("@print") {
-            //TODO: implement @print directive
-            Err(DsdlError::NotImplemented)
+            let directive = PrintDirective::parse(line, scope)?;
+            Ok(Directive::Print(directive))
         } else if let Some(line) = line.strip_prefix("@sealed") {
             let comment = Comment::parse(line)?;
             Ok(Directive::Sealed(comment))
-        } else if line.starts_with("@union") {
-            //TODO: implement @union directive
-            Err(DsdlError::NotImplemented)
+        } else if let Some(line) = line.strip_prefix("@union") {
+            let comment = Comment::parse(line)?;
+            Ok(Directive::Union(comment))
         } else {
             Err(DsdlError::OutOfRange("Unrecognized directive".to_string()))
         }
@@ -58,7 +70,7 @@ mod test {
     #[test]
     fn test_assert_enum() {
         let assert = "@assert _offset_ % 8 == {0}";
-        let result = Directive::parse(&assert);
+        let result = Directive::parse(assert, &());
         assert!(result.is_ok());
 
         let directive = result.unwrap();
@@ -68,7 +80,7 @@ mod test {
     #[test]
     fn test_assert_enum_with_comment() {
         let assert = "@assert _offset_ == {56}  # Fits into a single-frame Classic CAN transfer";
-        let result = Directive::parse(&assert);
+        let result = Directive::parse(assert, &());
         assert!(result.is_ok());
 
         let directive = result.unwrap();
@@ -78,10 +90,42 @@ mod test {
     #[test]
     fn test_extent_enum() {
         let extent = "@extent 12 * 8";
-        let result = Directive::parse(&extent);
+        let result = Directive::parse(extent, &());
         assert!(result.is_ok());
 
         let directive = result.unwrap();
         assert!(matches!(directive, Directive::Extent { .. }));
     }
+
+    #[test]
+    fn test_deprecated_enum() {
+        let result = Directive::parse("@deprecated  # use 2.0 instead", &());
+        assert!(result.is_ok());
+
+        let directive = result.unwrap();
+        assert!(matches!(directive, Directive::Deprecated(Some(_))));
+    }
+
+    #[test]
+    fn test_print_enum() {
+        let result = Directive::parse("@print _offset_", &());
+        assert!(result.is_ok());
+
+        let directive = result.unwrap();
+        assert!(matches!(directive, Directive::Print { .. }));
+    }
+
+    #[test]
+    fn test_union_enum() {
+        let result = Directive::parse("@union", &());
+        assert!(result.is_ok());
+
+        let directive = result.unwrap();
+        assert!(matches!(directive, Directive::Union(None)));
+    }
+
+    #[test]
+    fn test_unrecognized_directive() {
+        assert!(Directive::parse("@unknown", &()).is_err());
+    }
 }
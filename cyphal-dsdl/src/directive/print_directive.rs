@@ -0,0 +1,100 @@
+use crate::{expression::split_comment, Comment, DsdlError, DsdlResult, Expression, Scope, Value};
+
+/// Represents a Print Directive
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintDirective {
+    expression: Option<Expression>,
+    value: Option<Value>,
+    comment: Option<Comment>,
+}
+
+impl PrintDirective {
+    /// Constructs a new Print Directive
+    pub fn new(
+        expression: Option<Expression>,
+        value: Option<Value>,
+        comment: Option<Comment>,
+    ) -> DsdlResult<Self> {
+        Ok(Self {
+            expression,
+            value,
+            comment,
+        })
+    }
+
+    /// Returns the expression of the directive if it has one
+    pub fn expression(&self) -> Option<&Expression> {
+        self.expression.as_ref()
+    }
+
+    /// Returns the value of the expression, if it could be evaluated
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    /// Returns the comment if it has one
+    pub fn comment(&self) -> Option<&Comment> {
+        self.comment.as_ref()
+    }
+
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Self> {
+        if let Some(line) = line.strip_prefix("@print") {
+            let (text, comment) = split_comment(line);
+            let comment = match comment {
+                Some(s) => Comment::parse(s)?,
+                None => None,
+            };
+            if text.trim().is_empty() {
+                return PrintDirective::new(None, None, comment);
+            }
+
+            let expression = Expression::new(text.trim().to_string())?;
+            let value = match expression.evaluate(scope) {
+                Ok(value) => Some(value),
+                // the expression can depend on other types, which are resolved later on
+                Err(DsdlError::Unresolved(_)) => None,
+                Err(e) => return Err(e),
+            };
+
+            PrintDirective::new(Some(expression), value, comment)
+        } else {
+            Err(DsdlError::OutOfRange("Unrecognized directive".to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{PrintDirective, Rational, Value};
+
+    #[test]
+    fn test_print() {
+        let result = PrintDirective::parse("@print 2 ** 4  # sixteen", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert!(target.expression().is_some_and(|e| e.value() == "2 ** 4"));
+        assert_eq!(
+            target.value(),
+            Some(&Value::Rational(Rational::from_integer(16)))
+        );
+        assert!(target.comment().is_some_and(|c| c.text() == " sixteen"));
+    }
+
+    #[test]
+    fn test_empty_print() {
+        let result = PrintDirective::parse("@print", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert!(target.expression().is_none());
+        assert!(target.value().is_none());
+    }
+
+    #[test]
+    fn test_unresolved_print() {
+        let result = PrintDirective::parse("@print UNKNOWN", &());
+        assert!(result.is_ok());
+        assert!(result.unwrap().value().is_none());
+    }
+}
@@ -1,15 +1,40 @@
+use crate::Span;
 use thiserror::Error;
 
 /// Represents a DSDL error
+///
+/// The errors of a statement, such as `Parse` or `Expression`, are raised where the statement is
+/// parsed on its own and its location isn't known. The file that holds it reports them as an
+/// `InvalidStatement` with the span of the statement, or of the name or attribute at fault.
 #[derive(Error, Debug)]
 pub enum DsdlError {
+    /// Assertion failed error, located by the `@assert` directive
+    #[error("Assertion failed: `{0}`")]
+    AssertionFailed(String),
+
+    /// Types depending on each other error
+    #[error("Circular dependency: {0}")]
+    DependencyCycle(String),
+
+    /// Expression evaluation error, located by the statement that holds the expression
+    #[error("Expression error: {0}")]
+    Expression(String),
+
     /// File
     #[error("File error: `{0}`")]
     File(String),
 
-    /// The DSDL statement at line is not valid error
-    #[error("The DSDL statement at line `{0}` is not valid: {1}")]
-    InvalidStatement(u32, String),
+    /// A name that doesn't follow the DSDL naming rules error
+    #[error("Invalid name `{0}`: {1}")]
+    InvalidName(String, String),
+
+    /// The DSDL statement at a location is not valid error
+    #[error("{0}: {1}")]
+    InvalidStatement(Span, String),
+
+    /// An error in a DSDL file
+    #[error("`{0}`: {1}")]
+    InFile(String, Box<DsdlError>),
 
     /// IO error
     #[error("IO error: `{0}`")]
@@ -19,17 +44,40 @@ pub enum DsdlError {
     #[error("Not implemented")]
     NotImplemented,
 
-    /// Out of range error
+    /// Out of range error, located by the statement that holds the value
     #[error("Out of range: `{0}`")]
     OutOfRange(String),
 
-    /// Parse error
+    /// Parse error, located by the statement that couldn't be parsed
     #[error("Parse error: `{0}`")]
     Parse(String),
 
     /// Parse Int
     #[error("ParseIntError error: `{0}`")]
     ParseIntError(#[from] std::num::ParseIntError),
+
+    /// An attribute that a type or a value doesn't have error, located at the attribute
+    #[error("{0} has no attribute `{1}`")]
+    UnknownAttribute(String, String),
+
+    /// A composite type that isn't defined in any of the root namespaces error
+    #[error("{1}: Unresolved type `{0}`")]
+    UnresolvedType(String, Span),
+
+    /// An expression references an identifier or a type that isn't known yet
+    #[error("Unresolved reference to {0}")]
+    Unresolved(String),
+}
+
+impl DsdlError {
+    /// Returns the location of the source text that caused the error, if it is known
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Self::InvalidStatement(span, _) | Self::UnresolvedType(_, span) => Some(span),
+            Self::InFile(_, error) => error.span(),
+            _ => None,
+        }
+    }
 }
 
 /// Represents a DSDL result
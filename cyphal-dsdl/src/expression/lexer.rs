@@ -0,0 +1,361 @@
+use crate::{composite::TypeReference, DsdlError, DsdlResult, Rational};
+
+// Longest operators first, so that `**` isn't read as two `*`
+const OPERATORS: &[&str] = &[
+    "||", "&&", "==", "!=", "<=", ">=", "**", "//", "<", ">", "|", "^", "&", "+", "-", "*", "/",
+    "%", "!", ".", ",", "(", ")", "{", "}",
+];
+
+/// Represents a token of an expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Rational(Rational),
+    String(String),
+    Identifier(String),
+    Type(Vec<String>, String, u8, u8),
+    Operator(&'static str),
+}
+
+pub(crate) fn tokenize(text: &str) -> DsdlResult<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let c = chars[position];
+        if c.is_whitespace() {
+            position += 1;
+        } else if c.is_ascii_digit() {
+            let (value, next) = number(&chars, position)?;
+            tokens.push(Token::Rational(value));
+            position = next;
+        } else if c == '\'' || c == '"' {
+            let (value, next) = string(&chars, position)?;
+            tokens.push(Token::String(value));
+            position = next;
+        } else if is_identifier_start(c) {
+            position = identifier(&chars, position, &mut tokens)?;
+        } else {
+            let rest: String = chars[position..].iter().take(2).collect();
+            match OPERATORS.iter().find(|o| rest.starts_with(*o)) {
+                Some(operator) => {
+                    tokens.push(Token::Operator(operator));
+                    position += operator.len();
+                }
+                None => {
+                    return Err(DsdlError::Parse(format!(
+                        "Unexpected character `{}` in expression",
+                        c
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Finds the composite types referenced by a statement, without evaluating it
+pub(crate) fn type_references(text: &str) -> Vec<TypeReference> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut references = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let c = chars[position];
+        if c == '\'' || c == '"' {
+            match string(&chars, position) {
+                Ok((_, next)) => position = next,
+                Err(_) => break,
+            }
+        } else if c.is_ascii_digit() {
+            // skips numbers, so that the exponent of `1e3` isn't read as an identifier
+            position = take_while(&chars, position, is_identifier);
+        } else if is_identifier_start(c) {
+            let mut tokens = Vec::new();
+            // reading identifiers can't fail
+            position = identifier(&chars, position, &mut tokens).unwrap_or(chars.len());
+            for token in tokens {
+                if let Token::Type(namespace, ctype, major, minor) = token {
+                    references.push((namespace, ctype, major, minor));
+                }
+            }
+        } else {
+            position += 1;
+        }
+    }
+
+    references
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_identifier(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn take_while(chars: &[char], mut position: usize, predicate: impl Fn(char) -> bool) -> usize {
+    while position < chars.len() && predicate(chars[position]) {
+        position += 1;
+    }
+
+    position
+}
+
+// Reads dotted identifiers, which are a composite type when followed by a version number, e.g.
+// `uavcan.node.Health.1.0`, and identifiers with attributes otherwise, e.g. `_offset_.max`
+fn identifier(chars: &[char], position: usize, tokens: &mut Vec<Token>) -> DsdlResult<usize> {
+    let mut components = Vec::new();
+    let mut position = position;
+    loop {
+        let end = take_while(chars, position, is_identifier);
+        components.push(chars[position..end].iter().collect::<String>());
+        position = end;
+
+        let dotted = chars.get(position) == Some(&'.')
+            && chars
+                .get(position + 1)
+                .is_some_and(|c| is_identifier_start(*c));
+        if !dotted {
+            break;
+        }
+        position += 1;
+    }
+
+    if let Some((major, minor, end)) = version(chars, position) {
+        let ctype = components.pop().unwrap();
+        tokens.push(Token::Type(components, ctype, major, minor));
+        return Ok(end);
+    }
+
+    for (i, component) in components.into_iter().enumerate() {
+        if i > 0 {
+            tokens.push(Token::Operator("."));
+        }
+        tokens.push(Token::Identifier(component));
+    }
+
+    Ok(position)
+}
+
+fn version(chars: &[char], position: usize) -> Option<(u8, u8, usize)> {
+    let mut numbers = Vec::new();
+    let mut position = position;
+    for _ in 0..2 {
+        if chars.get(position) != Some(&'.') {
+            return None;
+        }
+        let end = take_while(chars, position + 1, |c| c.is_ascii_digit());
+        let number: String = chars[position + 1..end].iter().collect();
+        numbers.push(number.parse::<u8>().ok()?);
+        position = end;
+    }
+
+    // a version can't be followed by more digits or letters, e.g. `A.1.0e3`
+    if chars.get(position).is_some_and(|c| is_identifier(*c)) {
+        return None;
+    }
+
+    Some((numbers[0], numbers[1], position))
+}
+
+fn number(chars: &[char], position: usize) -> DsdlResult<(Rational, usize)> {
+    let radix = match (chars[position], chars.get(position + 1)) {
+        ('0', Some('x' | 'X')) => 16,
+        ('0', Some('b' | 'B')) => 2,
+        ('0', Some('o' | 'O')) => 8,
+        _ => 10,
+    };
+
+    if radix != 10 {
+        let end = take_while(chars, position + 2, |c| {
+            c.is_ascii_alphanumeric() || c == '_'
+        });
+        let digits: String = chars[position + 2..end]
+            .iter()
+            .filter(|c| **c != '_')
+            .collect();
+        let value = i128::from_str_radix(&digits, radix)?;
+        return Ok((Rational::from_integer(value), end));
+    }
+
+    let digit = |c: char| c.is_ascii_digit() || c == '_';
+    let mut end = take_while(chars, position, digit);
+    let mut mantissa: String = chars[position..end].iter().collect();
+    let mut scale: i32 = 0;
+
+    if chars.get(end) == Some(&'.') && chars.get(end + 1).is_some_and(|c| c.is_ascii_digit()) {
+        let fraction_end = take_while(chars, end + 1, digit);
+        let fraction: String = chars[end + 1..fraction_end].iter().collect();
+        scale -= fraction.chars().filter(|c| *c != '_').count() as i32;
+        mantissa.push_str(&fraction);
+        end = fraction_end;
+    }
+
+    if matches!(chars.get(end), Some('e' | 'E')) {
+        let sign = usize::from(matches!(chars.get(end + 1), Some('+' | '-')));
+        let exponent_end = take_while(chars, end + 1 + sign, |c| c.is_ascii_digit());
+        if exponent_end == end + 1 + sign {
+            return Err(DsdlError::Parse(
+                "Missing the exponent of a number".to_string(),
+            ));
+        }
+        let exponent: String = chars[end + 1..exponent_end].iter().collect();
+        scale += exponent.parse::<i32>()?;
+        end = exponent_end;
+    }
+
+    if chars.get(end).is_some_and(|c| is_identifier(*c)) {
+        return Err(DsdlError::Parse(format!(
+            "Invalid number `{}`",
+            chars[position..=end].iter().collect::<String>()
+        )));
+    }
+
+    let mantissa: String = mantissa.chars().filter(|c| *c != '_').collect();
+    let value = Rational::from_integer(mantissa.parse::<i128>()?);
+    let value =
+        value.mul(&Rational::from_integer(10).pow(&Rational::from_integer(scale.into()))?)?;
+
+    Ok((value, end))
+}
+
+fn string(chars: &[char], position: usize) -> DsdlResult<(String, usize)> {
+    let quote = chars[position];
+    let mut value = String::new();
+    let mut position = position + 1;
+
+    loop {
+        let c = match chars.get(position) {
+            Some(c) => *c,
+            None => return Err(DsdlError::Parse("Unterminated string".to_string())),
+        };
+        position += 1;
+
+        if c == quote {
+            return Ok((value, position));
+        } else if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let escaped = match chars.get(position) {
+            Some(e) => *e,
+            None => return Err(DsdlError::Parse("Unterminated string".to_string())),
+        };
+        position += 1;
+        match escaped {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '\\' | '\'' | '"' => value.push(escaped),
+            'u' => {
+                let code: String = chars.iter().skip(position).take(4).collect();
+                let c = u32::from_str_radix(&code, 16)
+                    .ok()
+                    .filter(|_| code.len() == 4)
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| {
+                        DsdlError::Parse(format!("Invalid unicode escape `\\u{}`", code))
+                    })?;
+                value.push(c);
+                position += 4;
+            }
+            _ => {
+                return Err(DsdlError::Parse(format!(
+                    "Invalid escape sequence `\\{}`",
+                    escaped
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{tokenize, type_references, Token};
+    use crate::Rational;
+
+    #[test]
+    fn test_numbers() {
+        let tokens = tokenize("0x_FF 0b101 0o17 1_000 2.5 1e3 25e-2").unwrap();
+        let values = [
+            Rational::from_integer(255),
+            Rational::from_integer(5),
+            Rational::from_integer(15),
+            Rational::from_integer(1000),
+            Rational::new(5, 2).unwrap(),
+            Rational::from_integer(1000),
+            Rational::new(1, 4).unwrap(),
+        ];
+
+        assert_eq!(tokens, values.map(Token::Rational));
+        assert!(tokenize("12abc").is_err());
+    }
+
+    #[test]
+    fn test_strings() {
+        let tokens = tokenize(r#"'it\'s' "a\tbA""#).unwrap();
+
+        assert_eq!(
+            tokens,
+            [
+                Token::String("it's".to_string()),
+                Token::String("a\tbA".to_string())
+            ]
+        );
+        assert!(tokenize("'open").is_err());
+    }
+
+    #[test]
+    fn test_identifiers_and_types() {
+        let tokens = tokenize("_offset_.max + uavcan.node.Health.1.0.NOMINAL").unwrap();
+
+        assert_eq!(
+            tokens,
+            [
+                Token::Identifier("_offset_".to_string()),
+                Token::Operator("."),
+                Token::Identifier("max".to_string()),
+                Token::Operator("+"),
+                Token::Type(
+                    vec!["uavcan".to_string(), "node".to_string()],
+                    "Health".to_string(),
+                    1,
+                    0
+                ),
+                Token::Operator("."),
+                Token::Identifier("NOMINAL".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_references() {
+        let references = type_references("uavcan.node.Health.1.0[<=2] health  # '1e3 Mode.1.0'");
+        assert_eq!(
+            references,
+            [(
+                vec!["uavcan".to_string(), "node".to_string()],
+                "Health".to_string(),
+                1,
+                0
+            )]
+        );
+
+        let references = type_references("uint8 A = Mode.1.0.X + 1e3 + 'Version.1.0'");
+        assert_eq!(references, [(Vec::new(), "Mode".to_string(), 1, 0)]);
+    }
+
+    #[test]
+    fn test_operators() {
+        let tokens = tokenize("2**8//3 != {1}").unwrap();
+
+        assert_eq!(tokens[1], Token::Operator("**"));
+        assert_eq!(tokens[3], Token::Operator("//"));
+        assert_eq!(tokens[5], Token::Operator("!="));
+        assert!(tokenize("1 $ 2").is_err());
+    }
+}
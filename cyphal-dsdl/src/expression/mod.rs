@@ -0,0 +1,191 @@
+pub(crate) mod lexer;
+
+mod node;
+use node::Node;
+
+mod rational;
+pub use rational::Rational;
+
+mod scope;
+pub use scope::Scope;
+
+mod value;
+pub use value::Value;
+
+use crate::{DsdlError, DsdlResult};
+
+/// Represents and Expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    value: String,
+    node: Node,
+}
+
+impl Expression {
+    /// Constructs a new expression
+    pub fn new(value: String) -> DsdlResult<Self> {
+        let node = Node::parse(&value)?;
+
+        Ok(Self { value, node })
+    }
+
+    /// Returns the value of the expression
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Evaluates the expression, resolving its identifiers and type attributes from the scope
+    pub fn evaluate(&self, scope: &dyn Scope) -> DsdlResult<Value> {
+        self.node.evaluate(scope)
+    }
+
+    pub(crate) fn parse(line: &str) -> DsdlResult<(Self, Option<String>)> {
+        let line = line.trim_start();
+        if line.is_empty() {
+            return Err(DsdlError::Parse("No expression is present".to_string()));
+        }
+
+        let (expression, comment) = split_comment(line);
+
+        Ok((
+            Self::new(expression.trim().to_string())?,
+            comment.map(|c| c.to_string()),
+        ))
+    }
+}
+
+// Splits a line at the start of its comment, ignoring the `#` inside of string literals
+pub(crate) fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if c == '#' => return (&line[..index], Some(&line[index..])),
+            None => {}
+        }
+    }
+
+    (line, None)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{DsdlError, DsdlResult, Expression, Rational, Scope, Value};
+
+    struct TestScope;
+
+    impl Scope for TestScope {
+        fn identifier(&self, name: &str) -> DsdlResult<Value> {
+            match name {
+                "MAX" => Ok(Value::Rational(Rational::from_integer(255))),
+                "_offset_" => Value::set(vec![
+                    Value::Rational(Rational::from_integer(8)),
+                    Value::Rational(Rational::from_integer(16)),
+                ]),
+                _ => Err(DsdlError::Unresolved(name.to_string())),
+            }
+        }
+
+        fn type_attribute(
+            &self,
+            namespace: &[String],
+            ctype: &str,
+            version: (u8, u8),
+            attribute: &str,
+        ) -> DsdlResult<Value> {
+            assert_eq!(namespace, ["uavcan", "node"]);
+            assert_eq!((ctype, version, attribute), ("Health", (1, 0), "WARNING"));
+
+            Ok(Value::Rational(Rational::from_integer(2)))
+        }
+    }
+
+    fn evaluate(text: &str) -> DsdlResult<Value> {
+        Expression::new(text.to_string())?.evaluate(&TestScope)
+    }
+
+    fn rational(numerator: i128, denominator: i128) -> Value {
+        Value::Rational(Rational::new(numerator, denominator).unwrap())
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(evaluate("2 ** 8 - 1").unwrap(), rational(255, 1));
+        assert_eq!(evaluate("-2 ** 2").unwrap(), rational(-4, 1));
+        assert_eq!(evaluate("2 ** -1 + 1 / 3").unwrap(), rational(5, 6));
+        assert_eq!(evaluate("(1 + 2) * 3 % 4").unwrap(), rational(1, 1));
+        assert_eq!(evaluate("7 // 2 | 8").unwrap(), rational(11, 1));
+        assert_eq!(evaluate("MAX + 1").unwrap(), rational(256, 1));
+        assert!(evaluate("1 / 0").is_err());
+        assert!(evaluate("1.5 & 1").is_err());
+    }
+
+    #[test]
+    fn test_booleans_and_strings() {
+        assert_eq!(evaluate("!true || 1 < 2").unwrap(), Value::Boolean(true));
+        assert_eq!(evaluate("!1 == 2 && false").unwrap(), Value::Boolean(false));
+        assert_eq!(
+            evaluate("'abc' + \"#d\"").unwrap(),
+            Value::String("abc#d".to_string())
+        );
+        assert!(evaluate("true + 1").is_err());
+    }
+
+    #[test]
+    fn test_sets() {
+        assert_eq!(
+            evaluate("_offset_ % 8 == {0}").unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(evaluate("_offset_.max").unwrap(), rational(16, 1));
+        assert_eq!(evaluate("{1, 2, 2}.count").unwrap(), rational(2, 1));
+        assert_eq!(evaluate("{1} < {1, 2}").unwrap(), Value::Boolean(true));
+        assert_eq!(
+            evaluate("{1, 2} + {10, 20}").unwrap(),
+            Value::set(vec![
+                rational(11, 1),
+                rational(12, 1),
+                rational(21, 1),
+                rational(22, 1)
+            ])
+            .unwrap()
+        );
+        assert!(evaluate("{1, true}").is_err());
+        assert!(evaluate("{}.max").is_err());
+    }
+
+    #[test]
+    fn test_attributes() {
+        assert_eq!(
+            evaluate("uavcan.node.Health.1.0.WARNING + 1").unwrap(),
+            rational(3, 1)
+        );
+        assert!(evaluate("uavcan.node.Health.1.0").is_err());
+        assert!(matches!(evaluate("UNKNOWN"), Err(DsdlError::Unresolved(_))));
+        assert!(matches!(
+            ().type_attribute(&[], "Health", (1, 0), "WARNING"),
+            Err(DsdlError::Unresolved(_))
+        ));
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert!(Expression::new("1 +".to_string()).is_err());
+        assert!(Expression::new("(1".to_string()).is_err());
+        assert!(Expression::new("{1 2}".to_string()).is_err());
+        assert!(Expression::new("1 2".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_parse_with_comment() {
+        let (expression, comment) = Expression::parse(" '#' + 'a'  # comment").unwrap();
+
+        assert_eq!(expression.value(), "'#' + 'a'");
+        assert_eq!(comment.as_deref(), Some("# comment"));
+    }
+}
@@ -0,0 +1,369 @@
+use super::lexer::{tokenize, Token};
+use crate::{DsdlError, DsdlResult, Rational, Scope, Value};
+
+// Binary operators from the lowest to the highest precedence after the logical operators, all of
+// them left associative
+const PRECEDENCE: &[&[&str]] = &[
+    &["==", "!=", "<=", ">=", "<", ">"],
+    &["|", "^", "&"],
+    &["+", "-"],
+    &["*", "/", "//", "%"],
+];
+
+/// Represents the syntax tree of an expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    Literal(Value),
+    Identifier(String),
+    Type(Vec<String>, String, u8, u8),
+    Set(Vec<Node>),
+    Attribute(Box<Node>, String),
+    Unary(&'static str, Box<Node>),
+    Binary(&'static str, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    pub(crate) fn parse(text: &str) -> DsdlResult<Self> {
+        let tokens = tokenize(text)?;
+        let mut parser = NodeParser {
+            tokens: &tokens,
+            position: 0,
+        };
+
+        let node = parser.logical()?;
+        match parser.peek() {
+            None => Ok(node),
+            Some(token) => Err(DsdlError::Parse(format!(
+                "Unexpected token {:?} in expression",
+                token
+            ))),
+        }
+    }
+
+    pub(crate) fn evaluate(&self, scope: &dyn Scope) -> DsdlResult<Value> {
+        match self {
+            Node::Literal(value) => Ok(value.clone()),
+            Node::Identifier(name) => scope.identifier(name),
+            Node::Type(_, ctype, ..) => Err(DsdlError::Expression(format!(
+                "The type `{}` can only be used to access its attributes",
+                ctype
+            ))),
+            Node::Set(items) => {
+                let items = items
+                    .iter()
+                    .map(|i| i.evaluate(scope))
+                    .collect::<DsdlResult<Vec<Value>>>()?;
+                Value::set(items)
+            }
+            Node::Attribute(node, attribute) => match node.as_ref() {
+                Node::Type(namespace, ctype, major, minor) => {
+                    scope.type_attribute(namespace, ctype, (*major, *minor), attribute)
+                }
+                node => set_attribute(node.evaluate(scope)?, attribute),
+            },
+            Node::Unary(operator, node) => unary(operator, node.evaluate(scope)?),
+            Node::Binary(operator, left, right) => {
+                binary(operator, left.evaluate(scope)?, right.evaluate(scope)?)
+            }
+        }
+    }
+}
+
+struct NodeParser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> NodeParser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> DsdlResult<&'a Token> {
+        let token = self
+            .peek()
+            .ok_or_else(|| DsdlError::Parse("Unexpected end of expression".to_string()))?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn operator(&mut self, operators: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Operator(o)) if operators.contains(o) => {
+                self.position += 1;
+                Some(o)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, operator: &str) -> DsdlResult<()> {
+        match self.operator(&[operator]) {
+            Some(_) => Ok(()),
+            None => Err(DsdlError::Parse(format!(
+                "Expected `{}` in expression",
+                operator
+            ))),
+        }
+    }
+
+    fn logical(&mut self) -> DsdlResult<Node> {
+        let mut node = self.logical_not()?;
+        while let Some(operator) = self.operator(&["||", "&&"]) {
+            node = Node::Binary(operator, Box::new(node), Box::new(self.logical_not()?));
+        }
+
+        Ok(node)
+    }
+
+    // The logical negation has a lower precedence than the comparisons, e.g. `!a == b`
+    // negates `a == b`
+    fn logical_not(&mut self) -> DsdlResult<Node> {
+        if self.operator(&["!"]).is_some() {
+            return Ok(Node::Unary("!", Box::new(self.logical_not()?)));
+        }
+
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: usize) -> DsdlResult<Node> {
+        if level == PRECEDENCE.len() {
+            return self.inversion();
+        }
+
+        let mut node = self.binary(level + 1)?;
+        while let Some(operator) = self.operator(PRECEDENCE[level]) {
+            node = Node::Binary(operator, Box::new(node), Box::new(self.binary(level + 1)?));
+        }
+
+        Ok(node)
+    }
+
+    fn inversion(&mut self) -> DsdlResult<Node> {
+        match self.operator(&["+", "-"]) {
+            Some(operator) => Ok(Node::Unary(operator, Box::new(self.inversion()?))),
+            None => self.exponential(),
+        }
+    }
+
+    // The exponentiation is right associative and binds tighter than a negation on its left,
+    // e.g. `-2 ** 2` is `-(2 ** 2)` while `2 ** -1` is `2 ** (-1)`
+    fn exponential(&mut self) -> DsdlResult<Node> {
+        let node = self.attribute()?;
+        match self.operator(&["**"]) {
+            Some(operator) => Ok(Node::Binary(
+                operator,
+                Box::new(node),
+                Box::new(self.inversion()?),
+            )),
+            None => Ok(node),
+        }
+    }
+
+    fn attribute(&mut self) -> DsdlResult<Node> {
+        let mut node = self.atom()?;
+        while self.operator(&["."]).is_some() {
+            match self.next()? {
+                Token::Identifier(name) => node = Node::Attribute(Box::new(node), name.clone()),
+                _ => {
+                    return Err(DsdlError::Parse(
+                        "Expected an attribute name after `.`".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(node)
+    }
+
+    fn atom(&mut self) -> DsdlResult<Node> {
+        match self.next()? {
+            Token::Rational(r) => Ok(Node::Literal(Value::Rational(*r))),
+            Token::String(s) => Ok(Node::Literal(Value::String(s.clone()))),
+            Token::Identifier(name) => Ok(match name.as_str() {
+                "true" => Node::Literal(Value::Boolean(true)),
+                "false" => Node::Literal(Value::Boolean(false)),
+                _ => Node::Identifier(name.clone()),
+            }),
+            Token::Type(namespace, ctype, major, minor) => {
+                Ok(Node::Type(namespace.clone(), ctype.clone(), *major, *minor))
+            }
+            Token::Operator("(") => {
+                let node = self.logical()?;
+                self.expect(")")?;
+                Ok(node)
+            }
+            Token::Operator("{") => {
+                let mut items = Vec::new();
+                if self.operator(&["}"]).is_some() {
+                    return Ok(Node::Set(items));
+                }
+                loop {
+                    items.push(self.logical()?);
+                    if self.operator(&["}"]).is_some() {
+                        return Ok(Node::Set(items));
+                    }
+                    self.expect(",")?;
+                }
+            }
+            token => Err(DsdlError::Parse(format!(
+                "Unexpected token {:?} in expression",
+                token
+            ))),
+        }
+    }
+}
+
+fn set_attribute(value: Value, attribute: &str) -> DsdlResult<Value> {
+    let items = match &value {
+        Value::Set(items) => items,
+        _ => {
+            return Err(DsdlError::UnknownAttribute(
+                format!("A {}", value.kind()),
+                attribute.to_string(),
+            ))
+        }
+    };
+
+    let empty = || DsdlError::Expression(format!("An empty set has no `{}`", attribute));
+    match attribute {
+        "count" => Ok(Value::Rational(Rational::from_integer(items.len() as i128))),
+        "min" => items.first().cloned().ok_or_else(empty),
+        "max" => items.last().cloned().ok_or_else(empty),
+        _ => Err(DsdlError::UnknownAttribute(
+            "A set".to_string(),
+            attribute.to_string(),
+        )),
+    }
+}
+
+fn unary(operator: &str, value: Value) -> DsdlResult<Value> {
+    match (operator, value) {
+        ("!", Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+        ("+", Value::Rational(r)) => Ok(Value::Rational(r)),
+        ("-", Value::Rational(r)) => Ok(Value::Rational(r.neg()?)),
+        (operator, Value::Set(items)) if operator != "!" => Value::set(
+            items
+                .into_iter()
+                .map(|i| unary(operator, i))
+                .collect::<DsdlResult<Vec<Value>>>()?,
+        ),
+        (operator, value) => Err(invalid_operand(operator, &value)),
+    }
+}
+
+fn binary(operator: &str, left: Value, right: Value) -> DsdlResult<Value> {
+    match (left, right) {
+        (Value::Rational(a), Value::Rational(b)) => rational(operator, &a, &b),
+        (Value::Boolean(a), Value::Boolean(b)) => match operator {
+            "||" => Ok(Value::Boolean(a || b)),
+            "&&" => Ok(Value::Boolean(a && b)),
+            "==" => Ok(Value::Boolean(a == b)),
+            "!=" => Ok(Value::Boolean(a != b)),
+            _ => Err(invalid_operand(operator, &Value::Boolean(a))),
+        },
+        (Value::String(a), Value::String(b)) => match operator {
+            "+" => Ok(Value::String(a + &b)),
+            "==" => Ok(Value::Boolean(a == b)),
+            "!=" => Ok(Value::Boolean(a != b)),
+            _ => Err(invalid_operand(operator, &Value::String(a))),
+        },
+        (Value::Set(a), Value::Set(b)) => set(operator, a, b),
+        // the other operations on a set are applied to each of its elements
+        (Value::Set(items), value) => Value::set(
+            items
+                .into_iter()
+                .map(|i| binary(operator, i, value.clone()))
+                .collect::<DsdlResult<Vec<Value>>>()?,
+        ),
+        (value, Value::Set(items)) => Value::set(
+            items
+                .into_iter()
+                .map(|i| binary(operator, value.clone(), i))
+                .collect::<DsdlResult<Vec<Value>>>()?,
+        ),
+        (a, b) => Err(DsdlError::Expression(format!(
+            "Operator `{}` can't be applied to a {} and a {}",
+            operator,
+            a.kind(),
+            b.kind()
+        ))),
+    }
+}
+
+fn rational(operator: &str, a: &Rational, b: &Rational) -> DsdlResult<Value> {
+    let value = match operator {
+        "==" => return Ok(Value::Boolean(a == b)),
+        "!=" => return Ok(Value::Boolean(a != b)),
+        "<" => return Ok(Value::Boolean(a < b)),
+        "<=" => return Ok(Value::Boolean(a <= b)),
+        ">" => return Ok(Value::Boolean(a > b)),
+        ">=" => return Ok(Value::Boolean(a >= b)),
+        "+" => a.add(b)?,
+        "-" => a.sub(b)?,
+        "*" => a.mul(b)?,
+        "/" => a.div(b)?,
+        "//" => a.floor_div(b)?,
+        "%" => a.rem(b)?,
+        "**" => a.pow(b)?,
+        "|" | "^" | "&" => {
+            let (a, b) = match (a.to_integer(), b.to_integer()) {
+                (Some(a), Some(b)) => (a, b),
+                _ => {
+                    return Err(DsdlError::Expression(format!(
+                        "Operator `{}` can only be applied to integers",
+                        operator
+                    )))
+                }
+            };
+            Rational::from_integer(match operator {
+                "|" => a | b,
+                "^" => a ^ b,
+                _ => a & b,
+            })
+        }
+        _ => return Err(invalid_operand(operator, &Value::Rational(*a))),
+    };
+
+    Ok(Value::Rational(value))
+}
+
+fn set(operator: &str, a: Vec<Value>, b: Vec<Value>) -> DsdlResult<Value> {
+    let subset = |a: &[Value], b: &[Value]| a.iter().all(|i| b.contains(i));
+    match operator {
+        "==" => Ok(Value::Boolean(a == b)),
+        "!=" => Ok(Value::Boolean(a != b)),
+        "<=" => Ok(Value::Boolean(subset(&a, &b))),
+        ">=" => Ok(Value::Boolean(subset(&b, &a))),
+        "<" => Ok(Value::Boolean(a != b && subset(&a, &b))),
+        ">" => Ok(Value::Boolean(a != b && subset(&b, &a))),
+        "|" => Value::set(a.into_iter().chain(b).collect()),
+        "&" => Value::set(a.into_iter().filter(|i| b.contains(i)).collect()),
+        "^" => {
+            let (only_a, only_b): (Vec<Value>, Vec<Value>) = (
+                a.iter().filter(|i| !b.contains(i)).cloned().collect(),
+                b.iter().filter(|i| !a.contains(i)).cloned().collect(),
+            );
+            Value::set(only_a.into_iter().chain(only_b).collect())
+        }
+        // the arithmetic operators are applied to every pair of elements
+        _ => {
+            let mut items = Vec::new();
+            for i in a.iter() {
+                for j in b.iter() {
+                    items.push(binary(operator, i.clone(), j.clone())?);
+                }
+            }
+            Value::set(items)
+        }
+    }
+}
+
+fn invalid_operand(operator: &str, value: &Value) -> DsdlError {
+    DsdlError::Expression(format!(
+        "Operator `{}` can't be applied to a {}",
+        operator,
+        value.kind()
+    ))
+}
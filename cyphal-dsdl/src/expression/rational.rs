@@ -0,0 +1,262 @@
+use crate::{DsdlError, DsdlResult};
+use std::{cmp::Ordering, fmt};
+
+/// Represents an exact rational number, the numeric type of DSDL expressions
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    /// Constructs a new rational number, reduced to its lowest terms
+    pub fn new(numerator: i128, denominator: i128) -> DsdlResult<Self> {
+        if denominator == 0 {
+            return Err(DsdlError::Expression("Division by zero".to_string()));
+        }
+
+        let divisor = gcd(numerator, denominator);
+        let sign = if denominator < 0 { -1 } else { 1 };
+        let numerator = (numerator / divisor)
+            .checked_mul(sign)
+            .ok_or_else(overflow)?;
+        let denominator = (denominator / divisor)
+            .checked_mul(sign)
+            .ok_or_else(overflow)?;
+
+        Ok(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// Constructs a new rational number from an integer
+    pub fn from_integer(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    /// Returns the numerator
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    /// Returns the denominator, which is always positive
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    /// Returns `true` if the number is an integer
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// Returns the number if it is an integer
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.is_integer() {
+            Some(self.numerator)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the closest floating point number
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    pub(crate) fn from_f64(value: f64) -> DsdlResult<Self> {
+        if !value.is_finite() {
+            return Err(DsdlError::Expression(format!(
+                "`{}` is not a rational number",
+                value
+            )));
+        }
+
+        // every finite float is a binary fraction, which can be converted exactly
+        let mut numerator = value;
+        let mut denominator: i128 = 1;
+        while numerator.fract() != 0.0 {
+            numerator *= 2.0;
+            denominator = denominator.checked_mul(2).ok_or_else(overflow)?;
+        }
+        if numerator.abs() >= i128::MAX as f64 {
+            return Err(overflow());
+        }
+
+        Self::new(numerator as i128, denominator)
+    }
+
+    pub(crate) fn add(&self, other: &Self) -> DsdlResult<Self> {
+        let numerator = mul(self.numerator, other.denominator)?
+            .checked_add(mul(other.numerator, self.denominator)?)
+            .ok_or_else(overflow)?;
+
+        Self::new(numerator, mul(self.denominator, other.denominator)?)
+    }
+
+    pub(crate) fn sub(&self, other: &Self) -> DsdlResult<Self> {
+        self.add(&other.neg()?)
+    }
+
+    pub(crate) fn mul(&self, other: &Self) -> DsdlResult<Self> {
+        Self::new(
+            mul(self.numerator, other.numerator)?,
+            mul(self.denominator, other.denominator)?,
+        )
+    }
+
+    pub(crate) fn div(&self, other: &Self) -> DsdlResult<Self> {
+        Self::new(
+            mul(self.numerator, other.denominator)?,
+            mul(self.denominator, other.numerator)?,
+        )
+    }
+
+    pub(crate) fn floor_div(&self, other: &Self) -> DsdlResult<Self> {
+        Ok(Self::from_integer(self.div(other)?.floor()))
+    }
+
+    pub(crate) fn rem(&self, other: &Self) -> DsdlResult<Self> {
+        self.sub(&self.floor_div(other)?.mul(other)?)
+    }
+
+    pub(crate) fn neg(&self) -> DsdlResult<Self> {
+        Ok(Self {
+            numerator: self.numerator.checked_neg().ok_or_else(overflow)?,
+            denominator: self.denominator,
+        })
+    }
+
+    pub(crate) fn pow(&self, exponent: &Self) -> DsdlResult<Self> {
+        let exponent = match exponent.to_integer() {
+            Some(e) => e,
+            None => {
+                return Err(DsdlError::Expression(format!(
+                    "The exponent `{}` must be an integer",
+                    exponent
+                )))
+            }
+        };
+        let power = u32::try_from(exponent.unsigned_abs()).map_err(|_| overflow())?;
+        let numerator = self.numerator.checked_pow(power).ok_or_else(overflow)?;
+        let denominator = self.denominator.checked_pow(power).ok_or_else(overflow)?;
+
+        if exponent < 0 {
+            Self::new(denominator, numerator)
+        } else {
+            Self::new(numerator, denominator)
+        }
+    }
+
+    fn floor(&self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the denominators are positive, so cross multiplying preserves the order
+        match (
+            self.numerator.checked_mul(other.denominator),
+            other.numerator.checked_mul(self.denominator),
+        ) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => self.to_f64().total_cmp(&other.to_f64()),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    // the denominator is never zero, so neither is the divisor
+    a.max(1) as i128
+}
+
+fn mul(a: i128, b: i128) -> DsdlResult<i128> {
+    a.checked_mul(b).ok_or_else(overflow)
+}
+
+fn overflow() -> DsdlError {
+    DsdlError::Expression("Arithmetic overflow".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Rational;
+
+    #[test]
+    fn test_reduce() {
+        let target = Rational::new(6, -4).unwrap();
+        assert_eq!(target.numerator(), -3);
+        assert_eq!(target.denominator(), 2);
+        assert!(!target.is_integer());
+        assert_eq!(target.to_string(), "-3/2");
+
+        assert!(Rational::new(1, 0).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Rational::new(7, 2).unwrap();
+        let b = Rational::from_integer(2);
+
+        assert_eq!(a.add(&b).unwrap(), Rational::new(11, 2).unwrap());
+        assert_eq!(a.sub(&b).unwrap(), Rational::new(3, 2).unwrap());
+        assert_eq!(a.mul(&b).unwrap(), Rational::from_integer(7));
+        assert_eq!(a.div(&b).unwrap(), Rational::new(7, 4).unwrap());
+        assert_eq!(a.floor_div(&b).unwrap(), Rational::from_integer(1));
+        assert_eq!(a.rem(&b).unwrap(), Rational::new(3, 2).unwrap());
+        assert_eq!(
+            a.neg().unwrap().floor_div(&b).unwrap(),
+            Rational::from_integer(-2)
+        );
+        assert_eq!(
+            b.pow(&Rational::from_integer(-3)).unwrap(),
+            Rational::new(1, 8).unwrap()
+        );
+        assert!(b.pow(&a).is_err());
+        assert!(a.div(&Rational::from_integer(0)).is_err());
+    }
+
+    #[test]
+    fn test_order() {
+        assert!(Rational::new(1, 3).unwrap() < Rational::new(1, 2).unwrap());
+        assert!(Rational::from_integer(-1) < Rational::new(-1, 2).unwrap());
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(
+            Rational::from_f64(0.375).unwrap(),
+            Rational::new(3, 8).unwrap()
+        );
+        assert_eq!(
+            Rational::from_f64(-4.0).unwrap(),
+            Rational::from_integer(-4)
+        );
+        assert!(Rational::from_f64(f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{BitLengthSet, DsdlError, DsdlResult, Value};
+
+/// Resolves the identifiers and type attributes referenced by an expression
+pub trait Scope {
+    /// Returns the value of an identifier, such as a constant or `_offset_`
+    fn identifier(&self, name: &str) -> DsdlResult<Value> {
+        Err(DsdlError::Unresolved(format!("`{}`", name)))
+    }
+
+    /// Returns the value of an attribute of a composite type, such as one of its constants
+    fn type_attribute(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+        attribute: &str,
+    ) -> DsdlResult<Value> {
+        let mut name = namespace.to_vec();
+        name.push(format!(
+            "{}.{}.{}.{}",
+            ctype, version.0, version.1, attribute
+        ));
+
+        Err(DsdlError::Unresolved(format!("`{}`", name.join("."))))
+    }
+
+    /// Returns the possible lengths in bits of a composite type serialized as a field, which
+    /// include the delimiter header of the types that aren't sealed
+    fn field_bit_length_set(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+    ) -> DsdlResult<BitLengthSet> {
+        let mut name = namespace.to_vec();
+        name.push(format!("{}.{}.{}", ctype, version.0, version.1));
+
+        Err(DsdlError::Unresolved(format!("`{}`", name.join("."))))
+    }
+}
+
+/// A scope without any identifier or type
+impl Scope for () {}
@@ -0,0 +1,122 @@
+use crate::{DsdlError, DsdlResult, Rational};
+use std::{cmp::Ordering, fmt};
+
+/// Represents the value of an evaluated expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// rational number
+    Rational(Rational),
+
+    /// boolean
+    Boolean(bool),
+
+    /// string
+    String(String),
+
+    /// set of values of the same kind, sorted and without duplicates
+    Set(Vec<Value>),
+}
+
+impl Value {
+    /// Constructs a set out of values of the same kind
+    pub fn set(mut items: Vec<Value>) -> DsdlResult<Self> {
+        if let Some(first) = items.first() {
+            if matches!(first, Value::Set(_)) {
+                return Err(DsdlError::Expression(
+                    "A set can't contain other sets".to_string(),
+                ));
+            }
+            if items.iter().any(|i| i.kind() != first.kind()) {
+                return Err(DsdlError::Expression(
+                    "The elements of a set must be of the same kind".to_string(),
+                ));
+            }
+        }
+
+        items.sort_by(compare);
+        items.dedup();
+
+        Ok(Value::Set(items))
+    }
+
+    /// Returns the name of the kind of value, used by error messages
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Rational(_) => "rational",
+            Value::Boolean(_) => "bool",
+            Value::String(_) => "string",
+            Value::Set(_) => "set",
+        }
+    }
+
+    /// Returns the number if the value is rational
+    pub fn as_rational(&self) -> Option<&Rational> {
+        match self {
+            Value::Rational(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean if the value is one
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is one
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Rational(r) => write!(f, "{}", r),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::Set(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "{{{}}}", items.join(", "))
+            }
+        }
+    }
+}
+
+// Orders the values of a set, whose elements are all of the same kind
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Rational(a), Value::Rational(b)) => a.cmp(b),
+        (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Rational, Value};
+
+    fn rational(value: i128) -> Value {
+        Value::Rational(Rational::from_integer(value))
+    }
+
+    #[test]
+    fn test_set() {
+        let target = Value::set(vec![rational(3), rational(1), rational(3)]).unwrap();
+
+        assert_eq!(target, Value::Set(vec![rational(1), rational(3)]));
+        assert_eq!(target.to_string(), "{1, 3}");
+    }
+
+    #[test]
+    fn test_invalid_set() {
+        assert!(Value::set(vec![rational(1), Value::Boolean(true)]).is_err());
+        assert!(Value::set(vec![Value::Set(Vec::new())]).is_err());
+    }
+}
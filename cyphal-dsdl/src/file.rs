@@ -1,5 +1,14 @@
-use crate::{Composite, Directive, DsdlError, DsdlResult, Primitive, Statement};
+use crate::{
+    bit_length_set::{length_prefix_bits, BitLengthSet},
+    composite::TypeReference,
+    definition::Layout,
+    expression::{lexer::type_references, split_comment},
+    name::validate,
+    Array, ArrayElement, Composite, Definition, Diagnostic, Directive, DsdlError, DsdlResult,
+    ExtentDirective, Primitive, Rational, Scope, Severity, Span, Statement, Value,
+};
 use std::{
+    collections::{HashMap, HashSet},
     io::{BufRead as _, BufReader},
     path::Path,
 };
@@ -16,11 +25,17 @@ const ERROR_MINOR_VERSION: &str = "Could not parse the DSDL file name minor vers
 #[derive(Debug)]
 pub struct File {
     port: Option<u16>,
+    namespace: Vec<String>,
     name: String,
     major: u8,
     minor: u8,
     path: String,
     statements: Vec<Statement>,
+    response: Option<usize>,
+    // one for a message, the request and the response for a service
+    layouts: Vec<Layout>,
+    diagnostics: Vec<Diagnostic>,
+    constants: HashMap<String, Value>,
 }
 
 impl File {
@@ -29,11 +44,24 @@ impl File {
         self.port.as_ref()
     }
 
+    /// Returns the namespace, which is empty unless the file was found in a root namespace
+    pub fn namespace(&self) -> &[String] {
+        &self.namespace
+    }
+
     /// Returns the file name
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Returns the full name of the type, e.g. `uavcan.node.Heartbeat`
+    pub fn full_name(&self) -> String {
+        let mut components = self.namespace.clone();
+        components.push(self.name.clone());
+
+        components.join(".")
+    }
+
     /// Returns the major version
     pub fn major(&self) -> u8 {
         self.major
@@ -49,161 +77,611 @@ impl File {
         &self.path
     }
 
-    /// Returns the DSDL statements
+    /// Returns the DSDL statements, which for a service are the statements of its request
+    /// followed by the statements of its response
     pub fn statements(&self) -> &[Statement] {
         &self.statements
     }
 
-    pub(crate) fn parse(path: &Path) -> DsdlResult<Self> {
-        // Make sure it's not a directory
-        if path.is_dir() {
-            return Err(DsdlError::File(ERROR_DIECTORY.to_string()));
-        }
+    /// Returns `true` if the type has a @deprecated directive
+    pub fn is_deprecated(&self) -> bool {
+        self.statements
+            .iter()
+            .any(|s| matches!(s, Statement::Directive(Directive::Deprecated(_))))
+    }
 
-        // Make sure it has a dsdl extention
-        match path.extension() {
-            None => return Err(DsdlError::File(ERROR_EXTENTION.to_string())),
-            Some(ext) => {
-                if ext != DSDL_EXTENTION {
-                    return Err(DsdlError::File(ERROR_EXTENTION.to_string()));
-                }
-            }
-        }
+    /// Returns the diagnostics reported while parsing, such as the output of @print directives
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
 
-        // split the file name into the various components
-        let pieces: Vec<&str> = path
-            .file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .split('.')
-            .collect();
-
-        // mnake sure the number of components is correct
-        let has_port = match pieces.len() {
-            5 => true,
-            4 => false,
-            _ => return Err(DsdlError::File(ERROR_FORMAT.to_string())),
-        };
+    // Returns the value of a constant of a message type
+    pub(crate) fn constant(&self, name: &str) -> Option<&Value> {
+        self.constants.get(name)
+    }
 
-        let mut position = 0;
+    /// Returns `true` if the file defines a service type rather than a message type
+    pub fn is_service(&self) -> bool {
+        self.response.is_some()
+    }
 
-        // get port number
-        let port_id = if has_port {
-            match pieces[position].parse::<u16>() {
-                Ok(value) => {
-                    position += 1;
-                    Some(value)
-                }
-                Err(_) => return Err(DsdlError::File(ERROR_PORT_ID.to_string())),
-            }
-        } else {
-            None
-        };
+    /// Returns the definition of a message type
+    pub fn message(&self) -> Option<Definition> {
+        match self.response {
+            None => Some(Definition::new(&self.statements, &self.layouts[0])),
+            Some(_) => None,
+        }
+    }
 
-        // get short name
-        let short_name = pieces[position].to_string();
-        position += 1;
+    /// Returns the definition of the request of a service type
+    pub fn request(&self) -> Option<Definition> {
+        self.response
+            .map(|index| Definition::new(&self.statements[..index], &self.layouts[0]))
+    }
 
-        // get major version
-        let major = match pieces[position].parse::<u8>() {
-            Ok(value) => {
-                position += 1;
-                value
-            }
-            Err(_) => return Err(DsdlError::File(ERROR_MAJOR_VERSION.to_string())),
-        };
+    /// Returns the definition of the response of a service type
+    pub fn response(&self) -> Option<Definition> {
+        self.response
+            .map(|index| Definition::new(&self.statements[index..], &self.layouts[1]))
+    }
 
-        // get minor version
-        let minor = match pieces[position].parse::<u8>() {
-            Ok(value) => value,
-            Err(_) => return Err(DsdlError::File(ERROR_MINOR_VERSION.to_string())),
-        };
+    // Parses a file of the given namespace, where the attributes of other types are resolved by
+    // `types`
+    pub(crate) fn parse(
+        path: &Path,
+        namespace: Vec<String>,
+        types: &dyn Scope,
+    ) -> DsdlResult<Self> {
+        let (port_id, short_name, major, minor) = parse_file_name(path)?;
 
         let target = std::fs::File::open(path)?;
-        let mut reader = BufReader::new(target);
+        let reader = BufReader::new(target);
+        let file_path = path.display().to_string();
 
         let mut statements: Vec<Statement> = Vec::new();
-        let mut line_number = 1;
-
-        loop {
-            let mut line = String::new();
-            let len = reader.read_line(&mut line)?;
-            if len == 0 {
-                // reached EoF
-                break;
-            }
+        let mut response = None;
+        let mut layouts = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut scope = FileScope::new(&namespace, types);
+        // the last statement, where the errors found once the file is read are reported
+        let mut last = (0, String::new());
 
-            // remove new lines
-            if line.ends_with('\n') {
-                line.pop();
-                if line.ends_with('\r') {
-                    line.pop();
-                }
-            }
+        for (index, source) in reader.lines().enumerate() {
+            let source = source?;
+            let line_number = index as u32 + 1;
+            let invalid = |error: &DsdlError, message: String| {
+                DsdlError::InvalidStatement(
+                    locate(&file_path, line_number, &source, error),
+                    message,
+                )
+            };
 
             // ignore leading empty spaces
-            let line = line.trim_start().to_string();
+            let line = source.trim_start().to_string();
+            let count = statements.len();
 
             if line.is_empty() {
                 statements.push(Statement::Empty)
             } else if let Some(s) = line.strip_prefix('#') {
                 statements.push(Statement::Comment(s.to_string()))
+            } else if is_service_separator(&line) {
+                if response.is_some() {
+                    return Err(DsdlError::InvalidStatement(
+                        Span::statement(&file_path, line_number, &source),
+                        "A service can only have one request/response separator".to_string(),
+                    ));
+                }
+                match scope.finish() {
+                    Ok(layout) => layouts.push(layout),
+                    Err(e) => return Err(invalid(&e, e.to_string())),
+                }
+                response = Some(statements.len());
+                // the response is evaluated independently of the request
+                scope = FileScope::new(&namespace, types);
+            } else if is_array(&line) {
+                match Array::parse(&line, &scope) {
+                    Ok(a) => statements.push(Statement::Array(a)),
+                    Err(e) => return Err(invalid(&e, format!("Could not parse array: {}", e))),
+                }
             } else if line.starts_with("bool")
+                || line.starts_with("saturated ")
+                || line.starts_with("truncated ")
                 || line.starts_with("float")
                 || line.starts_with("int")
                 || line.starts_with("uint")
                 || line.starts_with("void")
             {
-                match Primitive::parse(&line) {
+                match Primitive::parse(&line, &scope) {
                     Ok(p) => statements.push(Statement::Primitive(p)),
-                    Err(e) => {
-                        return Err(DsdlError::InvalidStatement(
-                            line_number,
-                            format!("Could not parse primitive: {}", e),
-                        ))
-                    }
+                    Err(e) => return Err(invalid(&e, format!("Could not parse primitive: {}", e))),
                 }
             } else if line.starts_with('@') {
-                match Directive::parse(&line) {
+                match Directive::parse(&line, &scope) {
                     Ok(d) => statements.push(Statement::Directive(d)),
-                    Err(e) => {
-                        return Err(DsdlError::InvalidStatement(
-                            line_number,
-                            format!("Could not parse directive: {}", e),
-                        ))
-                    }
+                    Err(e) => return Err(invalid(&e, format!("Could not parse directive: {}", e))),
                 }
             } else {
                 match Composite::parse(&line) {
                     Ok(c) => statements.push(Statement::Composite(c)),
                     Err(e) => {
-                        return Err(DsdlError::InvalidStatement(
-                            line_number,
+                        return Err(invalid(
+                            &e,
                             format!("Could not parse composite type: {}", e),
                         ))
                     }
                 }
             }
 
-            line_number += 1;
+            // the service separator is the only line that isn't a statement
+            let statement = statements.get(count);
+            if let Some(statement) = statement {
+                if let Err(e) = scope.add(statement) {
+                    return Err(invalid(&e, e.to_string()));
+                }
+            }
+
+            match statement {
+                Some(Statement::Directive(Directive::Print(print))) => {
+                    let message = match (print.value(), print.expression()) {
+                        (Some(value), _) => value.to_string(),
+                        (None, Some(expression)) => expression.value().to_string(),
+                        (None, None) => String::new(),
+                    };
+                    diagnostics.push(Diagnostic::new(
+                        file_path.clone(),
+                        line_number,
+                        Severity::Info,
+                        message,
+                    ));
+                }
+                Some(Statement::Directive(Directive::Deprecated(_))) => {
+                    if response.is_some() {
+                        return Err(DsdlError::InvalidStatement(
+                            Span::statement(&file_path, line_number, &source),
+                            "Only the request of a service can be deprecated".to_string(),
+                        ));
+                    }
+                    diagnostics.push(Diagnostic::new(
+                        file_path.clone(),
+                        line_number,
+                        Severity::Warning,
+                        format!("{}.{}.{} is deprecated", short_name, major, minor),
+                    ));
+                }
+                _ => {}
+            }
+
+            if !line.is_empty() {
+                last = (line_number, source);
+            }
         }
 
         if statements.is_empty() {
-            return Err(DsdlError::Parse("File is empty".to_string()));
+            return Err(DsdlError::InvalidStatement(
+                Span::statement(&file_path, 1, ""),
+                "File is empty".to_string(),
+            ));
         }
+        match scope.finish() {
+            Ok(layout) => layouts.push(layout),
+            Err(e) => {
+                let span = locate(&file_path, last.0, &last.1, &e);
+                return Err(DsdlError::InvalidStatement(span, e.to_string()));
+            }
+        }
+        // only the constants of messages can be referenced by other types
+        let constants = match response {
+            Some(_) => HashMap::new(),
+            None => scope.constants,
+        };
 
         Ok(File {
             port: port_id,
+            namespace,
             name: short_name,
             major,
             minor,
             path: path.to_str().unwrap().to_string(),
             statements,
+            response,
+            layouts,
+            diagnostics,
+            constants,
         })
     }
 }
 
+// Splits the file name into its fixed port ID, short name, major and minor versions
+pub(crate) fn parse_file_name(path: &Path) -> DsdlResult<(Option<u16>, String, u8, u8)> {
+    // Make sure it's not a directory
+    if path.is_dir() {
+        return Err(DsdlError::File(ERROR_DIECTORY.to_string()));
+    }
+
+    // Make sure it has a dsdl extention
+    match path.extension() {
+        None => return Err(DsdlError::File(ERROR_EXTENTION.to_string())),
+        Some(ext) => {
+            if ext != DSDL_EXTENTION {
+                return Err(DsdlError::File(ERROR_EXTENTION.to_string()));
+            }
+        }
+    }
+
+    // split the file name into the various components
+    let pieces: Vec<&str> = path
+        .file_name()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .split('.')
+        .collect();
+
+    // mnake sure the number of components is correct
+    let has_port = match pieces.len() {
+        5 => true,
+        4 => false,
+        _ => return Err(DsdlError::File(ERROR_FORMAT.to_string())),
+    };
+
+    let mut position = 0;
+
+    // get port number
+    let port_id = if has_port {
+        match pieces[position].parse::<u16>() {
+            Ok(value) => {
+                position += 1;
+                Some(value)
+            }
+            Err(_) => return Err(DsdlError::File(ERROR_PORT_ID.to_string())),
+        }
+    } else {
+        None
+    };
+
+    // get short name
+    let short_name = pieces[position].to_string();
+    validate(&short_name)?;
+    position += 1;
+
+    // get major version
+    let major = match pieces[position].parse::<u8>() {
+        Ok(value) => {
+            position += 1;
+            value
+        }
+        Err(_) => return Err(DsdlError::File(ERROR_MAJOR_VERSION.to_string())),
+    };
+
+    // get minor version
+    let minor = match pieces[position].parse::<u8>() {
+        Ok(value) => value,
+        Err(_) => return Err(DsdlError::File(ERROR_MINOR_VERSION.to_string())),
+    };
+
+    Ok((port_id, short_name, major, minor))
+}
+
+// Points at the identifier that an error couldn't resolve or the attribute that doesn't exist, or
+// else at the whole statement
+fn locate(path: &str, line: u32, source: &str, error: &DsdlError) -> Span {
+    match error {
+        DsdlError::Unresolved(name) => Span::find(path, line, source, name.trim_matches('`')),
+        DsdlError::InvalidName(name, _) => Span::find(path, line, source, name),
+        DsdlError::UnknownAttribute(_, attribute) => Span::find(path, line, source, attribute),
+        _ => Span::statement(path, line, source),
+    }
+}
+
+// Returns the composite types referenced by the file, along with where they are referenced
+pub(crate) fn references(path: &Path) -> DsdlResult<Vec<(Span, TypeReference)>> {
+    let target = std::fs::File::open(path)?;
+    let reader = BufReader::new(target);
+    let file_path = path.display().to_string();
+
+    let mut references = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let (text, _) = split_comment(&line);
+        for reference in type_references(text) {
+            // the reference as written, e.g. `Health.1.0` or `uavcan.node.Health.1.0`
+            let mut name = reference.0.clone();
+            name.push(format!("{}.{}.{}", reference.1, reference.2, reference.3));
+            let span = Span::find(&file_path, index as u32 + 1, &line, &name.join("."));
+            references.push((span, reference));
+        }
+    }
+
+    Ok(references)
+}
+
+// Resolves the identifiers in the expressions of a message, or of a section of a service
+struct FileScope<'a> {
+    namespace: &'a [String],
+    types: &'a dyn Scope,
+    constants: HashMap<String, Value>,
+    // the names of the fields and constants
+    names: HashSet<String>,
+    // unknown once a composite field is reached, unless its type is resolved
+    offset: Option<BitLengthSet>,
+    fields: usize,
+    union: bool,
+    sealed: bool,
+    extent: Option<ExtentDirective>,
+}
+
+impl<'a> FileScope<'a> {
+    fn new(namespace: &'a [String], types: &'a dyn Scope) -> Self {
+        Self {
+            namespace,
+            types,
+            constants: HashMap::new(),
+            names: HashSet::new(),
+            offset: Some(BitLengthSet::new(0)),
+            fields: 0,
+            union: false,
+            sealed: false,
+            extent: None,
+        }
+    }
+
+    // Registers the constant, or moves the offset past the field, of a statement
+    fn add(&mut self, statement: &Statement) -> DsdlResult<()> {
+        if let Some(name) = attribute_name(statement) {
+            if !self.names.insert(name.to_string()) {
+                return Err(DsdlError::InvalidName(
+                    name.to_string(),
+                    "an attribute with the same name is already defined".to_string(),
+                ));
+            }
+        }
+
+        let bits = match statement {
+            Statement::Primitive(Primitive::Bool(p)) => match p.value() {
+                Some(v) => return self.constant(p.name().text(), Value::Boolean(*v)),
+                None => Some(BitLengthSet::new(1)),
+            },
+            Statement::Primitive(Primitive::Int(p)) => match p.value() {
+                Some(v) => {
+                    let value = Value::Rational(Rational::from_integer(v.into()));
+                    return self.constant(p.name().text(), value);
+                }
+                None => Some(BitLengthSet::new(p.bits().into())),
+            },
+            Statement::Primitive(Primitive::Uint(p)) => match p.value() {
+                Some(v) => {
+                    let value = Value::Rational(Rational::from_integer(v.into()));
+                    return self.constant(p.name().text(), value);
+                }
+                None => Some(BitLengthSet::new(p.bits().into())),
+            },
+            Statement::Primitive(Primitive::Float(p)) => match p.value() {
+                Some(v) => {
+                    let value = Value::Rational(Rational::from_f64(*v)?);
+                    return self.constant(p.name().text(), value);
+                }
+                None => Some(BitLengthSet::new(p.bits().into())),
+            },
+            Statement::Primitive(Primitive::Void(_)) if self.union => {
+                return Err(DsdlError::Parse(
+                    "A union can't have padding fields".to_string(),
+                ))
+            }
+            Statement::Primitive(Primitive::Void(p)) => Some(BitLengthSet::new(p.bits().into())),
+            Statement::Array(a) => {
+                let element = match a.element() {
+                    ArrayElement::Bool => Some(BitLengthSet::new(1)),
+                    ArrayElement::Int { bits }
+                    | ArrayElement::Uint { bits, .. }
+                    | ArrayElement::Float { bits, .. } => Some(BitLengthSet::new((*bits).into())),
+                    ArrayElement::Composite {
+                        namespace,
+                        ctype,
+                        major,
+                        minor,
+                    } => self.composite(namespace, ctype, (*major, *minor))?,
+                };
+                element.map(|e| e.array(a.capacity()))
+            }
+            Statement::Composite(c) => {
+                self.composite(c.namespace(), c.ctype(), (c.major(), c.minor()))?
+            }
+            Statement::Directive(Directive::Union(_)) => {
+                if self.union || self.fields > 0 {
+                    return Err(DsdlError::Parse(
+                        "The @union directive must precede the fields".to_string(),
+                    ));
+                }
+                self.union = true;
+                return Ok(());
+            }
+            Statement::Directive(Directive::Sealed(_)) => {
+                self.sealed = true;
+                return Ok(());
+            }
+            Statement::Directive(Directive::Extent(extent)) => {
+                self.extent = Some(extent.clone());
+                return Ok(());
+            }
+            Statement::Directive(Directive::Deprecated(_)) => {
+                if self.fields > 0 || !self.constants.is_empty() {
+                    return Err(DsdlError::Parse(
+                        "The @deprecated directive must precede the attributes".to_string(),
+                    ));
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+
+        // the variants of a union share the same offset, right after the tag, while composite
+        // types and arrays of them start on a byte
+        let aligned = match statement {
+            Statement::Array(a) => matches!(a.element(), ArrayElement::Composite { .. }),
+            statement => matches!(statement, Statement::Composite(_)),
+        };
+        self.offset = match (&self.offset, bits) {
+            (Some(_), Some(bits)) if self.union && self.fields == 0 => Some(bits),
+            (Some(offset), Some(bits)) if self.union => Some(offset.union(&bits)),
+            (Some(offset), Some(bits)) if aligned => Some(offset.pad_to_alignment(8).concat(&bits)),
+            (Some(offset), Some(bits)) => Some(offset.concat(&bits)),
+            _ => None,
+        };
+        self.fields += 1;
+
+        Ok(())
+    }
+
+    // Checks the definition once all of its statements were added and computes its layout
+    fn finish(&self) -> DsdlResult<Layout> {
+        if self.union && self.fields < 2 {
+            return Err(DsdlError::Parse(
+                "A union must have at least two variants".to_string(),
+            ));
+        }
+        if self.sealed && self.extent.is_some() {
+            return Err(DsdlError::Parse(
+                "A sealed type can't have an extent".to_string(),
+            ));
+        }
+
+        // composite types are padded to a byte
+        let bit_length_set = self.lengths().map(|l| l.pad_to_alignment(8));
+        let extent = match (&self.extent, &bit_length_set) {
+            (Some(extent), Some(lengths)) => match extent.bits() {
+                Some(bits) if bits < lengths.max() => {
+                    return Err(DsdlError::Parse(format!(
+                        "The extent of {} bits is smaller than the maximum length of {} bits",
+                        bits,
+                        lengths.max()
+                    )))
+                }
+                bits => bits,
+            },
+            (Some(extent), None) => extent.bits(),
+            (None, Some(lengths)) if self.sealed => Some(lengths.max()),
+            // the default extent leaves room for the type to grow by half of its size
+            (None, Some(lengths)) => Some((lengths.max() * 3 / 2).div_ceil(8) * 8),
+            (None, None) => None,
+        };
+
+        Ok(Layout {
+            bit_length_set,
+            extent,
+        })
+    }
+
+    // The lengths of the fields added so far, which for a union include its tag
+    fn lengths(&self) -> Option<BitLengthSet> {
+        match &self.offset {
+            Some(offset) if self.union => {
+                let tag = length_prefix_bits(self.fields.saturating_sub(1) as u64);
+                Some(BitLengthSet::new(tag).concat(offset))
+            }
+            offset => offset.clone(),
+        }
+    }
+
+    // The lengths of a composite field, which are unknown if its type isn't resolved
+    fn composite(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+    ) -> DsdlResult<Option<BitLengthSet>> {
+        match self.field_bit_length_set(namespace, ctype, version) {
+            Ok(lengths) => Ok(Some(lengths)),
+            Err(DsdlError::Unresolved(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn constant(&mut self, name: &str, value: Value) -> DsdlResult<()> {
+        self.constants.insert(name.to_string(), value);
+
+        Ok(())
+    }
+}
+
+// The name of the field or constant declared by a statement, padding fields having none
+fn attribute_name(statement: &Statement) -> Option<&str> {
+    match statement {
+        Statement::Primitive(Primitive::Bool(p)) => Some(p.name().text()),
+        Statement::Primitive(Primitive::Int(p)) => Some(p.name().text()),
+        Statement::Primitive(Primitive::Uint(p)) => Some(p.name().text()),
+        Statement::Primitive(Primitive::Float(p)) => Some(p.name().text()),
+        Statement::Array(a) => Some(a.name().text()),
+        Statement::Composite(c) => Some(c.name().text()),
+        _ => None,
+    }
+}
+
+impl Scope for FileScope<'_> {
+    fn identifier(&self, name: &str) -> DsdlResult<Value> {
+        let value = if name == "_offset_" {
+            self.lengths().map(|l| l.to_value())
+        } else {
+            self.constants.get(name).cloned()
+        };
+
+        value.ok_or_else(|| DsdlError::Unresolved(name.to_string()))
+    }
+
+    fn type_attribute(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+        attribute: &str,
+    ) -> DsdlResult<Value> {
+        // types without a namespace are relative to the namespace of the file
+        let namespace = if namespace.is_empty() {
+            self.namespace
+        } else {
+            namespace
+        };
+
+        self.types
+            .type_attribute(namespace, ctype, version, attribute)
+    }
+
+    fn field_bit_length_set(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+    ) -> DsdlResult<BitLengthSet> {
+        let namespace = if namespace.is_empty() {
+            self.namespace
+        } else {
+            namespace
+        };
+
+        self.types.field_bit_length_set(namespace, ctype, version)
+    }
+}
+
+// Arrays are the only statements with a '[' outside of their comment
+fn is_array(line: &str) -> bool {
+    let line = match line.find('#') {
+        Some(index) => &line[..index],
+        None => line,
+    };
+
+    !line.starts_with('@') && line.contains('[')
+}
+
+// The request and response of a service are separated by a line of at least three dashes
+fn is_service_separator(line: &str) -> bool {
+    let line = match line.find('#') {
+        Some(index) => &line[..index],
+        None => line,
+    };
+    let line = line.trim_end();
+
+    line.len() >= 3 && line.chars().all(|c| c == '-')
+}
+
 impl From<File> for String {
     fn from(file: File) -> Self {
         match file.port {
@@ -221,14 +699,14 @@ impl From<File> for String {
 
 #[cfg(test)]
 mod test {
-    use crate::{DsdlError, File};
+    use crate::{test::TestDir, DsdlError, File, Severity};
     use std::path::Path;
 
     #[test]
     fn test_directory() {
         let target = Path::new("/");
 
-        let result = File::parse(target);
+        let result = File::parse(target, Vec::new(), &());
 
         assert!(result.is_err());
         let err = result.err().unwrap();
@@ -239,7 +717,7 @@ mod test {
     fn test_invalid_extention() {
         let target = Path::new("432.GetInfo.1.0.bad");
 
-        let result = File::parse(target);
+        let result = File::parse(target, Vec::new(), &());
 
         assert!(result.is_err());
         let err = result.err().unwrap();
@@ -254,7 +732,7 @@ mod test {
         ];
 
         for target in targets.iter() {
-            let result = File::parse(target);
+            let result = File::parse(target, Vec::new(), &());
 
             assert!(result.is_err());
             let err = result.err().unwrap();
@@ -266,7 +744,7 @@ mod test {
     fn test_invalid_port() {
         let target = Path::new("4O2.GetInfo.1.0.dsdl");
 
-        let result = File::parse(target);
+        let result = File::parse(target, Vec::new(), &());
 
         assert!(result.is_err());
         let err = result.err().unwrap();
@@ -277,7 +755,7 @@ mod test {
     fn test_invalid_major_version() {
         let target = Path::new("432.GetInfo.a.0.dsdl");
 
-        let result = File::parse(target);
+        let result = File::parse(target, Vec::new(), &());
 
         assert!(result.is_err());
         let err = result.err().unwrap();
@@ -288,10 +766,312 @@ mod test {
     fn test_invalid_minor_version() {
         let target = Path::new("432.GetInfo.1.a.dsdl");
 
-        let result = File::parse(target);
+        let result = File::parse(target, Vec::new(), &());
 
         assert!(result.is_err());
         let err = result.err().unwrap();
         assert!(matches!(err, DsdlError::File { .. }));
     }
+
+    #[test]
+    fn test_message() {
+        let directory = TestDir::new();
+        let path = directory.write("Health.1.0.dsdl", "uint2 value\n@sealed\n");
+
+        let file = File::parse(&path, Vec::new(), &()).unwrap();
+
+        assert!(!file.is_service());
+        assert!(file.request().is_none());
+        assert!(file.response().is_none());
+        let message = file.message().unwrap();
+        assert_eq!(message.statements().len(), 2);
+        assert!(message.is_sealed());
+        assert!(message.extent().is_none());
+    }
+
+    #[test]
+    fn test_service() {
+        let directory = TestDir::new();
+        let path = directory.write(
+            "430.GetInfo.1.0.dsdl",
+            "# Request\n@extent 8 * 8\n----- # Response\nuint8[<=50] name\n@sealed\n",
+        );
+
+        let file = File::parse(&path, Vec::new(), &()).unwrap();
+
+        assert!(file.is_service());
+        assert!(file.message().is_none());
+        assert_eq!(file.statements().len(), 4);
+
+        let request = file.request().unwrap();
+        assert_eq!(request.statements().len(), 2);
+        assert!(!request.is_sealed());
+        assert!(request
+            .extent()
+            .is_some_and(|e| e.expression().value() == "8 * 8"));
+
+        let response = file.response().unwrap();
+        assert_eq!(response.statements().len(), 2);
+        assert!(response.is_sealed());
+        assert!(response.extent().is_none());
+    }
+
+    #[test]
+    fn test_multiple_service_separators() {
+        let directory = TestDir::new();
+        let path = directory.write("Empty.1.0.dsdl", "---\n---\n");
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        assert!(result.is_err());
+        let err = result.err().unwrap();
+        assert!(matches!(err, DsdlError::InvalidStatement(span, _) if span.line() == 2));
+    }
+
+    #[test]
+    fn test_constant_expressions() {
+        let directory = TestDir::new();
+        let path = directory.write("Bytes.1.0.dsdl", "uint8 MAX = 2 ** 4 - 1\nuint8[<=MAX] data\n@assert _offset_ == {8, 16, 24, 32, 40, 48, 56, 64, 72, 80, 88, 96, 104, 112, 120, 128}\n@assert _offset_ % 8 == {0}\n@extent (MAX + 1) * 8\n");
+
+        let file = File::parse(&path, Vec::new(), &()).unwrap();
+
+        let message = file.message().unwrap();
+        assert!(message.extent().is_some_and(|e| e.bits() == Some(128)));
+        assert_eq!(message.extent_bits(), Some(128));
+    }
+
+    #[test]
+    fn test_layouts() {
+        let directory = TestDir::new();
+        let path = directory.write(
+            "Layout.1.0.dsdl",
+            "uint8 a\nbool[<=2] b\n---\nuint8 a\nbool b\n@sealed\n",
+        );
+
+        let file = File::parse(&path, Vec::new(), &()).unwrap();
+
+        // the request can grow by half of its maximum length, rounded up to a byte
+        let request = file.request().unwrap();
+        let lengths = request.bit_length_set().unwrap();
+        assert_eq!(lengths.iter().collect::<Vec<_>>(), [16, 24]);
+        assert_eq!(request.extent_bits(), Some(40));
+
+        let response = file.response().unwrap();
+        let lengths = response.bit_length_set().unwrap();
+        assert!(lengths.is_fixed());
+        assert_eq!(lengths.max(), 16);
+        assert_eq!(response.extent_bits(), Some(16));
+    }
+
+    #[test]
+    fn test_invalid_extents() {
+        let directory = TestDir::new();
+        let definitions = [
+            ("Small.1.0.dsdl", "uint16 a\n@extent 8\n"),
+            ("Sealed.1.0.dsdl", "uint16 a\n@sealed\n@extent 16\n"),
+        ];
+
+        for (name, definition) in definitions {
+            let path = directory.write(name, definition);
+
+            assert!(File::parse(&path, Vec::new(), &()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_failed_assertion() {
+        let directory = TestDir::new();
+        let path = directory.write(
+            "Unaligned.1.0.dsdl",
+            "uint8 a\nbool b\n@assert _offset_ % 8 == {0}\n",
+        );
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        assert!(result.is_err());
+        let err = result.err().unwrap();
+        assert!(matches!(err, DsdlError::InvalidStatement(span, _) if span.line() == 3));
+    }
+
+    #[test]
+    fn test_error_spans() {
+        let directory = TestDir::new();
+        let path = directory.write(
+            "Located.1.0.dsdl",
+            "# header\r\n\r\nuint8 a\r\n---\r\n  uint8 b = UNKNOWN + 1  # note\r\n",
+        );
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        let span = match result {
+            Err(DsdlError::InvalidStatement(span, _)) => span,
+            _ => panic!("expecting an invalid statement"),
+        };
+        assert!(span.path().ends_with("Located.1.0.dsdl"));
+        assert_eq!(span.line(), 5);
+        assert_eq!(span.column(), 13);
+        assert_eq!(span.text(), "UNKNOWN");
+        assert_eq!(span.source(), "  uint8 b = UNKNOWN + 1  # note");
+    }
+
+    #[test]
+    fn test_empty_file() {
+        let directory = TestDir::new();
+        let path = directory.write("Empty.1.0.dsdl", "");
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        assert!(matches!(
+            result,
+            Err(DsdlError::InvalidStatement(span, message))
+                if message == "File is empty" && span.line() == 1 && span.length() == 0
+        ));
+    }
+
+    #[test]
+    fn test_unknown_attribute_span() {
+        let directory = TestDir::new();
+        let path = directory.write("Sizes.1.0.dsdl", "uint8 SIZE = {1, 2}.mean\n@sealed\n");
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        let span = match result {
+            Err(DsdlError::InvalidStatement(span, _)) => span,
+            _ => panic!("expecting an invalid statement"),
+        };
+        assert_eq!(span.line(), 1);
+        assert_eq!(span.column(), 21);
+        assert_eq!(span.text(), "mean");
+    }
+
+    #[test]
+    fn test_duplicate_attributes() {
+        let directory = TestDir::new();
+
+        for (name, source, column) in [
+            (
+                "Fields",
+                "uint8 value
+float32 value
+",
+                9,
+            ),
+            (
+                "Constants",
+                "uint8 A = 1
+uint8 A = 2
+",
+                7,
+            ),
+            (
+                "Mixed",
+                "uint8 value
+bool value = true
+",
+                6,
+            ),
+        ] {
+            let path = directory.write(&format!("{}.1.0.dsdl", name), source);
+
+            let result = File::parse(&path, Vec::new(), &());
+
+            assert!(
+                matches!(
+                    &result,
+                    Err(DsdlError::InvalidStatement(span, _))
+                        if span.line() == 2 && span.column() == column
+                ),
+                "{}: {:?}",
+                name,
+                result.err()
+            );
+        }
+
+        // the request and the response of a service have their own attributes
+        let path = directory.write(
+            "Service.1.0.dsdl",
+            "uint8 value
+---
+uint8 value
+",
+        );
+        assert!(File::parse(&path, Vec::new(), &()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let directory = TestDir::new();
+        let path = directory.write("Named.1.0.dsdl", "uint8 value\nuint8 _count  # hidden\n");
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        assert!(matches!(
+            result,
+            Err(DsdlError::InvalidStatement(span, _))
+                if span.line() == 2 && span.column() == 7 && span.text() == "_count"
+        ));
+
+        let path = directory.write("Struct.1.0.dsdl", "uint8 value\n");
+
+        let result = File::parse(&path, Vec::new(), &());
+
+        assert!(matches!(result, Err(DsdlError::InvalidName(name, _)) if name == "Struct"));
+    }
+
+    #[test]
+    fn test_service_sections_scope() {
+        let directory = TestDir::new();
+        let path = directory.write(
+            "Scoped.1.0.dsdl",
+            "uint8 SIZE = 4\nuint8 a\n---\nuint8[SIZE] b\n",
+        );
+
+        assert!(File::parse(&path, Vec::new(), &()).is_err());
+    }
+
+    #[test]
+    fn test_union() {
+        let directory = TestDir::new();
+        let path = directory.write("Value.1.0.dsdl", "@union\nbool[<=8] bit\nuint16 natural\n@assert _offset_.min == 16 && _offset_.max == 24\n@sealed\n");
+
+        let file = File::parse(&path, Vec::new(), &()).unwrap();
+
+        let message = file.message().unwrap();
+        assert!(message.is_union());
+        assert_eq!(message.tag_bits(), Some(8));
+    }
+
+    #[test]
+    fn test_invalid_unions() {
+        let directory = TestDir::new();
+        let definitions = [
+            ("Single.1.0.dsdl", "@union\nuint8 a\n"),
+            ("Padded.1.0.dsdl", "@union\nuint8 a\nvoid8\n"),
+            ("Late.1.0.dsdl", "uint8 a\n@union\nuint8 b\n"),
+        ];
+
+        for (name, definition) in definitions {
+            let path = directory.write(name, definition);
+
+            assert!(File::parse(&path, Vec::new(), &()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let directory = TestDir::new();
+        let path = directory.write("Old.1.0.dsdl", "@deprecated\nuint8 a\n@print _offset_\n");
+
+        let file = File::parse(&path, Vec::new(), &()).unwrap();
+
+        assert!(file.is_deprecated());
+        let diagnostics = file.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line(), 1);
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+        assert_eq!(diagnostics[0].message(), "Old.1.0 is deprecated");
+        assert_eq!(diagnostics[1].line(), 3);
+        assert_eq!(diagnostics[1].severity(), Severity::Info);
+        assert_eq!(diagnostics[1].message(), "{8}");
+    }
 }
@@ -2,20 +2,38 @@
 #![forbid(missing_docs)]
 #![allow(async_fn_in_trait)]
 
+mod array;
+pub use array::{Array, ArrayCapacity, ArrayElement};
+
+mod bit_length_set;
+pub use bit_length_set::BitLengthSet;
+
+mod cast_mode;
+pub use cast_mode::CastMode;
+
+mod compat;
+pub use compat::{compare_minor_versions, Change, Compatibility, Section};
+
 mod comment;
 pub use comment::Comment;
 
 mod composite;
 pub use composite::Composite;
 
+mod definition;
+pub use definition::Definition;
+
 mod directive;
-pub use directive::{AssertDirective, Directive, ExtentDirective};
+pub use directive::{AssertDirective, Directive, ExtentDirective, PrintDirective};
+
+mod diagnostic;
+pub use diagnostic::{Diagnostic, Severity};
 
 mod error;
 pub use error::{DsdlError, DsdlResult};
 
 mod expression;
-pub use expression::Expression;
+pub use expression::{Expression, Rational, Scope, Value};
 
 mod file;
 pub use file::File;
@@ -31,5 +49,11 @@ pub use primitive::{
     BoolPrimitive, FloatPrimitive, IntPrimitive, Primitive, UintPrimitive, VoidPrimitive,
 };
 
+mod span;
+pub use span::Span;
+
 mod statement;
 pub use statement::Statement;
+
+#[cfg(test)]
+pub(crate) mod test;
@@ -1,14 +1,44 @@
 use crate::{DsdlError, DsdlResult};
 
-/// Represents a comment
+// The words reserved by the specification, regardless of their case, other than the names of the
+// primitive types which are followed by digits
+const RESERVED_WORDS: &[&str] = &[
+    "truncated",
+    "saturated",
+    "true",
+    "false",
+    "bool",
+    "optional",
+    "aligned",
+    "const",
+    "struct",
+    "super",
+    "template",
+    "enum",
+    "self",
+    "and",
+    "or",
+    "not",
+    "auto",
+    "type",
+    // reserved file names on some operating systems
+    "con",
+    "prn",
+    "aux",
+    "nul",
+];
+
+/// Represents the name of a field or a constant
 #[derive(Debug, Clone, PartialEq)]
 pub struct Name {
     text: String,
 }
 
 impl Name {
-    /// Constructs a new comment
+    /// Constructs a new name, which must follow the DSDL naming rules
     pub fn new(text: String) -> DsdlResult<Self> {
+        validate(&text)?;
+
         Ok(Self { text })
     }
 
@@ -28,19 +58,63 @@ impl Name {
             Some(r) => (Self::new(r.0.to_string())?, Some(r.1)),
         };
 
-        //TODO: make sure it's a valid name
         Ok(result)
     }
 }
 
+// Checks that a name, a type name or a namespace component follows the DSDL naming rules: it
+// starts with a letter, is made of letters, digits and underscores, and isn't a reserved word.
+// Names starting with an underscore are reserved for special identifiers such as `_offset_`.
+pub(crate) fn validate(name: &str) -> DsdlResult<()> {
+    let invalid = |reason: &str| Err(DsdlError::InvalidName(name.to_string(), reason.to_string()));
+
+    match name.chars().next() {
+        None => return invalid("a name can't be empty"),
+        Some('_') => return invalid("names starting with an underscore are reserved"),
+        Some(c) if !c.is_ascii_alphabetic() => return invalid("a name must start with a letter"),
+        Some(_) => {}
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return invalid("a name can only have letters, digits and underscores");
+    }
+    if is_reserved(name) {
+        return invalid("the name is a reserved word");
+    }
+
+    Ok(())
+}
+
+fn is_reserved(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    // the primitive types, with or without their number of bits
+    let primitive = ["void", "uint", "int", "float"]
+        .iter()
+        .any(|prefix| name.strip_prefix(prefix).is_some_and(digits));
+    // fixed point types such as `q16_8` and `uq8_8`
+    let fixed_point = name
+        .strip_prefix('u')
+        .unwrap_or(&name)
+        .strip_prefix('q')
+        .and_then(|s| s.split_once('_'))
+        .is_some_and(|(i, f)| !i.is_empty() && !f.is_empty() && digits(i) && digits(f));
+    // reserved file names on some operating systems
+    let device = ["com", "lpt"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|d| d.len() == 1 && digits(d))
+    });
+
+    RESERVED_WORDS.contains(&name.as_str()) || primitive || fixed_point || device
+}
+
 #[cfg(test)]
 mod test {
-    use crate::Name;
+    use crate::{DsdlError, Name};
 
     #[test]
     fn test_name() {
         let name = "uptime";
-        let result = Name::parse(&name);
+        let result = Name::parse(name);
         assert!(result.is_ok());
 
         let tuple = result.unwrap();
@@ -53,7 +127,7 @@ mod test {
     #[test]
     fn test_name_with_comment() {
         let name = " uptime                       # [second]";
-        let result = Name::parse(&name);
+        let result = Name::parse(name);
         assert!(result.is_ok());
 
         let tuple = result.unwrap();
@@ -63,10 +137,64 @@ mod test {
         assert!(extra.is_some_and(|e| e == "                      # [second]"));
     }
 
+    #[test]
+    fn test_invalid_names() {
+        let names = [
+            "",
+            "1st",
+            "_hidden",
+            "has-dash",
+            "héllo",
+            "bool",
+            "Saturated",
+            "TYPE",
+            "void",
+            "uint8",
+            "int",
+            "float64",
+            "q16_8",
+            "UQ8_8",
+            "com1",
+            "Nul",
+        ];
+
+        for name in names {
+            assert!(
+                matches!(Name::new(name.to_string()), Err(DsdlError::InvalidName(..))),
+                "`{}` should be invalid",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_valid_names() {
+        let names = [
+            "uptime",
+            "Heartbeat",
+            "integer",
+            "uint8_value",
+            "void_mode",
+            "q16",
+            "com",
+            "com10",
+            "trueness",
+            "x_",
+        ];
+
+        for name in names {
+            assert!(
+                Name::new(name.to_string()).is_ok(),
+                "`{}` should be valid",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_name_with_value_and_comment() {
         let name = " MAX_PUBLICATION_PERIOD = 1   # [second]";
-        let result = Name::parse(&name);
+        let result = Name::parse(name);
         assert!(result.is_ok());
 
         let tuple = result.unwrap();
@@ -1,10 +1,27 @@
-use crate::{DsdlResult, File};
-use std::{collections::HashMap, fs, path::Path};
+use crate::{
+    composite::TypeReference,
+    file::{parse_file_name, references},
+    name::validate,
+    BitLengthSet, DsdlError, DsdlResult, File, Rational, Scope, Value,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// Represents a DSDL parser
 #[derive(Debug)]
 pub struct Parser {
     files: HashMap<String, File>,
+    // the paths of the parsed files in the order they were parsed, which puts dependencies first
+    order: Vec<String>,
+    // the DSDL files found in the root namespaces along with their namespace
+    definitions: BTreeMap<TypeReference, (PathBuf, Vec<String>)>,
+    // the paths of the parsed files by type
+    types: HashMap<TypeReference, String>,
+    // the paths of the files defining the types referenced by each parsed file
+    dependencies: HashMap<String, Vec<String>>,
 }
 
 impl Parser {
@@ -12,27 +29,452 @@ impl Parser {
     pub fn new() -> DsdlResult<Self> {
         Ok(Self {
             files: HashMap::new(),
+            order: Vec::new(),
+            definitions: BTreeMap::new(),
+            types: HashMap::new(),
+            dependencies: HashMap::new(),
         })
     }
 
-    /// Reads a DSDL file
+    /// Reads a DSDL file.  The types it references are read first if the file belongs to a root
+    /// namespace, otherwise they are resolved from the files parsed so far.
     pub fn parse_dsdl(&mut self, path: &Path) -> DsdlResult<&File> {
         let path = fs::canonicalize(path)?;
-        let file = File::parse(&path)?;
+
+        let key = self
+            .definitions
+            .iter()
+            .find(|(_, (p, _))| *p == path)
+            .map(|(key, _)| key.clone());
+        let path = match key {
+            Some(key) => self.load(&key, &mut Vec::new())?,
+            None => {
+                let file = File::parse(&path, Vec::new(), self).map_err(|e| in_file(&path, e))?;
+                self.insert(file, Vec::new())
+            }
+        };
+
+        Ok(&self.files[&path])
+    }
+
+    /// Adds a root namespace directory, e.g. `uavcan`, whose DSDL files are read by `parse_roots`
+    pub fn add_root(&mut self, root: &Path) -> DsdlResult<()> {
+        let root = fs::canonicalize(root)?;
+        let name = match root.file_name().and_then(|n| n.to_str()) {
+            Some(n) => n.to_string(),
+            None => return Err(DsdlError::File("Invalid root namespace name".to_string())),
+        };
+
+        for (path, namespace) in find_dsdl_files(&root, vec![name])? {
+            let in_file = |e| in_file(&path, e);
+            for component in namespace.iter() {
+                validate(component).map_err(in_file)?;
+            }
+            let (_, name, major, minor) = parse_file_name(&path).map_err(in_file)?;
+            let key = (namespace.clone(), name, major, minor);
+            if let Some((other, _)) = self.definitions.get(&key) {
+                if *other != path {
+                    return Err(DsdlError::File(format!(
+                        "`{}` is defined by both `{}` and `{}`",
+                        type_name(&key),
+                        other.display(),
+                        path.display()
+                    )));
+                }
+            }
+            self.definitions.insert(key, (path, namespace));
+        }
+
+        Ok(())
+    }
+
+    /// Reads the DSDL files of the root namespaces, each one after the types it references
+    pub fn parse_roots(&mut self) -> DsdlResult<()> {
+        let keys: Vec<TypeReference> = self.definitions.keys().cloned().collect();
+        for key in keys {
+            self.load(&key, &mut Vec::new())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the DSDL files parsed so far, each one after the files it depends on
+    pub fn files(&self) -> impl Iterator<Item = &File> {
+        self.order.iter().map(|path| &self.files[path])
+    }
+
+    /// Returns the parsed file defining a version of a type, e.g. `uavcan.node.Heartbeat` 1.0
+    pub fn find(&self, full_name: &str, major: u8, minor: u8) -> Option<&File> {
+        let mut namespace: Vec<String> = full_name.split('.').map(|s| s.to_string()).collect();
+        let name = namespace.pop()?;
+
+        self.types
+            .get(&(namespace, name, major, minor))
+            .map(|path| &self.files[path])
+    }
+
+    /// Returns the files defining the composite types referenced by a parsed file
+    pub fn dependencies(&self, file: &File) -> impl Iterator<Item = &File> {
+        self.dependencies
+            .get(file.path())
+            .into_iter()
+            .flatten()
+            .map(|path| &self.files[path])
+    }
+
+    // Reads the file of a type found in a root namespace, after the files of the types it
+    // references, and returns its path.  The stack holds the types being read, which depend on
+    // each other in that order.
+    fn load(&mut self, key: &TypeReference, stack: &mut Vec<TypeReference>) -> DsdlResult<String> {
+        if let Some(path) = self.types.get(key) {
+            return Ok(path.clone());
+        }
+
+        if let Some(index) = stack.iter().position(|k| k == key) {
+            let mut cycle: Vec<String> = stack[index..].iter().map(type_name).collect();
+            cycle.push(type_name(key));
+            return Err(DsdlError::DependencyCycle(cycle.join(" -> ")));
+        }
+
+        let (path, namespace) = self.definitions[key].clone();
+        let in_file = |e| in_file(&path, e);
+
+        stack.push(key.clone());
+        let mut dependencies = Vec::new();
+        for (span, reference) in references(&path).map_err(in_file)? {
+            let dependency = self
+                .resolve(&namespace, reference)
+                .map_err(|name| DsdlError::UnresolvedType(name, span))?;
+            let dependency = self.load(&dependency, stack)?;
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        }
+        stack.pop();
+
+        let file = File::parse(&path, namespace, self).map_err(in_file)?;
+
+        Ok(self.insert(file, dependencies))
+    }
+
+    // Finds the definition of a type referenced from a namespace, or returns its full name.
+    // References without a namespace are relative to the namespace they are made from.
+    fn resolve(
+        &self,
+        namespace: &[String],
+        reference: TypeReference,
+    ) -> Result<TypeReference, String> {
+        let (reference_namespace, name, major, minor) = reference;
+        let namespace = if reference_namespace.is_empty() {
+            namespace.to_vec()
+        } else {
+            reference_namespace
+        };
+        let key = (namespace, name, major, minor);
+
+        if self.definitions.contains_key(&key) {
+            return Ok(key);
+        }
+
+        // list the versions that do exist, to help picking the right one
+        let versions: Vec<String> = self
+            .definitions
+            .keys()
+            .filter(|k| k.0 == key.0 && k.1 == key.1)
+            .map(|k| format!("{}.{}", k.2, k.3))
+            .collect();
+        if versions.is_empty() {
+            Err(type_name(&key))
+        } else {
+            Err(format!(
+                "{} (available versions: {})",
+                type_name(&key),
+                versions.join(", ")
+            ))
+        }
+    }
+
+    fn insert(&mut self, file: File, dependencies: Vec<String>) -> String {
         let path = file.path().to_string();
+        let key = (
+            file.namespace().to_vec(),
+            file.name().to_string(),
+            file.major(),
+            file.minor(),
+        );
+
+        if !self.files.contains_key(&path) {
+            self.order.push(path.clone());
+        }
+        self.types.insert(key, path.clone());
         self.files.insert(path.clone(), file);
+        self.dependencies.insert(path.clone(), dependencies);
+
+        path
+    }
+}
+
+impl Scope for Parser {
+    fn type_attribute(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+        attribute: &str,
+    ) -> DsdlResult<Value> {
+        let key = (namespace.to_vec(), ctype.to_string(), version.0, version.1);
+        let file = match self.types.get(&key) {
+            Some(path) => &self.files[path],
+            None => return Err(DsdlError::Unresolved(type_name(&key))),
+        };
+
+        let message = file.message();
+        let value = match attribute {
+            "_extent_" => message
+                .and_then(|m| m.extent_bits())
+                .map(|e| Value::Rational(Rational::from_integer(e.into()))),
+            "_bit_length_" => message
+                .and_then(|m| m.bit_length_set())
+                .map(|b| b.to_value()),
+            attribute => file.constant(attribute).cloned(),
+        };
+
+        value.ok_or_else(|| {
+            DsdlError::UnknownAttribute(format!("`{}`", type_name(&key)), attribute.to_string())
+        })
+    }
+
+    fn field_bit_length_set(
+        &self,
+        namespace: &[String],
+        ctype: &str,
+        version: (u8, u8),
+    ) -> DsdlResult<BitLengthSet> {
+        let key = (namespace.to_vec(), ctype.to_string(), version.0, version.1);
+        let message = match self.types.get(&key).map(|path| self.files[path].message()) {
+            Some(Some(message)) => message,
+            Some(None) => {
+                return Err(DsdlError::Parse(format!(
+                    "`{}` is a service type and can't be used as a field",
+                    type_name(&key)
+                )))
+            }
+            None => return Err(DsdlError::Unresolved(type_name(&key))),
+        };
+
+        // types that aren't sealed are preceded by a delimiter header and can be extended up to
+        // their extent
+        match (message.bit_length_set(), message.extent_bits()) {
+            (Some(lengths), _) if message.is_sealed() => Ok(lengths.clone()),
+            (_, Some(extent)) if !message.is_sealed() => {
+                Ok(BitLengthSet::new(32).concat(&BitLengthSet::range(0, extent, 8)))
+            }
+            _ => Err(DsdlError::Unresolved(type_name(&key))),
+        }
+    }
+}
 
-        Ok(self.files.get(&path).unwrap())
+// Names the file an error was found in, unless the error is already located in it
+fn in_file(path: &Path, error: DsdlError) -> DsdlError {
+    match error.span() {
+        Some(_) => error,
+        None => DsdlError::InFile(path.display().to_string(), Box::new(error)),
     }
 }
 
+// Returns the full name and version of a type, e.g. `uavcan.node.Heartbeat.1.0`
+fn type_name(key: &TypeReference) -> String {
+    let mut components = key.0.clone();
+    components.push(format!("{}.{}.{}", key.1, key.2, key.3));
+
+    components.join(".")
+}
+
+// Returns the DSDL files below the directory along with their namespace, sorted by path
+fn find_dsdl_files(
+    directory: &Path,
+    namespace: Vec<String>,
+) -> DsdlResult<Vec<(PathBuf, Vec<String>)>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+
+    let mut files = Vec::new();
+    for path in entries {
+        if path.is_dir() {
+            let name = match path.file_name().and_then(|n| n.to_str()) {
+                Some(n) if !n.starts_with('.') => n.to_string(),
+                _ => continue,
+            };
+            let mut namespace = namespace.clone();
+            namespace.push(name);
+            files.append(&mut find_dsdl_files(&path, namespace)?);
+        } else if path.extension().is_some_and(|e| e == "dsdl") {
+            files.push((path, namespace.clone()));
+        }
+    }
+
+    Ok(files)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::Parser;
+    use crate::{test::TestDir, DsdlError, Parser, Rational, Value};
     use std::path::PathBuf;
 
     const UAVCAN: &str = "tests/assets/public_regulated_data_types/uavcan";
 
+    #[test]
+    fn test_parse_roots() {
+        let directory = TestDir::with_files(&[
+            (
+                "uavcan/node/Health.1.0.dsdl",
+                "uint2 value\nuint2 WARNING = 2\n@sealed\n",
+            ),
+            (
+                "uavcan/node/Heartbeat.1.0.dsdl",
+                "Health.1.0 health\nuint8 LEVEL = Health.1.0.WARNING + 1\n",
+            ),
+            (
+                "reg/Status.1.0.dsdl",
+                "uavcan.node.Health.1.0[<=uavcan.node.Heartbeat.1.0.LEVEL] health\n",
+            ),
+        ]);
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("uavcan")).unwrap();
+        parser.add_root(&directory.join("reg")).unwrap();
+        assert!(parser.parse_roots().is_ok());
+
+        let names: Vec<String> = parser.files().map(|f| f.full_name()).collect();
+        assert_eq!(
+            names,
+            ["uavcan.node.Health", "uavcan.node.Heartbeat", "reg.Status"]
+        );
+
+        let heartbeat = parser.find("uavcan.node.Heartbeat", 1, 0).unwrap();
+        assert_eq!(heartbeat.namespace(), ["uavcan", "node"]);
+        assert_eq!(
+            heartbeat.constant("LEVEL"),
+            Some(&Value::Rational(Rational::from_integer(3)))
+        );
+
+        let status = parser.find("reg.Status", 1, 0).unwrap();
+        let dependencies: Vec<&str> = parser.dependencies(status).map(|f| f.name()).collect();
+        assert_eq!(dependencies, ["Health", "Heartbeat"]);
+    }
+
+    #[test]
+    fn test_nested_layouts() {
+        let directory = TestDir::with_files(&[
+                ("uavcan/node/Health.1.0.dsdl", "uint2 value\n@sealed\n"),
+                ("uavcan/node/Mode.1.0.dsdl", "uint3 value\n"),
+                (
+                    "uavcan/node/Heartbeat.1.0.dsdl",
+                    "uint32 uptime\nHealth.1.0 health\nMode.1.0 mode\nuint8 vssc\n@assert _offset_ == {80, 88, 96}\n@assert Mode.1.0._extent_ == 16\n@extent 12 * 8\n",
+                ),
+            ],
+        );
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("uavcan")).unwrap();
+        assert!(parser.parse_roots().is_ok());
+
+        let health = parser.find("uavcan.node.Health", 1, 0).unwrap();
+        let health = health.message().unwrap();
+        assert!(health.is_sealed());
+        assert_eq!(health.extent_bits(), Some(8));
+
+        let heartbeat = parser.find("uavcan.node.Heartbeat", 1, 0).unwrap();
+        let heartbeat = heartbeat.message().unwrap();
+        let lengths = heartbeat.bit_length_set().unwrap();
+        assert_eq!(lengths.iter().collect::<Vec<_>>(), [80, 88, 96]);
+        assert_eq!(lengths.alignment(), 8);
+        assert_eq!(heartbeat.extent_bits(), Some(96));
+    }
+
+    #[test]
+    fn test_invalid_namespace() {
+        let directory =
+            TestDir::with_files(&[("uavcan/2nd/Health.1.0.dsdl", "uint2 value\n@sealed\n")]);
+
+        let mut parser = Parser::new().unwrap();
+        let result = parser.add_root(&directory.join("uavcan"));
+
+        assert!(matches!(
+            result,
+            Err(DsdlError::InFile(_, e)) if matches!(*e, DsdlError::InvalidName(ref name, _) if name == "2nd")
+        ));
+    }
+
+    #[test]
+    fn test_unresolved_type() {
+        let directory = TestDir::with_files(&[
+            ("uavcan/node/Health.1.0.dsdl", "uint2 value\n@sealed\n"),
+            (
+                "uavcan/node/Heartbeat.1.0.dsdl",
+                "uint32 uptime\nHealth.1.1 health\n",
+            ),
+        ]);
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("uavcan")).unwrap();
+        let result = parser.parse_roots();
+
+        assert!(matches!(
+            result,
+            Err(DsdlError::UnresolvedType(name, span))
+                if name == "uavcan.node.Health.1.1 (available versions: 1.0)"
+                    && span.path().ends_with("Heartbeat.1.0.dsdl")
+                    && span.line() == 2
+                    && span.column() == 1
+                    && span.text() == "Health.1.1"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_type_attribute() {
+        let directory = TestDir::with_files(&[
+            ("uavcan/node/Health.1.0.dsdl", "uint2 value\n@sealed\n"),
+            (
+                "uavcan/node/Heartbeat.1.0.dsdl",
+                "uint8 LEVEL = Health.1.0.MAX\n@sealed\n",
+            ),
+        ]);
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("uavcan")).unwrap();
+        let result = parser.parse_roots();
+
+        assert!(matches!(
+            result,
+            Err(DsdlError::InvalidStatement(span, message))
+                if message.contains("`uavcan.node.Health.1.0` has no attribute `MAX`")
+                    && span.path().ends_with("Heartbeat.1.0.dsdl")
+                    && span.line() == 1
+                    && span.column() == 26
+                    && span.text() == "MAX"
+        ));
+    }
+
+    #[test]
+    fn test_dependency_cycle() {
+        let directory = TestDir::with_files(&[
+            ("ns/A.1.0.dsdl", "B.1.0 b\n"),
+            ("ns/B.1.0.dsdl", "ns.A.1.0 a\n"),
+        ]);
+
+        let mut parser = Parser::new().unwrap();
+        parser.add_root(&directory.join("ns")).unwrap();
+        let result = parser.parse_roots();
+
+        assert!(matches!(
+            result,
+            Err(DsdlError::DependencyCycle(cycle)) if cycle == "ns.A.1.0 -> ns.B.1.0 -> ns.A.1.0"
+        ));
+    }
+
     #[test]
     #[ignore = "not implemented"]
     fn test_7509_heartbeat_1_0() {
@@ -1,4 +1,5 @@
-use crate::{Comment, DsdlError, DsdlResult, Name};
+use super::parse_value;
+use crate::{Comment, DsdlError, DsdlResult, Name, Scope};
 
 /// Represents a boolean Primitive Type
 #[derive(Debug, Clone, PartialEq)]
@@ -33,14 +34,14 @@ impl BoolPrimitive {
         self.comment.as_ref()
     }
 
-    pub(crate) fn parse(line: &str) -> DsdlResult<Self> {
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Self> {
         if let Some(line) = line.strip_prefix("bool") {
             let result = Name::parse(line)?;
             let name = result.0;
             match result.1 {
                 None => BoolPrimitive::new(name, None, None),
                 Some(s) => {
-                    let result = parse_bool_value(s)?;
+                    let result = parse_bool_value(s, scope)?;
                     let value = result.0;
                     let comment = match result.1 {
                         Some(s) => Comment::parse(s)?,
@@ -57,28 +58,23 @@ impl BoolPrimitive {
     }
 }
 
-fn parse_bool_value(line: &str) -> DsdlResult<(Option<bool>, Option<&str>)> {
-    let mut line = line.trim_start();
-    if line.is_empty() {
-        return Ok((None, None));
-    }
-
-    if line.starts_with('=') {
-        line = line[1..].trim_start();
-        let value = if line.starts_with("true") {
-            line = &line[4..];
-            Some(true)
-        } else if line.starts_with("false") {
-            line = &line[5..];
-            Some(false)
-        } else {
-            return Err(DsdlError::Parse("Could not find a bool value".to_string()));
-        };
-
-        let line = if line.is_empty() { None } else { Some(line) };
+fn parse_bool_value<'a>(
+    line: &'a str,
+    scope: &dyn Scope,
+) -> DsdlResult<(Option<bool>, Option<&'a str>)> {
+    let (value, line) = parse_value(line, scope)?;
+    let value = match value {
+        Some(v) => match v.as_bool() {
+            Some(b) => Some(b),
+            None => {
+                return Err(DsdlError::Expression(format!(
+                    "Expected a boolean value but found `{}`",
+                    v
+                )))
+            }
+        },
+        None => None,
+    };
 
-        Ok((value, line))
-    } else {
-        Ok((None, Some(line)))
-    }
+    Ok((value, line))
 }
@@ -1,8 +1,13 @@
-use crate::{Comment, DsdlError, DsdlResult, Name};
+use super::{parse_bits, parse_value};
+use crate::{CastMode, Comment, DsdlError, DsdlResult, Name, Scope};
+
+// Largest finite value of a 16 bits floating point number
+const F16_MAX: f64 = 65504.0;
 
 /// Represents a floating point Primitive Type
 #[derive(Debug, Clone, PartialEq)]
 pub struct FloatPrimitive {
+    cast_mode: CastMode,
     bits: u8,
     name: Name,
     value: Option<f64>,
@@ -10,8 +15,9 @@ pub struct FloatPrimitive {
 }
 
 impl FloatPrimitive {
-    /// Constructs a new int primitive
+    /// Constructs a new float primitive
     pub fn new(
+        cast_mode: CastMode,
         bits: u8,
         name: Name,
         value: Option<f64>,
@@ -24,17 +30,21 @@ impl FloatPrimitive {
         }
 
         if let Some(v) = value {
-            if bits == 32 && (v > f32::MAX as f64 || v < f32::MIN as f64) {
+            let max = match bits {
+                16 => F16_MAX,
+                32 => f32::MAX as f64,
+                _ => f64::MAX,
+            };
+            if v.is_finite() && (v > max || v < -max) {
                 return Err(DsdlError::OutOfRange(
                     "The value is outside the permissable range of what the bits can hold"
                         .to_string(),
                 ));
-            } else if bits == 16 {
-                //TODO: check f16 -> https://github.com/rust-lang/rust/issues/116909
             }
         }
 
         Ok(Self {
+            cast_mode,
             bits,
             name,
             value,
@@ -42,6 +52,11 @@ impl FloatPrimitive {
         })
     }
 
+    /// Returns the cast mode
+    pub fn cast_mode(&self) -> CastMode {
+        self.cast_mode
+    }
+
     /// Returns the number of bits
     pub fn bits(&self) -> u8 {
         self.bits
@@ -61,4 +76,106 @@ impl FloatPrimitive {
     pub fn comment(&self) -> Option<&Comment> {
         self.comment.as_ref()
     }
+
+    pub(crate) fn parse(line: &str, cast_mode: CastMode, scope: &dyn Scope) -> DsdlResult<Self> {
+        if let Some(s) = line.strip_prefix("float") {
+            let result = parse_bits(s)?;
+            let bits = result.0;
+            let result = if let Some(s) = result.1 {
+                Name::parse(s)?
+            } else {
+                return Err(DsdlError::Parse(
+                    "Primitive type is missing a name".to_string(),
+                ));
+            };
+            let name = result.0;
+
+            match result.1 {
+                None => FloatPrimitive::new(cast_mode, bits, name, None, None),
+                Some(s) => {
+                    let result = parse_float_value(s, scope)?;
+                    let value = result.0;
+                    let comment = match result.1 {
+                        Some(s) => Comment::parse(s)?,
+                        None => None,
+                    };
+                    FloatPrimitive::new(cast_mode, bits, name, value, comment)
+                }
+            }
+        } else {
+            Err(DsdlError::Parse(
+                "The float primitive prefix was not found".to_string(),
+            ))
+        }
+    }
+}
+
+fn parse_float_value<'a>(
+    line: &'a str,
+    scope: &dyn Scope,
+) -> DsdlResult<(Option<f64>, Option<&'a str>)> {
+    let (value, line) = parse_value(line, scope)?;
+    let value = match value {
+        Some(v) => match v.as_rational() {
+            Some(r) => Some(r.to_f64()),
+            None => {
+                return Err(DsdlError::Expression(format!(
+                    "Expected a rational value but found `{}`",
+                    v
+                )))
+            }
+        },
+        None => None,
+    };
+
+    Ok((value, line))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CastMode, FloatPrimitive};
+
+    #[test]
+    fn test_float() {
+        let result = FloatPrimitive::parse("float32 kelvin  # [K]", CastMode::Saturated, &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.cast_mode(), CastMode::Saturated);
+        assert_eq!(target.bits(), 32);
+        assert_eq!(target.name().text(), "kelvin");
+        assert!(target.value().is_none());
+        assert!(target.comment().is_some_and(|c| c.text() == " [K]"));
+    }
+
+    #[test]
+    fn test_float_constant() {
+        let result =
+            FloatPrimitive::parse("float64 PI = 3.141592653589793", CastMode::Truncated, &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.cast_mode(), CastMode::Truncated);
+        assert_eq!(target.bits(), 64);
+        assert_eq!(target.name().text(), "PI");
+        assert_eq!(target.value(), Some(&std::f64::consts::PI));
+        assert!(target.comment().is_none());
+    }
+
+    #[test]
+    fn test_float_constant_with_comment() {
+        let result =
+            FloatPrimitive::parse("float16 MAX = 6.5e4 # largest", CastMode::Saturated, &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.value(), Some(&65000.0));
+        assert!(target.comment().is_some_and(|c| c.text() == " largest"));
+    }
+
+    #[test]
+    fn test_float_out_of_range() {
+        assert!(FloatPrimitive::parse("float16 MAX = 65520", CastMode::Saturated, &()).is_err());
+        assert!(FloatPrimitive::parse("float8 x", CastMode::Saturated, &()).is_err());
+    }
 }
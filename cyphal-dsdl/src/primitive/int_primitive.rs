@@ -1,4 +1,5 @@
-use crate::{Comment, DsdlError, DsdlResult, Name};
+use super::{integer_value, parse_bits, parse_value};
+use crate::{Comment, DsdlError, DsdlResult, Name, Scope};
 
 /// Represents a integer Primitive Type
 #[derive(Debug, Clone, PartialEq)]
@@ -17,14 +18,15 @@ impl IntPrimitive {
         value: Option<i64>,
         comment: Option<Comment>,
     ) -> DsdlResult<Self> {
-        if bits > 64 {
+        if bits == 0 || bits > 64 {
             return Err(DsdlError::OutOfRange(
-                "The maximum number of bits an integer can have is 64".to_string(),
+                "An integer must have between 1 and 64 bits".to_string(),
             ));
         }
 
         if let Some(v) = value {
-            if v > (2_i64.pow((bits - 1) as u32)) || v < -(2_i64.pow(bits as u32)) {
+            let max = (1_i128 << (bits - 1)) - 1;
+            if (v as i128) > max || (v as i128) < -max - 1 {
                 return Err(DsdlError::OutOfRange(
                     "The value is outside the permissable range of what the bits can hold"
                         .to_string(),
@@ -60,7 +62,86 @@ impl IntPrimitive {
         self.comment.as_ref()
     }
 
-    pub(crate) fn parse(_line: &str) -> DsdlResult<Self> {
-        Err(DsdlError::NotImplemented)
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Self> {
+        if let Some(s) = line.strip_prefix("int") {
+            let result = parse_bits(s)?;
+            let bits = result.0;
+            let result = if let Some(s) = result.1 {
+                Name::parse(s)?
+            } else {
+                return Err(DsdlError::Parse(
+                    "Primitive type is missing a name".to_string(),
+                ));
+            };
+            let name = result.0;
+
+            match result.1 {
+                None => IntPrimitive::new(bits, name, None, None),
+                Some(s) => {
+                    let result = parse_int_value(s, scope)?;
+                    let value = result.0;
+                    let comment = match result.1 {
+                        Some(s) => Comment::parse(s)?,
+                        None => None,
+                    };
+                    IntPrimitive::new(bits, name, value, comment)
+                }
+            }
+        } else {
+            Err(DsdlError::Parse(
+                "The int primitive prefix was not found".to_string(),
+            ))
+        }
+    }
+}
+
+fn parse_int_value<'a>(
+    line: &'a str,
+    scope: &dyn Scope,
+) -> DsdlResult<(Option<i64>, Option<&'a str>)> {
+    let (value, line) = parse_value(line, scope)?;
+    let value = match value {
+        Some(v) => Some(i64::try_from(integer_value(&v)?).map_err(|_| {
+            DsdlError::OutOfRange(
+                "The value is outside the permissable range of what the bits can hold".to_string(),
+            )
+        })?),
+        None => None,
+    };
+
+    Ok((value, line))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IntPrimitive;
+
+    #[test]
+    fn test_int() {
+        let result = IntPrimitive::parse("int16 temperature   # [kelvin]", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.bits(), 16);
+        assert_eq!(target.name().text(), "temperature");
+        assert!(target.value().is_none());
+        assert!(target.comment().is_some_and(|c| c.text() == " [kelvin]"));
+    }
+
+    #[test]
+    fn test_int_constant() {
+        let result = IntPrimitive::parse("int8 MIN = -128", &());
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.bits(), 8);
+        assert_eq!(target.name().text(), "MIN");
+        assert_eq!(target.value(), Some(-128));
+    }
+
+    #[test]
+    fn test_int_constant_out_of_range() {
+        let result = IntPrimitive::parse("int8 MAX = 128", &());
+        assert!(result.is_err());
     }
 }
@@ -13,7 +13,7 @@ pub use uint_primitive::UintPrimitive;
 mod void_primitive;
 pub use void_primitive::VoidPrimitive;
 
-use crate::{DsdlError, DsdlResult};
+use crate::{expression::split_comment, CastMode, DsdlError, DsdlResult, Expression, Scope, Value};
 
 /// Represents the primitive's type
 #[derive(Debug, Clone, PartialEq)]
@@ -35,19 +35,37 @@ pub enum Primitive {
 }
 
 impl Primitive {
-    pub(crate) fn parse(line: &str) -> DsdlResult<Primitive> {
+    pub(crate) fn parse(line: &str, scope: &dyn Scope) -> DsdlResult<Primitive> {
+        let (cast_mode, line) = CastMode::parse(line)?;
+
         if line.starts_with("int") {
-            let primitive = IntPrimitive::parse(line)?;
+            if cast_mode == Some(CastMode::Truncated) {
+                return Err(DsdlError::Parse(
+                    "Signed integers can only be saturated".to_string(),
+                ));
+            }
+            let primitive = IntPrimitive::parse(line, scope)?;
             Ok(Primitive::Int(primitive))
         } else if line.starts_with("uint") {
-            let primitive = UintPrimitive::parse(line)?;
+            let primitive = UintPrimitive::parse(line, cast_mode.unwrap_or_default(), scope)?;
             Ok(Primitive::Uint(primitive))
         } else if line.starts_with("float") {
-            Err(DsdlError::NotImplemented)
+            let primitive = FloatPrimitive::parse(line, cast_mode.unwrap_or_default(), scope)?;
+            Ok(Primitive::Float(primitive))
         } else if line.starts_with("bool") {
-            let primitive = BoolPrimitive::parse(line)?;
+            if cast_mode == Some(CastMode::Truncated) {
+                return Err(DsdlError::Parse(
+                    "Booleans can only be saturated".to_string(),
+                ));
+            }
+            let primitive = BoolPrimitive::parse(line, scope)?;
             Ok(Primitive::Bool(primitive))
         } else if line.starts_with("void") {
+            if cast_mode.is_some() {
+                return Err(DsdlError::Parse(
+                    "Void fields can't have a cast mode".to_string(),
+                ));
+            }
             let primitive = VoidPrimitive::parse(line)?;
             Ok(Primitive::Void(primitive))
         } else {
@@ -56,7 +74,39 @@ impl Primitive {
     }
 }
 
-fn parse_bits(line: &str) -> DsdlResult<(u8, Option<&str>)> {
+// Evaluates the value assigned to a constant, returning it with the rest of the line
+pub(crate) fn parse_value<'a>(
+    line: &'a str,
+    scope: &dyn Scope,
+) -> DsdlResult<(Option<Value>, Option<&'a str>)> {
+    let line = line.trim_start();
+    if line.is_empty() {
+        return Ok((None, None));
+    }
+
+    match line.strip_prefix('=') {
+        Some(line) => {
+            let (expression, comment) = split_comment(line);
+            let value = Expression::new(expression.trim().to_string())?.evaluate(scope)?;
+
+            Ok((Some(value), comment))
+        }
+        None => Ok((None, Some(line))),
+    }
+}
+
+// Returns the value of an integer constant
+pub(crate) fn integer_value(value: &Value) -> DsdlResult<i128> {
+    match value.as_rational().and_then(|r| r.to_integer()) {
+        Some(v) => Ok(v),
+        None => Err(DsdlError::Expression(format!(
+            "Expected an integer value but found `{}`",
+            value
+        ))),
+    }
+}
+
+pub(crate) fn parse_bits(line: &str) -> DsdlResult<(u8, Option<&str>)> {
     let mut chars = line.chars();
     let mut v: Vec<char> = Vec::new();
     let mut bits_length = 0;
@@ -106,13 +156,12 @@ fn parse_bits(line: &str) -> DsdlResult<(u8, Option<&str>)> {
 
 #[cfg(test)]
 mod test {
-    use crate::Primitive;
+    use crate::{CastMode, Primitive};
 
     #[test]
-    #[ignore = "not implemented"]
     fn test_int_enum() {
         let int8 = "int8 name";
-        let result = Primitive::parse(&int8);
+        let result = Primitive::parse(int8, &());
         assert!(result.is_ok());
 
         let primitive = result.unwrap();
@@ -122,7 +171,7 @@ mod test {
     #[test]
     fn test_uint_enum() {
         let uint8 = "uint8 name";
-        let result = Primitive::parse(&uint8);
+        let result = Primitive::parse(uint8, &());
         assert!(result.is_ok());
 
         let primitive = result.unwrap();
@@ -130,13 +179,28 @@ mod test {
     }
 
     #[test]
-    #[ignore = "not implemented"]
     fn test_float_enum() {
         let float = "float16 name";
-        let result = Primitive::parse(&float);
+        let result = Primitive::parse(float, &());
         assert!(result.is_ok());
 
         let primitive = result.unwrap();
         assert!(matches!(primitive, Primitive::Float { .. }));
     }
+
+    #[test]
+    fn test_cast_mode_prefix() {
+        let result = Primitive::parse("truncated uint8 name", &());
+        assert!(result.is_ok());
+
+        let primitive = result.unwrap();
+        assert!(matches!(primitive, Primitive::Uint(p) if p.cast_mode() == CastMode::Truncated));
+    }
+
+    #[test]
+    fn test_invalid_cast_mode_prefix() {
+        assert!(Primitive::parse("truncated int8 name", &()).is_err());
+        assert!(Primitive::parse("truncated bool name", &()).is_err());
+        assert!(Primitive::parse("saturated void2", &()).is_err());
+    }
 }
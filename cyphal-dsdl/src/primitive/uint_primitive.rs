@@ -1,9 +1,10 @@
-use super::parse_bits;
-use crate::{Comment, DsdlError, DsdlResult, Name};
+use super::{integer_value, parse_bits, parse_value};
+use crate::{CastMode, Comment, DsdlError, DsdlResult, Name, Scope};
 
 /// Represents a unsigned integer Primitive Type
 #[derive(Debug, Clone, PartialEq)]
 pub struct UintPrimitive {
+    cast_mode: CastMode,
     bits: u8,
     name: Name,
     value: Option<u64>,
@@ -13,6 +14,7 @@ pub struct UintPrimitive {
 impl UintPrimitive {
     /// Constructs a new int primitive
     pub fn new(
+        cast_mode: CastMode,
         bits: u8,
         name: Name,
         value: Option<u64>,
@@ -25,7 +27,7 @@ impl UintPrimitive {
         }
 
         if let Some(v) = value {
-            if v > 2_u64.pow(bits as u32) {
+            if bits < 64 && v >= 1 << bits {
                 return Err(DsdlError::OutOfRange(
                     "The value is outside the permissable range of what the bits can hold"
                         .to_string(),
@@ -34,6 +36,7 @@ impl UintPrimitive {
         }
 
         Ok(Self {
+            cast_mode,
             bits,
             name,
             value,
@@ -41,6 +44,11 @@ impl UintPrimitive {
         })
     }
 
+    /// Returns the cast mode
+    pub fn cast_mode(&self) -> CastMode {
+        self.cast_mode
+    }
+
     /// Returns the number of bits
     pub fn bits(&self) -> u8 {
         self.bits
@@ -61,7 +69,7 @@ impl UintPrimitive {
         self.comment.as_ref()
     }
 
-    pub(crate) fn parse(line: &str) -> DsdlResult<Self> {
+    pub(crate) fn parse(line: &str, cast_mode: CastMode, scope: &dyn Scope) -> DsdlResult<Self> {
         if let Some(s) = line.strip_prefix("uint") {
             let result = parse_bits(s)?;
             let bits = result.0;
@@ -75,15 +83,15 @@ impl UintPrimitive {
             let name = result.0;
 
             match result.1 {
-                None => UintPrimitive::new(bits, name, None, None),
+                None => UintPrimitive::new(cast_mode, bits, name, None, None),
                 Some(s) => {
-                    let result = parse_uint_value(s)?;
+                    let result = parse_uint_value(s, scope)?;
                     let value = result.0;
                     let comment = match result.1 {
                         Some(s) => Comment::parse(s)?,
                         None => None,
                     };
-                    UintPrimitive::new(bits, name, value, comment)
+                    UintPrimitive::new(cast_mode, bits, name, value, comment)
                 }
             }
         } else {
@@ -94,35 +102,19 @@ impl UintPrimitive {
     }
 }
 
-fn parse_uint_value(line: &str) -> DsdlResult<(Option<u64>, Option<&str>)> {
-    let mut line = line.trim_start();
-    if line.is_empty() {
-        return Ok((None, None));
-    }
+fn parse_uint_value<'a>(
+    line: &'a str,
+    scope: &dyn Scope,
+) -> DsdlResult<(Option<u64>, Option<&'a str>)> {
+    let (value, line) = parse_value(line, scope)?;
+    let value = match value {
+        Some(v) => Some(u64::try_from(integer_value(&v)?).map_err(|_| {
+            DsdlError::OutOfRange(
+                "The value is outside the permissable range of what the bits can hold".to_string(),
+            )
+        })?),
+        None => None,
+    };
 
-    if line.starts_with('=') {
-        line = line[1..].trim_start();
-        let chars = line.chars();
-        let mut v: Vec<char> = Vec::new();
-        let mut bits_length = 0;
-        for c in chars {
-            if c.is_numeric() {
-                v.push(c);
-                bits_length += 1;
-            } else if c != ' ' {
-                bits_length += 1;
-                break;
-            }
-        }
-
-        let value: String = v.into_iter().collect();
-        let value = value.parse::<u64>()?;
-
-        let line = &line[bits_length..];
-        let line = if line.is_empty() { None } else { Some(line) };
-
-        Ok((Some(value), line))
-    } else {
-        Ok((None, Some(line)))
-    }
+    Ok((value, line))
 }
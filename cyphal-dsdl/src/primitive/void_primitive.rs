@@ -1,3 +1,4 @@
+use super::parse_bits;
 use crate::{Comment, DsdlError, DsdlResult};
 
 /// Represents a void Primitive Type
@@ -10,9 +11,9 @@ pub struct VoidPrimitive {
 impl VoidPrimitive {
     /// Constructs a new void primitive
     pub fn new(bits: u8, comment: Option<Comment>) -> DsdlResult<Self> {
-        if bits > 64 {
+        if bits == 0 || bits > 64 {
             return Err(DsdlError::OutOfRange(
-                "The maximum number of bits a void can have is 64".to_string(),
+                "A void must have between 1 and 64 bits".to_string(),
             ));
         }
 
@@ -29,7 +30,40 @@ impl VoidPrimitive {
         self.comment.as_ref()
     }
 
-    pub(crate) fn parse(_line: &str) -> DsdlResult<Self> {
-        Err(DsdlError::NotImplemented)
+    pub(crate) fn parse(line: &str) -> DsdlResult<Self> {
+        if let Some(s) = line.strip_prefix("void") {
+            let result = parse_bits(s)?;
+            let comment = match result.1 {
+                Some(s) => Comment::parse(s)?,
+                None => None,
+            };
+
+            VoidPrimitive::new(result.0, comment)
+        } else {
+            Err(DsdlError::Parse(
+                "The void primitive prefix was not found".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::VoidPrimitive;
+
+    #[test]
+    fn test_void() {
+        let result = VoidPrimitive::parse("void3   # padding");
+        assert!(result.is_ok());
+
+        let target = result.unwrap();
+        assert_eq!(target.bits(), 3);
+        assert!(target.comment().is_some_and(|c| c.text() == " padding"));
+    }
+
+    #[test]
+    fn test_void_out_of_range() {
+        assert!(VoidPrimitive::parse("void0").is_err());
+        assert!(VoidPrimitive::parse("void65").is_err());
     }
 }
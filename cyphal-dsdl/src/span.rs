@@ -0,0 +1,139 @@
+use crate::expression::split_comment;
+use std::fmt::Display;
+
+/// Represents the location of some source text in a DSDL file, such as the cause of an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    path: String,
+    line: u32,
+    column: u32,
+    length: u32,
+    source: String,
+}
+
+impl Span {
+    // Spans the statement of a line, which excludes its indentation and comment
+    pub(crate) fn statement(path: &str, line: u32, source: &str) -> Self {
+        let (code, _) = split_comment(source);
+        let start = code.len() - code.trim_start().len();
+        let end = code.trim_end().len().max(start);
+
+        Self::new(path, line, source, start, end)
+    }
+
+    // Spans the first occurrence of `text` in the statement of a line, preferably one that isn't
+    // part of a longer identifier, or the whole statement if it doesn't occur
+    pub(crate) fn find(path: &str, line: u32, source: &str, text: &str) -> Self {
+        let (code, _) = split_comment(source);
+        let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let whole = code
+            .match_indices(text)
+            .map(|(start, _)| start)
+            .find(|&start| {
+                let before = code[..start].chars().next_back();
+                let after = code[start + text.len()..].chars().next();
+                !before.is_some_and(is_identifier) && !after.is_some_and(is_identifier)
+            });
+        match whole.or_else(|| code.find(text)) {
+            Some(start) if !text.is_empty() => {
+                Self::new(path, line, source, start, start + text.len())
+            }
+            _ => Self::statement(path, line, source),
+        }
+    }
+
+    // Columns and lengths are counted in characters, from byte offsets into the source
+    fn new(path: &str, line: u32, source: &str, start: usize, end: usize) -> Self {
+        Self {
+            path: path.to_string(),
+            line,
+            column: source[..start].chars().count() as u32 + 1,
+            length: source[start..end].chars().count() as u32,
+            source: source.to_string(),
+        }
+    }
+
+    /// Returns the path of the file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the line number, starting at 1
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column of the first character, starting at 1
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// Returns the number of characters spanned, which is 0 for an empty line
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// Returns the whole source line holding the span
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the spanned text
+    pub fn text(&self) -> &str {
+        let offset = |characters: usize| {
+            self.source
+                .char_indices()
+                .nth(characters)
+                .map_or(self.source.len(), |(i, _)| i)
+        };
+        let start = self.column as usize - 1;
+
+        &self.source[offset(start)..offset(start + self.length as usize)]
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.path, self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Span;
+
+    #[test]
+    fn test_statement() {
+        let target = Span::statement("Health.1.0.dsdl", 3, "  uint99 value  # the health");
+
+        assert_eq!(target.to_string(), "Health.1.0.dsdl:3:3");
+        assert_eq!(target.length(), 12);
+        assert_eq!(target.text(), "uint99 value");
+        assert_eq!(target.source(), "  uint99 value  # the health");
+    }
+
+    #[test]
+    fn test_find() {
+        let target = Span::find("Health.1.0.dsdl", 1, "uint8 µ = BAD + 1 # BAD", "BAD");
+
+        assert_eq!(target.column(), 11);
+        assert_eq!(target.length(), 3);
+        assert_eq!(target.text(), "BAD");
+
+        let word = Span::find("Health.1.0.dsdl", 1, "float32 a", "a");
+        assert_eq!(word.column(), 9);
+
+        let missing = Span::find("Health.1.0.dsdl", 1, "uint8 a", "b");
+        assert_eq!(missing.column(), 1);
+        assert_eq!(missing.text(), "uint8 a");
+    }
+
+    #[test]
+    fn test_empty_line() {
+        let target = Span::statement("Empty.1.0.dsdl", 1, "");
+
+        assert_eq!(target.column(), 1);
+        assert_eq!(target.length(), 0);
+        assert_eq!(target.text(), "");
+    }
+}
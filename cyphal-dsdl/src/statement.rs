@@ -1,8 +1,11 @@
-use crate::{Composite, Directive, Primitive};
+use crate::{Array, Composite, Directive, Primitive};
 
 /// Represents a DSDL statement
 #[derive(Debug, Clone, PartialEq)]
 pub enum Statement {
+    /// Represents an array field
+    Array(Array),
+
     /// Represents a comment
     Comment(String),
 
@@ -18,3 +21,18 @@ pub enum Statement {
     /// Represents an empty statement
     Empty,
 }
+
+impl Statement {
+    // Fields are the statements holding serialized data, unlike constants
+    pub(crate) fn is_field(&self) -> bool {
+        match self {
+            Statement::Array(_) | Statement::Composite(_) => true,
+            Statement::Primitive(Primitive::Bool(p)) => p.value().is_none(),
+            Statement::Primitive(Primitive::Int(p)) => p.value().is_none(),
+            Statement::Primitive(Primitive::Uint(p)) => p.value().is_none(),
+            Statement::Primitive(Primitive::Float(p)) => p.value().is_none(),
+            Statement::Primitive(Primitive::Void(_)) => true,
+            _ => false,
+        }
+    }
+}
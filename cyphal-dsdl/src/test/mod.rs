@@ -0,0 +1,2 @@
+mod dir;
+pub use dir::TestDir;
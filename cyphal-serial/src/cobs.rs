@@ -0,0 +1,110 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+/// Byte delimiting the frames on the stream
+pub const FRAME_DELIMITER: u8 = 0x00;
+
+/// Appends the COBS encoding of the data to `output`.  The encoded data contains no zero byte.
+pub fn encode(data: &[u8], output: &mut Vec<u8>) {
+    let mut code_index = output.len();
+    let mut code: u8 = 1;
+    output.push(0);
+
+    for (i, &byte) in data.iter().enumerate() {
+        if byte != 0 {
+            output.push(byte);
+            code += 1;
+        }
+
+        if byte == 0 || code == 0xFF {
+            output[code_index] = code;
+            code = 1;
+
+            // a full block at the end of the data isn't followed by an empty one
+            if byte == 0 || i < data.len() - 1 {
+                code_index = output.len();
+                output.push(0);
+            } else {
+                return;
+            }
+        }
+    }
+
+    output[code_index] = code;
+}
+
+/// Decodes COBS encoded data.  Returns `None` if the data is not valid.
+pub fn decode(data: &[u8]) -> Option<Vec<u8>> {
+    let mut output: Vec<u8> = Vec::with_capacity(data.len());
+    let mut index = 0;
+
+    while index < data.len() {
+        let code = data[index] as usize;
+        if code == 0 || index + code > data.len() {
+            return None;
+        }
+
+        output.extend_from_slice(&data[index + 1..index + code]);
+        index += code;
+
+        // the zero byte is implicit, except after a full block or at the end of the data
+        if code < 0xFF && index < data.len() {
+            output.push(0);
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::{decode, encode};
+    use std::{vec, vec::Vec};
+
+    fn check(data: &[u8], encoded: &[u8]) {
+        let mut output: Vec<u8> = Vec::new();
+        encode(data, &mut output);
+
+        assert_eq!(output, encoded);
+        assert_eq!(decode(encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_zero_bytes() {
+        check(&[], &[0x01]);
+        check(&[0x00], &[0x01, 0x01]);
+        check(&[0x00, 0x00], &[0x01, 0x01, 0x01]);
+        check(&[0x00, 0x11, 0x00], &[0x01, 0x02, 0x11, 0x01]);
+        check(&[0x11, 0x22, 0x00, 0x33], &[0x03, 0x11, 0x22, 0x02, 0x33]);
+        check(&[0x11, 0x22, 0x33, 0x44], &[0x05, 0x11, 0x22, 0x33, 0x44]);
+        check(&[0x11, 0x00, 0x00, 0x00], &[0x02, 0x11, 0x01, 0x01, 0x01]);
+    }
+
+    #[test]
+    fn test_full_blocks() {
+        let data: Vec<u8> = (1..=254).collect();
+        let mut encoded = vec![0xFF];
+        encoded.extend_from_slice(&data);
+        check(&data, &encoded);
+
+        let data: Vec<u8> = (0..=254).collect();
+        let mut encoded = vec![0x01, 0xFF];
+        encoded.extend(1..=254);
+        check(&data, &encoded);
+
+        let data: Vec<u8> = (1..=255).collect();
+        let mut encoded = vec![0xFF];
+        encoded.extend(1..=254);
+        encoded.extend([0x02, 0xFF]);
+        check(&data, &encoded);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(decode(&[0x00]), None);
+        assert_eq!(decode(&[0x05, 0x11, 0x22]), None);
+    }
+}
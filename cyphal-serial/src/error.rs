@@ -0,0 +1,28 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use cyphal::{CyphalError, TransportErrorKind};
+
+/// Represents a Serial Error
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SerialError {
+    /// Connection
+    Connection,
+}
+
+impl Display for SerialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Connection => write!(f, "A connection failure has occured"),
+        }
+    }
+}
+
+impl From<SerialError> for CyphalError {
+    fn from(value: SerialError) -> Self {
+        match value {
+            SerialError::Connection => CyphalError::Transport(TransportErrorKind::Connection),
+        }
+    }
+}
+
+/// The result of a Cyphal Serial operation.  On failure, a `SerialError` will be included.
+pub type SerialResult<T> = Result<T, SerialError>;
@@ -2,3 +2,17 @@
 #![doc = include_str!("../README.md")]
 #![forbid(missing_docs)]
 #![allow(async_fn_in_trait)]
+
+mod cobs;
+
+mod error;
+pub use error::{SerialError, SerialResult};
+
+mod serial;
+pub use serial::Serial;
+
+#[cfg(test)]
+pub(crate) mod test;
+
+mod transport;
+pub use transport::SerialTransport;
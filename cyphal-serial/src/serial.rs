@@ -0,0 +1,12 @@
+use crate::SerialResult;
+
+/// Trait representing a serial byte stream, such as a UART or a TCP connection
+pub trait Serial {
+    /// Writes all the bytes to the stream.
+    async fn write(&self, data: &[u8]) -> SerialResult<()>;
+
+    /// Reads bytes from the stream.  Blocks until at least one byte is available.
+    ///
+    /// On success, returns the number of bytes read.
+    async fn read(&self, data: &mut [u8]) -> SerialResult<usize>;
+}
@@ -0,0 +1,40 @@
+use core::{cell::Cell, future::poll_fn, task::Poll, time::Duration};
+use cyphal::Clock;
+
+/// Clock advancing by `step` every time it's read.  Sleeping keeps reading the clock until it
+/// reaches the deadline, yielding in between.
+#[derive(Default)]
+pub struct TestClock {
+    pub now: Cell<Duration>,
+    pub step: Duration,
+}
+
+impl TestClock {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            now: Cell::new(Duration::ZERO),
+            step,
+        }
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Duration {
+        let now = self.now.get();
+        self.now.set(now + self.step);
+
+        now
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        poll_fn(|cx| {
+            if self.now() >= deadline {
+                return Poll::Ready(());
+            }
+
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        })
+        .await
+    }
+}
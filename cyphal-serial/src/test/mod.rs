@@ -0,0 +1,14 @@
+mod clock;
+pub use clock::*;
+
+mod message;
+pub use message::*;
+
+mod pipe;
+pub use pipe::*;
+
+mod router;
+pub use router::*;
+
+mod service;
+pub use service::*;
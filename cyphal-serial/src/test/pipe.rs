@@ -0,0 +1,89 @@
+extern crate std;
+
+use crate::{Serial, SerialError, SerialResult};
+use core::{cell::RefCell, future::poll_fn, task::Poll, task::Waker};
+use std::{collections::VecDeque, rc::Rc, vec::Vec};
+
+#[derive(Default)]
+struct Buffer {
+    data: VecDeque<u8>,
+    closed: bool,
+    reader: Option<Waker>,
+}
+
+/// End of an in-memory pipe.  The bytes written on one end are read from the other end.
+pub struct TestSerial {
+    input: Rc<RefCell<Buffer>>,
+    output: Rc<RefCell<Buffer>>,
+}
+
+/// Constructs the two ends of an in-memory pipe
+pub fn pipe() -> (TestSerial, TestSerial) {
+    let a: Rc<RefCell<Buffer>> = Rc::default();
+    let b: Rc<RefCell<Buffer>> = Rc::default();
+
+    (
+        TestSerial {
+            input: a.clone(),
+            output: b.clone(),
+        },
+        TestSerial {
+            input: b,
+            output: a,
+        },
+    )
+}
+
+impl TestSerial {
+    /// Once all the bytes written so far have been read, reading the other end fails
+    pub fn close(&self) {
+        let mut output = self.output.borrow_mut();
+        output.closed = true;
+        if let Some(waker) = output.reader.take() {
+            waker.wake();
+        }
+    }
+
+    /// Returns the bytes written on the other end that haven't been read yet
+    pub fn pending_bytes(&self) -> Vec<u8> {
+        self.input.borrow_mut().data.drain(..).collect()
+    }
+}
+
+impl Serial for TestSerial {
+    async fn write(&self, data: &[u8]) -> SerialResult<()> {
+        let mut output = self.output.borrow_mut();
+        if output.closed {
+            return Err(SerialError::Connection);
+        }
+
+        output.data.extend(data);
+        if let Some(waker) = output.reader.take() {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    async fn read(&self, data: &mut [u8]) -> SerialResult<usize> {
+        poll_fn(|cx| {
+            let mut input = self.input.borrow_mut();
+            if input.data.is_empty() {
+                if input.closed {
+                    return Poll::Ready(Err(SerialError::Connection));
+                }
+
+                input.reader = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+
+            let len = data.len().min(input.data.len());
+            for (byte, value) in data.iter_mut().zip(input.data.drain(..len)) {
+                *byte = value;
+            }
+
+            Poll::Ready(Ok(len))
+        })
+        .await
+    }
+}
@@ -0,0 +1,56 @@
+extern crate std;
+
+use super::{TestResponse, TEST_RESPONSE_SIZE};
+use core::cell::RefCell;
+use cyphal::{CyphalResult, NodeId, Priority, Response, Router, ServiceId, SubjectId};
+use std::vec::Vec;
+
+pub type TestReceivedMessage = (SubjectId, Option<NodeId>, Vec<u8>);
+pub type TestReceivedRequest = (ServiceId, NodeId, NodeId, Vec<u8>);
+
+#[derive(Default)]
+pub struct TestRouter {
+    pub messages: RefCell<Vec<TestReceivedMessage>>,
+    pub requests: RefCell<Vec<TestReceivedRequest>>,
+}
+
+impl TestRouter {
+    pub fn new() -> Self {
+        Self {
+            messages: RefCell::new(Vec::new()),
+            requests: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Router for &TestRouter {
+    async fn process_message(
+        &self,
+        _priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<()> {
+        self.messages
+            .borrow_mut()
+            .push((subject, source, data.to_vec()));
+        Ok(())
+    }
+
+    async fn process_request(
+        &self,
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Option<impl Response>> {
+        self.requests
+            .borrow_mut()
+            .push((service, source, destination, data.to_vec()));
+
+        // responses are sent back from the destination of the request
+        let response: [u8; TEST_RESPONSE_SIZE] = [7; TEST_RESPONSE_SIZE];
+        TestResponse::new(priority, service, destination, source, response).map(Some)
+    }
+}
@@ -0,0 +1,211 @@
+extern crate alloc;
+
+use super::CRC32C;
+use crate::cobs::{self, FRAME_DELIMITER};
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    vec::Vec,
+};
+use cyphal::{NodeId, ServiceId};
+use cyphal_udp::{Header, UdpTransferId, HEADER_SIZE};
+
+type ResponseKey = (ServiceId, NodeId, NodeId, UdpTransferId);
+
+pub struct InboundQueue {
+    max_frame_size: usize,
+    frame: Vec<u8>,
+    overflow: bool,
+    complete_incoming: VecDeque<(Header, Vec<u8>)>,
+    expected_responses: BTreeSet<ResponseKey>,
+    complete_responses: BTreeMap<ResponseKey, (Header, Vec<u8>)>,
+}
+
+impl InboundQueue {
+    /// Constructs a new queue.  Frames carrying more than `max_payload_size` bytes are discarded.
+    pub fn new(max_payload_size: usize) -> Self {
+        // the COBS encoding adds one byte every 254 bytes, plus one
+        let max_frame_size = HEADER_SIZE + max_payload_size + 4;
+
+        Self {
+            max_frame_size: max_frame_size + max_frame_size / 254 + 1,
+            frame: Vec::new(),
+            overflow: false,
+            complete_incoming: VecDeque::new(),
+            expected_responses: BTreeSet::new(),
+            complete_responses: BTreeMap::new(),
+        }
+    }
+
+    /// Adds bytes read from the stream to the queue.
+    ///
+    /// Once a frame delimiter is received, the frame is decoded.  Messages and requests accepted by
+    /// `accept` are kept until they are popped, and responses until they are taken if they are
+    /// expected.  Invalid frames and responses nobody is waiting for are discarded.
+    pub fn push<A>(&mut self, bytes: &[u8], accept: A)
+    where
+        A: Fn(&Header) -> bool,
+    {
+        for &byte in bytes {
+            if byte != FRAME_DELIMITER {
+                if self.frame.len() < self.max_frame_size {
+                    self.frame.push(byte);
+                } else {
+                    self.overflow = true;
+                }
+                continue;
+            }
+
+            // consecutive delimiters delimit empty frames, which are ignored
+            if !self.frame.is_empty() && !self.overflow {
+                if let Some((header, payload)) = parse_frame(&self.frame) {
+                    if accept(&header) {
+                        self.complete(header, payload);
+                    }
+                }
+            }
+
+            self.frame.clear();
+            self.overflow = false;
+        }
+    }
+
+    /// Returns the next received message or request
+    pub fn pop_incoming(&mut self) -> Option<(Header, Vec<u8>)> {
+        self.complete_incoming.pop_front()
+    }
+
+    /// Keeps the response from `source` to `destination` once it is received
+    pub fn expect_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+    ) {
+        self.expected_responses
+            .insert((service, source, destination, transfer));
+    }
+
+    /// Returns the expected response if it has been received, in which case it is no longer expected
+    pub fn take_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+    ) -> Option<(Header, Vec<u8>)> {
+        let key = (service, source, destination, transfer);
+        let response = self.complete_responses.remove(&key)?;
+        self.expected_responses.remove(&key);
+
+        Some(response)
+    }
+
+    /// Stops expecting a response that hasn't been received
+    pub fn forget_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+    ) {
+        let key = (service, source, destination, transfer);
+        self.expected_responses.remove(&key);
+        self.complete_responses.remove(&key);
+    }
+
+    fn complete(&mut self, header: Header, payload: Vec<u8>) {
+        match header {
+            Header::Service(id) if !id.is_request() => {
+                let key = (id.service(), id.source(), id.destination(), id.transfer());
+                if self.expected_responses.contains(&key) {
+                    self.complete_responses.insert(key, (header, payload));
+                }
+            }
+            _ => self.complete_incoming.push_back((header, payload)),
+        }
+    }
+}
+
+/// Decodes a frame and returns its header and its payload, without the transfer CRC.
+///
+/// Returns `None` if the frame is not valid.  Every transfer fits in a single frame.
+fn parse_frame(frame: &[u8]) -> Option<(Header, Vec<u8>)> {
+    let frame = cobs::decode(frame)?;
+    if frame.len() < HEADER_SIZE + 4 {
+        return None;
+    }
+
+    let (header, data) = frame.split_at(HEADER_SIZE);
+    let header = Header::new(header.try_into().ok()?).ok()?;
+    if header.index() != 0 || !header.end_of_transfer() {
+        return None;
+    }
+
+    let (payload, checksum) = data.split_at(data.len() - 4);
+    if CRC32C.checksum(payload).to_le_bytes() != checksum {
+        return None;
+    }
+
+    Some((header, payload.to_vec()))
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::InboundQueue;
+    use crate::transport::encode_frame;
+    use cyphal::Priority;
+    use cyphal_udp::{MessageHeader, UdpTransferId};
+    use std::vec::Vec;
+
+    fn message_frame(data: &[u8]) -> Vec<u8> {
+        let header = MessageHeader::new(
+            Priority::Nominal,
+            Some(1),
+            None,
+            10,
+            UdpTransferId::default(),
+            0,
+            true,
+        )
+        .unwrap();
+
+        let mut frame: Vec<u8> = Vec::new();
+        encode_frame(header.into(), data, &mut frame);
+        frame
+    }
+
+    #[test]
+    fn test_split_frames() {
+        let frame = message_frame(&[1, 0, 2]);
+        let mut queue = InboundQueue::new(16);
+
+        // bytes can be read in arbitrary chunks
+        for chunk in frame.chunks(5) {
+            assert!(queue.pop_incoming().is_none());
+            queue.push(chunk, |_| true);
+        }
+
+        let (_, payload) = queue.pop_incoming().unwrap();
+        assert_eq!(payload, [1, 0, 2]);
+    }
+
+    #[test]
+    fn test_invalid_frames() {
+        let mut corrupted = message_frame(&[1, 2, 3]);
+        corrupted[30] ^= 0x01;
+
+        let mut queue = InboundQueue::new(16);
+        queue.push(&[0x05, 0x00, 0x00], |_| true);
+        queue.push(&corrupted, |_| true);
+        queue.push(&message_frame(&[0; 17]), |_| true);
+        queue.push(&message_frame(&[4]), |_| false);
+        assert!(queue.pop_incoming().is_none());
+
+        // the stream resynchronizes on the next delimiter
+        queue.push(&message_frame(&[5]), |_| true);
+        assert_eq!(queue.pop_incoming().unwrap().1, [5]);
+    }
+}
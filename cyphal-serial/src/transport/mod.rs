@@ -0,0 +1,564 @@
+mod inbound_queue;
+use inbound_queue::InboundQueue;
+
+extern crate alloc;
+
+use crate::{
+    cobs::{self, FRAME_DELIMITER},
+    Serial,
+};
+use alloc::vec::Vec;
+use core::{
+    future::{poll_fn, Future},
+    mem,
+    pin::pin,
+    task::{Poll, Waker},
+    time::Duration,
+};
+use crc::Crc;
+use cyphal::{
+    Clock, CyphalError, CyphalResult, Message, NodeId, Request, Response, Router, ServiceId,
+    Shared, SubjectId, TransferId, Transport,
+};
+use cyphal_udp::{Header, MessageHeader, ServiceHeader, UdpTransferId, HEADER_SIZE};
+
+const CRC32C: Crc<u32> = Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+/// Number of bytes read from the stream at once
+const READ_BUFFER_SIZE: usize = 64;
+
+/// Represents a Serial Transport
+///
+/// Every transfer is sent in a single frame made of the same header as Cyphal/UDP, the payload
+/// and its CRC-32C, COBS encoded and delimited by zero bytes.  Payloads above `MAX_PAYLOAD_SIZE`
+/// bytes can't be sent and are discarded when received.
+///
+/// The transport can be shared between tasks: frames received while a task is waiting on a
+/// response are made available to the task serving the incoming transfers, and vice versa.
+pub struct SerialTransport<const MAX_PAYLOAD_SIZE: usize, S: Serial, K: Clock> {
+    serial: S,
+    clock: K,
+    transfer: Shared<UdpTransferId>,
+    inbound_queue: Shared<InboundQueue>,
+    received: Shared<usize>,
+    waiters: Shared<Vec<Waker>>,
+    serving: Shared<usize>,
+}
+
+impl<const MAX_PAYLOAD_SIZE: usize, S: Serial, K: Clock> SerialTransport<MAX_PAYLOAD_SIZE, S, K> {
+    /// Constructs a new serial transport.  The clock is used to time out service calls.
+    pub fn new(serial: S, clock: K) -> CyphalResult<Self> {
+        Ok(SerialTransport {
+            serial,
+            clock,
+            transfer: Shared::new(UdpTransferId::default()),
+            inbound_queue: Shared::new(InboundQueue::new(MAX_PAYLOAD_SIZE)),
+            received: Shared::new(0),
+            waiters: Shared::new(Vec::new()),
+            serving: Shared::new(0),
+        })
+    }
+
+    fn next_transfer(&self) -> UdpTransferId {
+        // read and incremented at once, so that concurrent transfers get distinct IDs
+        self.transfer.with(|transfer| {
+            *transfer = transfer.next();
+            *transfer
+        })
+    }
+
+    /// Encodes the frame and writes it to the stream
+    async fn send_frame(&self, header: Header, data: &[u8]) -> CyphalResult<()> {
+        if data.len() > MAX_PAYLOAD_SIZE {
+            return Err(CyphalError::OutOfRange);
+        }
+
+        let mut frame: Vec<u8> = Vec::new();
+        encode_frame(header, data, &mut frame);
+
+        Ok(self.serial.write(&frame).await?)
+    }
+
+    /// Counts that bytes were read and wakes the tasks waiting for it
+    fn notify(&self) {
+        // the count is incremented and the waiters taken at once, so that a task registering in
+        // the meantime sees the new count
+        let waiters = self.waiters.with(|waiters| {
+            self.received.with(|count| *count = count.wrapping_add(1));
+            mem::take(waiters)
+        });
+        waiters.into_iter().for_each(Waker::wake);
+    }
+
+    /// Registers the waker of a task until bytes are read, unless the count moved past
+    /// `received` in the meantime, in which case true is returned
+    fn wait(&self, received: usize, waker: &Waker) -> bool {
+        self.waiters.with(|waiters| {
+            if self.received.get() != received {
+                return true;
+            }
+            if !waiters.iter().any(|w| w.will_wake(waker)) {
+                waiters.push(waker.clone());
+            }
+
+            false
+        })
+    }
+
+    /// Waits until bytes have been added to the inbound queue.
+    ///
+    /// The inbound queue is shared by all the tasks using the transport, so this returns as soon
+    /// as bytes are read by this task or any other.  Frames rejected by `accept` are dropped
+    /// without being added to the queue.
+    async fn receive<A>(&self, accept: A) -> CyphalResult<()>
+    where
+        A: Fn(&Header) -> bool,
+    {
+        let received = self.received.get();
+        let mut buffer: [u8; READ_BUFFER_SIZE] = [0; READ_BUFFER_SIZE];
+
+        let len = {
+            let mut read = pin!(self.serial.read(&mut buffer));
+
+            poll_fn(|cx| {
+                if self.received.get() != received {
+                    return Poll::Ready(Ok(None));
+                }
+
+                match read.as_mut().poll(cx) {
+                    Poll::Ready(result) => Poll::Ready(result.map(Some)),
+                    Poll::Pending => match self.wait(received, cx.waker()) {
+                        true => Poll::Ready(Ok(None)),
+                        false => Poll::Pending,
+                    },
+                }
+            })
+            .await?
+        };
+
+        if let Some(len) = len {
+            self.inbound_queue
+                .with(|queue| queue.push(&buffer[..len], accept));
+
+            self.notify();
+        }
+
+        Ok(())
+    }
+
+    /// Reads the stream until the expected response is received or the deadline is reached
+    async fn receive_response(
+        &self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+        deadline: Duration,
+    ) -> CyphalResult<(Header, Vec<u8>)> {
+        // messages and requests are only kept if a task is serving them
+        let accept = |header: &Header| match header {
+            Header::Service(id) if !id.is_request() => true,
+            _ => self.serving.get() > 0,
+        };
+
+        loop {
+            let response = self
+                .inbound_queue
+                .with(|queue| queue.take_response(service, source, destination, transfer));
+            if let Some(response) = response {
+                return Ok(response);
+            }
+
+            self.clock
+                .run_until(deadline, self.receive(accept))
+                .await??;
+        }
+    }
+}
+
+impl<const MAX_PAYLOAD_SIZE: usize, S: Serial, K: Clock> Transport
+    for SerialTransport<MAX_PAYLOAD_SIZE, S, K>
+{
+    /// Maximim Subject ID
+    const MAX_SUBJECT_ID: SubjectId = 8191;
+
+    /// Maximim Subject ID
+    const MAX_SERVICE_ID: ServiceId = 511;
+
+    /// Maximim Subject ID
+    const MAX_NODE_ID: NodeId = 65534;
+
+    async fn publish<M>(&self, message: &M) -> CyphalResult<()>
+    where
+        M: Message,
+    {
+        if message.subject() > Self::MAX_SUBJECT_ID
+            || message.source().is_some_and(|id| id > Self::MAX_NODE_ID)
+        {
+            return Err(CyphalError::OutOfRange);
+        }
+
+        let header = MessageHeader::new(
+            message.priority(),
+            message.source(),
+            None,
+            message.subject(),
+            self.next_transfer(),
+            0,
+            true,
+        )?;
+
+        self.send_frame(header.into(), message.data()).await
+    }
+
+    async fn invoque<R>(&self, request: &R, timeout: Duration) -> CyphalResult<R::Response>
+    where
+        R: Request,
+    {
+        if request.service() > Self::MAX_SERVICE_ID
+            || request.source() > Self::MAX_NODE_ID
+            || request.destination() > Self::MAX_NODE_ID
+        {
+            return Err(CyphalError::OutOfRange);
+        }
+
+        let deadline = self.clock.now() + timeout;
+        let transfer = self.next_transfer();
+        let header = ServiceHeader::new(
+            request.priority(),
+            request.source(),
+            request.destination(),
+            true,
+            request.service(),
+            transfer,
+            0,
+            true,
+        )?;
+
+        self.inbound_queue.with(|queue| {
+            queue.expect_response(
+                request.service(),
+                request.destination(),
+                request.source(),
+                transfer,
+            )
+        });
+
+        let response = async {
+            self.send_frame(header.into(), request.data()).await?;
+
+            self.receive_response(
+                request.service(),
+                request.destination(),
+                request.source(),
+                transfer,
+                deadline,
+            )
+            .await
+        }
+        .await;
+
+        // a late response must not be kept forever
+        let (header, payload) = match response {
+            Ok(response) => response,
+            Err(error) => {
+                self.inbound_queue.with(|queue| {
+                    queue.forget_response(
+                        request.service(),
+                        request.destination(),
+                        request.source(),
+                        transfer,
+                    )
+                });
+                return Err(error);
+            }
+        };
+
+        match header {
+            Header::Service(id) => R::Response::new_raw(
+                id.priority(),
+                id.service(),
+                id.source(),
+                id.destination(),
+                &payload,
+            ),
+            Header::Message(_) => Err(CyphalError::MalformedTransfer),
+        }
+    }
+
+    async fn serve<R>(&self, router: R) -> CyphalResult<()>
+    where
+        R: Router,
+    {
+        // only the ports the router processes are kept
+        let accept = |header: &Header| match header {
+            Header::Message(id) => router.accepts_message(id.subject()),
+            Header::Service(id) if id.is_request() => router.accepts_request(id.service()),
+            Header::Service(_) => true,
+        };
+
+        // messages and requests received by the other tasks are kept for this one
+        let _serving = Serving::new(&self.serving);
+
+        loop {
+            let incoming = self.inbound_queue.with(|queue| queue.pop_incoming());
+            let (header, payload) = match incoming {
+                Some(transfer) => transfer,
+                None => {
+                    if self.receive(accept).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            match header {
+                // an error processing one transfer must not stop the transport from serving the
+                // next ones, and the router is the one able to report it, so it is ignored here
+                Header::Message(id) => {
+                    let _ = router
+                        .process_message(id.priority(), id.subject(), id.source(), &payload)
+                        .await;
+                }
+                Header::Service(id) => {
+                    let response = router
+                        .process_request(
+                            id.priority(),
+                            id.service(),
+                            id.source(),
+                            id.destination(),
+                            &payload,
+                        )
+                        .await;
+
+                    if let Ok(Some(response)) = response {
+                        // the response must use the same transfer ID as the request
+                        let header = ServiceHeader::new(
+                            response.priority(),
+                            response.source(),
+                            response.destination(),
+                            false,
+                            response.service(),
+                            id.transfer(),
+                            0,
+                            true,
+                        )?;
+
+                        self.send_frame(header.into(), response.data()).await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends the frame, COBS encoded and surrounded by frame delimiters, to `output`.  The CRC-32C
+/// transfer checksum is appended to the data before it is encoded.
+pub(crate) fn encode_frame(header: Header, data: &[u8], output: &mut Vec<u8>) {
+    let mut frame: Vec<u8> = Vec::with_capacity(HEADER_SIZE + data.len() + 4);
+    frame.extend_from_slice(&header.as_raw());
+    frame.extend_from_slice(data);
+    frame.extend_from_slice(&CRC32C.checksum(data).to_le_bytes());
+
+    output.push(FRAME_DELIMITER);
+    cobs::encode(&frame, output);
+    output.push(FRAME_DELIMITER);
+}
+
+/// Counts a task serving the incoming transfers until it is dropped
+struct Serving<'a>(&'a Shared<usize>);
+
+impl<'a> Serving<'a> {
+    fn new(count: &'a Shared<usize>) -> Self {
+        count.with(|count| *count += 1);
+        Self(count)
+    }
+}
+
+impl Drop for Serving<'_> {
+    fn drop(&mut self) {
+        self.0.with(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::{encode_frame, CRC32C};
+    use crate::{
+        cobs,
+        test::{
+            pipe, TestClock, TestLargeMessage, TestRequest, TestRouter, TestSerial,
+            TestSmallMessage, LARGE_MESSAGE_SIZE, TEST_REQUEST_SIZE, TEST_RESPONSE_SIZE,
+        },
+        Serial as _, SerialTransport,
+    };
+    use core::{
+        future::{poll_fn, Future},
+        pin::pin,
+        time::Duration,
+    };
+    use cyphal::{CyphalError, Priority, Response, TransferId, Transport as _};
+    use cyphal_udp::{Header, MessageHeader, ServiceHeader, UdpTransferId, HEADER_SIZE};
+    use std::{vec, vec::Vec};
+
+    type TestTransport = SerialTransport<128, TestSerial, TestClock>;
+
+    fn decode_frame(frame: &[u8]) -> (Header, Vec<u8>) {
+        assert_eq!(frame.first(), Some(&0));
+        assert_eq!(frame.last(), Some(&0));
+        assert!(!frame[1..frame.len() - 1].contains(&0));
+
+        let frame = cobs::decode(&frame[1..frame.len() - 1]).unwrap();
+        let header: &[u8; HEADER_SIZE] = frame[..HEADER_SIZE].try_into().unwrap();
+        let (data, checksum) = frame[HEADER_SIZE..].split_at(frame.len() - HEADER_SIZE - 4);
+        assert_eq!(CRC32C.checksum(data).to_le_bytes(), checksum);
+
+        (Header::new(header).unwrap(), data.to_vec())
+    }
+
+    #[async_std::test]
+    async fn transmit_message() {
+        let (serial, other) = pipe();
+        let transport = TestTransport::new(serial, TestClock::default()).unwrap();
+
+        let data: Vec<u8> = (0..LARGE_MESSAGE_SIZE as u8).collect();
+        let data: [u8; LARGE_MESSAGE_SIZE] = data.try_into().unwrap();
+        let message = TestLargeMessage::new(Priority::High, 7509, Some(42), data).unwrap();
+        transport.publish(&message).await.unwrap();
+
+        let (header, payload) = decode_frame(&other.pending_bytes());
+        match header {
+            Header::Message(header) => {
+                assert_eq!(header.priority(), Priority::High);
+                assert_eq!(header.subject(), 7509);
+                assert_eq!(header.source(), Some(42));
+                assert_eq!(header.index(), 0);
+                assert!(header.end_of_transfer());
+            }
+            Header::Service(_) => panic!("Expected a message"),
+        }
+        assert_eq!(payload, data);
+    }
+
+    #[async_std::test]
+    async fn transmit_too_large() {
+        let (serial, _other) = pipe();
+        let transport: SerialTransport<64, TestSerial, TestClock> =
+            SerialTransport::new(serial, TestClock::default()).unwrap();
+
+        let message =
+            TestLargeMessage::new(Priority::High, 1, None, [0; LARGE_MESSAGE_SIZE]).unwrap();
+        let result = transport.publish(&message).await;
+
+        assert_eq!(result, Err(CyphalError::OutOfRange));
+    }
+
+    #[async_std::test]
+    async fn test_serve() {
+        let (serial, other) = pipe();
+        let transfer = UdpTransferId::default().next();
+
+        let mut received: Vec<u8> = Vec::new();
+        let header = MessageHeader::new(Priority::Nominal, None, None, 100, transfer, 0, true);
+        encode_frame(header.unwrap().into(), &[0, 1, 0], &mut received);
+        let header = ServiceHeader::new(Priority::High, 3, 2, true, 1, transfer, 0, true);
+        encode_frame(header.unwrap().into(), &[5, 6], &mut received);
+
+        other.write(&received).await.unwrap();
+        other.close();
+
+        let transport = TestTransport::new(serial, TestClock::default()).unwrap();
+        let router = TestRouter::new();
+        transport.serve(&router).await.unwrap();
+
+        let messages = router.messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0], (100, None, vec![0, 1, 0]));
+
+        let requests = router.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0], (1, 3, 2, vec![5, 6]));
+
+        let (header, payload) = decode_frame(&other.pending_bytes());
+        match header {
+            Header::Service(header) => {
+                assert!(!header.is_request());
+                assert_eq!(header.source(), 2);
+                assert_eq!(header.destination(), 3);
+                assert_eq!(header.transfer(), transfer);
+            }
+            Header::Message(_) => panic!("Expected a response"),
+        }
+        assert_eq!(payload, [7; TEST_RESPONSE_SIZE]);
+    }
+
+    #[async_std::test]
+    async fn test_invoque() {
+        let (client, server) = pipe();
+        let client = TestTransport::new(client, TestClock::new(Duration::from_millis(1))).unwrap();
+        let server = TestTransport::new(server, TestClock::default()).unwrap();
+        let router = TestRouter::new();
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, [5; TEST_REQUEST_SIZE]).unwrap();
+        let mut serve = pin!(server.serve(&router));
+        let mut invoque = pin!(client.invoque(&request, Duration::from_secs(1)));
+
+        // both ends run concurrently on the same task
+        let response = poll_fn(|cx| {
+            assert!(serve.as_mut().poll(cx).is_pending());
+            invoque.as_mut().poll(cx)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(router.requests.borrow().len(), 1);
+        assert_eq!(response.source(), 2);
+        assert_eq!(response.destination(), 3);
+        assert_eq!(response.data(), &[7; TEST_RESPONSE_SIZE]);
+    }
+
+    #[async_std::test]
+    async fn test_invoque_timeout() {
+        let (serial, other) = pipe();
+        let transport =
+            TestTransport::new(serial, TestClock::new(Duration::from_millis(100))).unwrap();
+
+        // the server answers an other request
+        let header = ServiceHeader::new(
+            Priority::Nominal,
+            2,
+            3,
+            false,
+            1,
+            UdpTransferId::default(),
+            0,
+            true,
+        );
+        let mut response: Vec<u8> = Vec::new();
+        encode_frame(header.unwrap().into(), &[1, 2], &mut response);
+        other.write(&response).await.unwrap();
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, [5; TEST_REQUEST_SIZE]).unwrap();
+        let result = transport.invoque(&request, Duration::from_secs(1)).await;
+
+        assert_eq!(result.err(), Some(CyphalError::Timeout));
+    }
+
+    #[async_std::test]
+    async fn publish_small_message() {
+        let (serial, other) = pipe();
+        let transport = TestTransport::new(serial, TestClock::default()).unwrap();
+
+        let message = TestSmallMessage::new(Priority::Nominal, 1, None, [0, 0]).unwrap();
+        transport.publish(&message).await.unwrap();
+        transport.publish(&message).await.unwrap();
+
+        let bytes = other.pending_bytes();
+        let frames: Vec<&[u8]> = bytes.split(|b| *b == 0).filter(|f| !f.is_empty()).collect();
+        assert_eq!(frames.len(), 2);
+    }
+}
@@ -1,13 +1,13 @@
-use cyphal::{CyphalResult, Message, NodeId, Priority, SubjectId, Transport};
+use cyphal::{CyphalError, CyphalResult, Message, NodeId, Priority, SubjectId, Transport};
 use cyphal_can::CanTransport;
-use cyphal_socketcan::CanFdSocket;
+use cyphal_socketcan::{CanFdSocket, SystemClock};
 
 const MESSAGE_SIZE: usize = 65;
 
 #[async_std::main]
 async fn main() {
     let socket = CanFdSocket::new("vcan1").unwrap();
-    let mut transport = CanTransport::new(socket).unwrap();
+    let transport = CanTransport::new(socket, SystemClock::new()).unwrap();
 
     let data: Vec<u8> = (1..(MESSAGE_SIZE + 1) as u8).collect();
     let data: [u8; MESSAGE_SIZE] = data.try_into().unwrap();
@@ -45,6 +45,17 @@ impl TestMessage {
 impl Message for TestMessage {
     const SIZE: usize = MESSAGE_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn source(&self) -> Option<NodeId> {
         self.source
     }
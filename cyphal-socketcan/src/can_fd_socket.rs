@@ -21,7 +21,7 @@ impl CanFdSocket {
 impl Can<FD_PAYLOAD_SIZE> for CanFdSocket {
     type Frame = FdFrame;
 
-    async fn transmit(&mut self, frame: &Self::Frame) -> CanResult<()> {
+    async fn transmit(&self, frame: &Self::Frame) -> CanResult<()> {
         let result = self.socket.write_frame(frame.inner_frame()).await;
 
         match result {
@@ -30,7 +30,7 @@ impl Can<FD_PAYLOAD_SIZE> for CanFdSocket {
         }
     }
 
-    async fn receive(&mut self) -> CanResult<Self::Frame> {
+    async fn receive(&self) -> CanResult<Self::Frame> {
         let result = self.socket.read_frame().await;
 
         match result {
@@ -55,7 +55,7 @@ impl Can<FD_PAYLOAD_SIZE> for CanFdSocket {
 mod test {
     use crate::{
         test::{MultiFrameMessage, SingleFrameMessage},
-        CanFdSocket,
+        CanFdSocket, SystemClock,
     };
     use cyphal::{Priority, Transport};
     use cyphal_can::CanTransport;
@@ -64,7 +64,7 @@ mod test {
     #[ignore = "need to have vcan setup"]
     async fn publish_single_frame() {
         let socket = CanFdSocket::new("vcan1").unwrap();
-        let mut transport = CanTransport::new(socket).unwrap();
+        let transport = CanTransport::new(socket, SystemClock::new()).unwrap();
 
         let data: Vec<u8> = (1..3).collect();
         let data: [u8; 2] = data.try_into().unwrap();
@@ -77,7 +77,7 @@ mod test {
     #[ignore = "need to have vcan setup"]
     async fn publish_multi_frame() {
         let socket = CanFdSocket::new("vcan1").unwrap();
-        let mut transport = CanTransport::new(socket).unwrap();
+        let transport = CanTransport::new(socket, SystemClock::new()).unwrap();
 
         let data: Vec<u8> = (1..66).collect();
         let data: [u8; 65] = data.try_into().unwrap();
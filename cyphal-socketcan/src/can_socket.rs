@@ -21,7 +21,7 @@ impl CanSocket {
 impl Can<CLASSIC_PAYLOAD_SIZE> for CanSocket {
     type Frame = Frame;
 
-    async fn transmit(&mut self, frame: &Self::Frame) -> CanResult<()> {
+    async fn transmit(&self, frame: &Self::Frame) -> CanResult<()> {
         let result = self.socket.write_frame(frame.inner_frame()).await;
 
         match result {
@@ -30,7 +30,7 @@ impl Can<CLASSIC_PAYLOAD_SIZE> for CanSocket {
         }
     }
 
-    async fn receive(&mut self) -> CanResult<Self::Frame> {
+    async fn receive(&self) -> CanResult<Self::Frame> {
         let result = self.socket.read_frame().await;
 
         match result {
@@ -55,7 +55,7 @@ impl Can<CLASSIC_PAYLOAD_SIZE> for CanSocket {
 mod test {
     use crate::{
         test::{MultiFrameMessage, SingleFrameMessage},
-        CanSocket,
+        CanSocket, SystemClock,
     };
     use cyphal::{Priority, Transport};
     use cyphal_can::CanTransport;
@@ -64,7 +64,7 @@ mod test {
     #[ignore = "need to have vcan setup"]
     async fn publish() {
         let socket = CanSocket::new("vcan0").unwrap();
-        let mut transport = CanTransport::new(socket).unwrap();
+        let transport = CanTransport::new(socket, SystemClock::new()).unwrap();
 
         let data: Vec<u8> = (1..3).collect();
         let data: [u8; 2] = data.try_into().unwrap();
@@ -77,7 +77,7 @@ mod test {
     #[ignore = "need to have vcan setup"]
     async fn publish_multi_frame() {
         let socket = CanSocket::new("vcan0").unwrap();
-        let mut transport = CanTransport::new(socket).unwrap();
+        let transport = CanTransport::new(socket, SystemClock::new()).unwrap();
 
         let data: Vec<u8> = (1..66).collect();
         let data: [u8; 65] = data.try_into().unwrap();
@@ -14,5 +14,7 @@ pub use frame::Frame;
 mod fd_frame;
 pub use fd_frame::FdFrame;
 
+pub use cyphal::SystemClock;
+
 #[cfg(test)]
 pub(crate) mod test;
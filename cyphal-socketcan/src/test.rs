@@ -1,4 +1,4 @@
-use cyphal::{CyphalResult, Message, NodeId, Priority, SubjectId};
+use cyphal::{CyphalError, CyphalResult, Message, NodeId, Priority, SubjectId};
 
 const SINGLE_SIZE: usize = 2;
 const MULTI_SIZE: usize = 65;
@@ -29,6 +29,17 @@ impl SingleFrameMessage {
 impl Message for SingleFrameMessage {
     const SIZE: usize = SINGLE_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn priority(&self) -> Priority {
         self.priority
     }
@@ -72,6 +83,17 @@ impl MultiFrameMessage {
 impl Message for MultiFrameMessage {
     const SIZE: usize = MULTI_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn source(&self) -> Option<NodeId> {
         self.source
     }
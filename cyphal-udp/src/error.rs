@@ -1,4 +1,5 @@
 use core::fmt::{Display, Formatter, Result as FmtResult};
+use cyphal::{CyphalError, TransportErrorKind};
 
 /// Represents a UDP Error
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -25,5 +26,15 @@ impl Display for UdpError {
     }
 }
 
+impl From<UdpError> for CyphalError {
+    fn from(value: UdpError) -> Self {
+        match value {
+            UdpError::InvalidAddress => CyphalError::Transport(TransportErrorKind::Address),
+            UdpError::Connection => CyphalError::Transport(TransportErrorKind::Connection),
+            UdpError::MaxPayloadExceded => CyphalError::OutOfRange,
+        }
+    }
+}
+
 /// The result of a Cyphal UDP operation.  On failure, a `UdpError` will be included.
 pub type UdpResult<T> = Result<T, UdpError>;
@@ -1,7 +1,13 @@
-use crate::{MessageHeader, ServiceHeader};
+use crate::{MessageHeader, ServiceHeader, UdpTransferId};
 use core::cmp::Ordering;
+use crc::Crc;
 use cyphal::{CyphalResult, Priority};
 
+const CRC16: Crc<u16> = Crc::<u16>::new(&crc::CRC_16_IBM_3740);
+
+/// Size of the header in bytes
+pub const HEADER_SIZE: usize = 24;
+
 /// Represents a payload header
 #[derive(Debug, Copy, Clone)]
 pub enum Header {
@@ -15,8 +21,8 @@ pub enum Header {
 impl Header {
     /// Constructs a new `Header`
     pub fn new(header: &[u8; 24]) -> CyphalResult<Header> {
-        // check bit 25 to see what type of Id this is
-        if (header[9] & 0x01) == 0 {
+        // check the service-not-message bit to see what type of header this is
+        if (header[7] & 0x80) == 0 {
             match MessageHeader::try_from(header) {
                 Ok(header) => Ok(Header::Message(header)),
                 Err(e) => Err(e),
@@ -29,7 +35,7 @@ impl Header {
         }
     }
 
-    /// Returns a `&[u8; 24]` representation of the `Header`
+    /// Returns a `[u8; 24]` representation of the `Header`
     pub fn as_raw(&self) -> [u8; 24] {
         match *self {
             Header::Message(m) => m.as_raw(),
@@ -44,6 +50,30 @@ impl Header {
             Header::Service(s) => s.priority(),
         }
     }
+
+    /// Returns the Transfer ID of the `Header`
+    pub fn transfer(&self) -> UdpTransferId {
+        match *self {
+            Header::Message(m) => m.transfer(),
+            Header::Service(s) => s.transfer(),
+        }
+    }
+
+    /// Returns the frame index of the `Header`
+    pub fn index(&self) -> u32 {
+        match *self {
+            Header::Message(m) => m.index(),
+            Header::Service(s) => s.index(),
+        }
+    }
+
+    /// Returns `true` if the `Header` belongs to the last frame of a transfer
+    pub fn end_of_transfer(&self) -> bool {
+        match *self {
+            Header::Message(m) => m.end_of_transfer(),
+            Header::Service(s) => s.end_of_transfer(),
+        }
+    }
 }
 
 impl From<MessageHeader> for Header {
@@ -79,3 +109,8 @@ impl PartialEq for Header {
 }
 
 impl Eq for Header {}
+
+/// Computes the CRC-16/CCITT-FALSE of the first 22 bytes of a header, in big endian byte order
+pub(crate) fn header_crc(header: &[u8; HEADER_SIZE]) -> [u8; 2] {
+    CRC16.checksum(&header[..22]).to_be_bytes()
+}
@@ -13,7 +13,7 @@ mod group_address;
 pub use group_address::GroupAddress;
 
 mod header;
-pub use header::Header;
+pub use header::{Header, HEADER_SIZE};
 
 mod message_group_address;
 pub use message_group_address::MessageGroupAddress;
@@ -27,6 +27,9 @@ pub use service_header::ServiceHeader;
 mod service_group_address;
 pub use service_group_address::ServiceGroupAddress;
 
+#[cfg(test)]
+pub(crate) mod test;
+
 mod transport;
 pub use transport::UdpTransport;
 
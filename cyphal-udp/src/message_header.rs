@@ -1,4 +1,4 @@
-use crate::UdpTransferId;
+use crate::{header::header_crc, UdpTransferId};
 use cyphal::{CyphalError, CyphalResult, NodeId, Priority, SubjectId, TransferId};
 
 /// Represents a UDP payload header used for messages
@@ -11,7 +11,6 @@ pub struct MessageHeader {
     transfer: UdpTransferId,
     index: u32,
     end_of_transfer: bool,
-    crc16: [u8; 2],
 }
 
 impl MessageHeader {
@@ -25,9 +24,8 @@ impl MessageHeader {
         transfer: UdpTransferId,
         index: u32,
         end_of_transfer: bool,
-        crc16: [u8; 2],
     ) -> CyphalResult<Self> {
-        if index > 0x7FFFFFFF {
+        if index > 0x7FFFFFFF || subject > 0x7FFF {
             return Err(CyphalError::OutOfRange);
         }
 
@@ -39,7 +37,6 @@ impl MessageHeader {
             transfer,
             index,
             end_of_transfer,
-            crc16,
         })
     }
 
@@ -78,49 +75,57 @@ impl MessageHeader {
         self.end_of_transfer
     }
 
-    /// Returns the crc16 of the message
-    pub fn crc16(&self) -> &[u8; 2] {
-        &self.crc16
+    /// Returns the header CRC-16/CCITT-FALSE of the message
+    pub fn crc16(&self) -> [u8; 2] {
+        let raw = self.as_raw();
+        [raw[22], raw[23]]
     }
 
-    /// Returns a `&[u8; 24]` representation of the message header
+    /// Returns a `[u8; 24]` representation of the message header
     pub fn as_raw(&self) -> [u8; 24] {
-        let source: [u8; 2] = match self.source {
-            Some(i) => [(i >> 8) as u8, (i & 0xFF) as u8],
-            None => [0xFF, 0xFF],
-        };
-        let destination: [u8; 2] = match self.destination {
-            Some(i) => [(i >> 8) as u8, (i & 0xFF) as u8],
-            None => [0xFF, 0xFF],
-        };
-        let transfer = self.transfer.value();
-        let index_mask: u8 = if self.end_of_transfer { 1 } else { 0 };
-        [
+        let source = self.source.unwrap_or(0xFFFF).to_le_bytes();
+        let destination = self.destination.unwrap_or(0xFFFF).to_le_bytes();
+        // the service-not-message bit 15 is cleared for messages
+        let specifier = self.subject.to_le_bytes();
+        let transfer = self.transfer.value().to_le_bytes();
+        let mut index = self.index;
+        if self.end_of_transfer {
+            index |= 0x8000_0000;
+        }
+        let index = index.to_le_bytes();
+
+        let mut raw = [
             1,
-            (self.priority as u8) << 5,
+            self.priority as u8,
             source[0],
             source[1],
             destination[0],
             destination[1],
-            (self.subject >> 7) as u8,
-            ((self.subject & 0x7F) << 1) as u8,
-            (transfer >> 56) as u8,
-            ((transfer >> 48) & 0xFF) as u8,
-            ((transfer >> 40) & 0xFF) as u8,
-            ((transfer >> 32) & 0xFF) as u8,
-            ((transfer >> 24) & 0xFF) as u8,
-            ((transfer >> 16) & 0xFF) as u8,
-            ((transfer >> 8) & 0xFF) as u8,
-            (transfer & 0xFF) as u8,
-            ((self.index >> 23) & 0xFF) as u8,
-            ((self.index >> 15) & 0xFF) as u8,
-            ((self.index >> 7) & 0xFF) as u8,
-            ((self.index & 0x7F) << 1) as u8 | index_mask,
+            specifier[0],
+            specifier[1],
+            transfer[0],
+            transfer[1],
+            transfer[2],
+            transfer[3],
+            transfer[4],
+            transfer[5],
+            transfer[6],
+            transfer[7],
+            index[0],
+            index[1],
+            index[2],
+            index[3],
+            0,
             0,
             0,
-            self.crc16[0],
-            self.crc16[1],
-        ]
+            0,
+        ];
+
+        let crc16 = header_crc(&raw);
+        raw[22] = crc16[0];
+        raw[23] = crc16[1];
+
+        raw
     }
 }
 
@@ -128,38 +133,32 @@ impl TryFrom<&[u8; 24]> for MessageHeader {
     type Error = CyphalError;
 
     fn try_from(value: &[u8; 24]) -> CyphalResult<Self> {
-        // ensure version = 1, not a service,
-        if value[0] != 1 || (value[7] & 1) == 1 {
+        // ensure version = 1, not a service
+        if value[0] != 1 || (value[7] & 0x80) != 0 {
             return Err(CyphalError::OutOfRange);
         }
 
-        let priority = Priority::try_from(value[1] >> 5)?;
-        let source: Option<NodeId> = if value[2] == 0xFF && value[3] == 0xFF {
-            None
-        } else {
-            Some(((value[2] as u16) << 8) | (value[3] as u16))
+        // ensure the header has not been corrupted
+        if header_crc(value) != [value[22], value[23]] {
+            return Err(CyphalError::CrcMismatch);
+        }
+
+        let priority = Priority::try_from(value[1] & 0x07)?;
+        let source: Option<NodeId> = match u16::from_le_bytes([value[2], value[3]]) {
+            0xFFFF => None,
+            id => Some(id),
         };
-        let destination: Option<NodeId> = if value[4] == 0xFF && value[5] == 0xFF {
-            None
-        } else {
-            Some(((value[4] as u16) << 8) | (value[5] as u16))
+        let destination: Option<NodeId> = match u16::from_le_bytes([value[4], value[5]]) {
+            0xFFFF => None,
+            id => Some(id),
         };
-        let subject: SubjectId = ((value[6] as u16) << 7) | ((value[7] as u16) >> 1);
-        let transfer: UdpTransferId = (((value[8] as u64) << 56)
-            | ((value[9] as u64) << 48)
-            | ((value[10] as u64) << 40)
-            | ((value[11] as u64) << 32)
-            | ((value[12] as u64) << 24)
-            | ((value[13] as u64) << 16)
-            | ((value[14] as u64) << 8)
-            | (value[15] as u64))
-            .try_into()?;
-        let index = ((value[16] as u32) << 23)
-            | ((value[17] as u32) << 15)
-            | ((value[18] as u32) << 7)
-            | ((value[19] as u32) >> 1);
-        let end_of_transfer = value[19] & 0x01 == 1;
-        let crc16 = [value[22], value[23]];
+        let subject: SubjectId = u16::from_le_bytes([value[6], value[7]]);
+        let transfer: UdpTransferId = u64::from_le_bytes([
+            value[8], value[9], value[10], value[11], value[12], value[13], value[14], value[15],
+        ])
+        .try_into()?;
+        let index = u32::from_le_bytes([value[16], value[17], value[18], value[19]]);
+        let end_of_transfer = index & 0x8000_0000 != 0;
 
         Ok(Self {
             priority,
@@ -167,9 +166,8 @@ impl TryFrom<&[u8; 24]> for MessageHeader {
             destination,
             subject,
             transfer,
-            index,
+            index: index & 0x7FFF_FFFF,
             end_of_transfer,
-            crc16,
         })
     }
 }
@@ -177,7 +175,7 @@ impl TryFrom<&[u8; 24]> for MessageHeader {
 #[cfg(test)]
 mod test {
     use crate::{MessageHeader, UdpTransferId};
-    use cyphal::{NodeId, Priority, SubjectId};
+    use cyphal::{CyphalError, NodeId, Priority, SubjectId};
 
     #[test]
     fn test() {
@@ -188,7 +186,6 @@ mod test {
         let transfer: UdpTransferId = 0x0404040404040404.try_into().unwrap();
         let index: u32 = 0x05050505;
         let end_of_transfer = false;
-        let crc16: [u8; 2] = [6, 7];
 
         let header = MessageHeader::new(
             priority,
@@ -198,7 +195,6 @@ mod test {
             transfer,
             index,
             end_of_transfer,
-            crc16,
         )
         .unwrap();
 
@@ -213,7 +209,39 @@ mod test {
         assert_eq!(target.transfer(), transfer);
         assert_eq!(target.index(), index);
         assert_eq!(target.end_of_transfer(), end_of_transfer);
-        assert_eq!(target.crc16()[0], crc16[0]);
-        assert_eq!(target.crc16()[1], crc16[1]);
+        assert_eq!(target.crc16(), header.crc16());
+    }
+
+    #[test]
+    fn test_corrupted_header() {
+        let subject: SubjectId = 7509;
+        let transfer: UdpTransferId = 42.try_into().unwrap();
+        let header =
+            MessageHeader::new(Priority::Nominal, None, None, subject, transfer, 0, true).unwrap();
+
+        let mut raw = header.as_raw();
+        raw[6] ^= 0x01;
+
+        assert_eq!(
+            MessageHeader::try_from(&raw).err(),
+            Some(CyphalError::CrcMismatch)
+        );
+    }
+
+    #[test]
+    fn test_wire_layout() {
+        let transfer: UdpTransferId = 0x0102030405060708.try_into().unwrap();
+        let header =
+            MessageHeader::new(Priority::Nominal, Some(42), None, 7509, transfer, 0, true).unwrap();
+
+        let raw = header.as_raw();
+
+        assert_eq!(raw[0], 1);
+        assert_eq!(raw[1], 4);
+        assert_eq!(raw[2..4], [42, 0]);
+        assert_eq!(raw[4..6], [0xFF, 0xFF]);
+        assert_eq!(raw[6..8], 7509_u16.to_le_bytes());
+        assert_eq!(raw[8..16], [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(raw[16..20], [0, 0, 0, 0x80]);
     }
 }
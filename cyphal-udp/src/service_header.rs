@@ -1,4 +1,4 @@
-use crate::UdpTransferId;
+use crate::{header::header_crc, UdpTransferId};
 use cyphal::{CyphalError, CyphalResult, NodeId, Priority, ServiceId, TransferId};
 
 /// Represents a payload header used for services
@@ -12,7 +12,6 @@ pub struct ServiceHeader {
     transfer: UdpTransferId,
     index: u32,
     end_of_transfer: bool,
-    crc16: [u8; 2],
 }
 
 impl ServiceHeader {
@@ -27,9 +26,8 @@ impl ServiceHeader {
         transfer: UdpTransferId,
         index: u32,
         end_of_transfer: bool,
-        crc16: [u8; 2],
     ) -> CyphalResult<Self> {
-        if index > 0x7FFFFFFF {
+        if index > 0x7FFFFFFF || service > 0x3FFF {
             return Err(CyphalError::OutOfRange);
         }
 
@@ -42,7 +40,6 @@ impl ServiceHeader {
             transfer,
             index,
             end_of_transfer,
-            crc16,
         })
     }
 
@@ -86,53 +83,61 @@ impl ServiceHeader {
         self.end_of_transfer
     }
 
-    /// Returns the crc16 of the service
-    pub fn crc16(&self) -> &[u8; 2] {
-        &self.crc16
+    /// Returns the header CRC-16/CCITT-FALSE of the service
+    pub fn crc16(&self) -> [u8; 2] {
+        let raw = self.as_raw();
+        [raw[22], raw[23]]
     }
 
-    /// Returns a `u32` representation of the service header
+    /// Returns a `[u8; 24]` representation of the service header
     pub fn as_raw(&self) -> [u8; 24] {
-        let index_mask: u8 = if self.end_of_transfer { 1 } else { 0 };
-        // If this is a service response transfer, this value equals the service-ID.
-        // If this is a service request transfer, this value equals 16384 + service-ID.
-        let service_id = if self.is_request {
-            self.service + 16384
-        } else {
-            self.service
-        };
-        let specifier = [
-            (service_id >> 7) as u8,
-            (((service_id & 0xFF) << 1) | 0x01) as u8,
-        ];
-        let transfer = self.transfer.value();
+        let source = self.source.to_le_bytes();
+        let destination = self.destination.to_le_bytes();
+        // set the service-not-message bit 15 and the request-not-response bit 14
+        let mut specifier = 0x8000 | self.service;
+        if self.is_request {
+            specifier |= 0x4000;
+        }
+        let specifier = specifier.to_le_bytes();
+        let transfer = self.transfer.value().to_le_bytes();
+        let mut index = self.index;
+        if self.end_of_transfer {
+            index |= 0x8000_0000;
+        }
+        let index = index.to_le_bytes();
 
-        [
+        let mut raw = [
             1,
-            (self.priority as u8) << 5,
-            (self.source >> 8) as u8,
-            (self.source & 0xFF) as u8,
-            (self.destination >> 8) as u8,
-            self.destination as u8,
+            self.priority as u8,
+            source[0],
+            source[1],
+            destination[0],
+            destination[1],
             specifier[0],
             specifier[1],
-            (transfer >> 56) as u8,
-            ((transfer >> 48) & 0xFF) as u8,
-            ((transfer >> 40) & 0xFF) as u8,
-            ((transfer >> 32) & 0xFF) as u8,
-            ((transfer >> 24) & 0xFF) as u8,
-            ((transfer >> 16) & 0xFF) as u8,
-            ((transfer >> 8) & 0xFF) as u8,
-            (transfer & 0xFF) as u8,
-            ((self.index >> 23) & 0xFF) as u8,
-            ((self.index >> 15) & 0xFF) as u8,
-            ((self.index >> 7) & 0xFF) as u8,
-            ((self.index & 0x7F) << 1) as u8 | index_mask,
+            transfer[0],
+            transfer[1],
+            transfer[2],
+            transfer[3],
+            transfer[4],
+            transfer[5],
+            transfer[6],
+            transfer[7],
+            index[0],
+            index[1],
+            index[2],
+            index[3],
+            0,
             0,
             0,
-            self.crc16[0],
-            self.crc16[1],
-        ]
+            0,
+        ];
+
+        let crc16 = header_crc(&raw);
+        raw[22] = crc16[0];
+        raw[23] = crc16[1];
+
+        raw
     }
 }
 
@@ -140,41 +145,36 @@ impl TryFrom<&[u8; 24]> for ServiceHeader {
     type Error = CyphalError;
 
     fn try_from(value: &[u8; 24]) -> CyphalResult<Self> {
-        // ensure version = 1, not a service,
-        if value[0] != 1 || (value[7] & 1) != 1 {
+        // ensure version = 1, is a service
+        if value[0] != 1 || (value[7] & 0x80) == 0 {
             return Err(CyphalError::OutOfRange);
         }
 
-        let priority = Priority::try_from(value[1] >> 5)?;
-        let service_id = ((value[6] as u16) << 7) | ((value[7] as u16) >> 1);
-        let service: ServiceId = service_id & 0x3FFF;
-        let is_request = service_id & 0x4000 > 0;
-        let transfer: UdpTransferId = (((value[8] as u64) << 56)
-            | ((value[9] as u64) << 48)
-            | ((value[10] as u64) << 40)
-            | ((value[11] as u64) << 32)
-            | ((value[12] as u64) << 24)
-            | ((value[13] as u64) << 16)
-            | ((value[14] as u64) << 8)
-            | (value[15] as u64))
-            .try_into()?;
-        let index = ((value[16] as u32) << 23)
-            | ((value[17] as u32) << 15)
-            | ((value[18] as u32) << 7)
-            | ((value[19] as u32) >> 1);
-        let end_of_transfer = value[19] & 0x01 == 1;
-        let crc16 = [value[22], value[23]];
+        // ensure the header has not been corrupted
+        if header_crc(value) != [value[22], value[23]] {
+            return Err(CyphalError::CrcMismatch);
+        }
+
+        let priority = Priority::try_from(value[1] & 0x07)?;
+        let specifier = u16::from_le_bytes([value[6], value[7]]);
+        let service: ServiceId = specifier & 0x3FFF;
+        let is_request = specifier & 0x4000 > 0;
+        let transfer: UdpTransferId = u64::from_le_bytes([
+            value[8], value[9], value[10], value[11], value[12], value[13], value[14], value[15],
+        ])
+        .try_into()?;
+        let index = u32::from_le_bytes([value[16], value[17], value[18], value[19]]);
+        let end_of_transfer = index & 0x8000_0000 != 0;
 
         Ok(Self {
             priority,
-            source: ((value[2] as u16) << 8) | (value[3] as u16),
-            destination: ((value[4] as u16) << 8) | (value[5] as u16),
+            source: u16::from_le_bytes([value[2], value[3]]),
+            destination: u16::from_le_bytes([value[4], value[5]]),
             service,
             is_request,
             transfer,
-            index,
+            index: index & 0x7FFF_FFFF,
             end_of_transfer,
-            crc16,
         })
     }
 }
@@ -194,7 +194,6 @@ mod test {
         let transfer: UdpTransferId = 0x0404040404040404.try_into().unwrap();
         let index: u32 = 0x05050505;
         let end_of_transfer = false;
-        let crc16: [u8; 2] = [6, 7];
 
         let header = ServiceHeader::new(
             priority,
@@ -205,7 +204,6 @@ mod test {
             transfer,
             index,
             end_of_transfer,
-            crc16,
         )
         .unwrap();
 
@@ -221,7 +219,21 @@ mod test {
         assert_eq!(target.transfer(), transfer);
         assert_eq!(target.index(), index);
         assert_eq!(target.end_of_transfer(), end_of_transfer);
-        assert_eq!(target.crc16()[0], crc16[0]);
-        assert_eq!(target.crc16()[1], crc16[1]);
+        assert_eq!(target.crc16(), header.crc16());
+    }
+
+    #[test]
+    fn test_response() {
+        let transfer: UdpTransferId = 7.try_into().unwrap();
+        let header =
+            ServiceHeader::new(Priority::Nominal, 2, 3, false, 430, transfer, 0, true).unwrap();
+
+        let raw = header.as_raw();
+        assert_eq!(raw[6..8], (0x8000_u16 | 430).to_le_bytes());
+
+        let target = ServiceHeader::try_from(&raw).unwrap();
+        assert!(!target.is_request());
+        assert_eq!(target.service(), 430);
+        assert!(target.end_of_transfer());
     }
 }
@@ -0,0 +1,49 @@
+extern crate std;
+
+use crate::{Udp, UdpError, UdpResult};
+use core::{
+    cell::{Cell, RefCell},
+    future::pending,
+};
+use std::{collections::VecDeque, string::String, vec::Vec};
+
+pub const TEST_PAYLOAD_SIZE: usize = 40;
+
+pub struct TestUdp {
+    pub sent: RefCell<Vec<(String, Vec<u8>)>>,
+    pub received: RefCell<VecDeque<Vec<u8>>>,
+    pub pending: Cell<bool>,
+}
+
+impl TestUdp {
+    pub fn new(received: Vec<Vec<u8>>) -> Self {
+        Self {
+            sent: RefCell::new(Vec::new()),
+            received: RefCell::new(received.into()),
+            pending: Cell::new(false),
+        }
+    }
+}
+
+impl Udp<TEST_PAYLOAD_SIZE> for TestUdp {
+    async fn send(&self, address: &str, buf: &[u8]) -> UdpResult<usize> {
+        if buf.len() > TEST_PAYLOAD_SIZE {
+            return Err(UdpError::MaxPayloadExceded);
+        }
+
+        self.sent.borrow_mut().push((address.into(), buf.to_vec()));
+        Ok(buf.len())
+    }
+
+    async fn receive(&self, data: &mut [u8]) -> UdpResult<usize> {
+        let datagram = self.received.borrow_mut().pop_front();
+        match datagram {
+            Some(datagram) => {
+                data[..datagram.len()].copy_from_slice(&datagram);
+                Ok(datagram.len())
+            }
+            None if self.pending.get() => pending().await,
+            None => Err(UdpError::Connection),
+        }
+    }
+}
@@ -0,0 +1,417 @@
+extern crate alloc;
+
+use super::CRC32C;
+use crate::{Header, UdpTransferId};
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    vec::Vec,
+};
+use core::time::Duration;
+use cyphal::{NodeId, ServiceId, SubjectId};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum Session {
+    Message(SubjectId, Option<NodeId>),
+    Request(ServiceId, NodeId, NodeId),
+    Response(ServiceId, NodeId, NodeId),
+}
+
+impl From<&Header> for Session {
+    fn from(header: &Header) -> Self {
+        match header {
+            Header::Message(m) => Session::Message(m.subject(), m.source()),
+            Header::Service(s) if s.is_request() => {
+                Session::Request(s.service(), s.source(), s.destination())
+            }
+            Header::Service(s) => Session::Response(s.service(), s.source(), s.destination()),
+        }
+    }
+}
+
+/// Transfer being reassembled
+struct PendingTransfer {
+    header: Header,
+    timestamp: Duration,
+    frames: BTreeMap<u32, Vec<u8>>,
+    last_index: Option<u32>,
+}
+
+pub struct InboundQueue {
+    timeout: Duration,
+    pending: BTreeMap<(Session, UdpTransferId), PendingTransfer>,
+    // the last transfer completed on each session and when it started
+    last_transfers: BTreeMap<Session, (UdpTransferId, Duration)>,
+    complete_incoming: VecDeque<(Header, Vec<u8>)>,
+    expected_responses: BTreeSet<(Session, UdpTransferId)>,
+    complete_responses: BTreeMap<(Session, UdpTransferId), (Header, Vec<u8>)>,
+}
+
+impl InboundQueue {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            pending: BTreeMap::new(),
+            last_transfers: BTreeMap::new(),
+            complete_incoming: VecDeque::new(),
+            expected_responses: BTreeSet::new(),
+            complete_responses: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the transfer-ID timeout
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Adds a frame received at `timestamp` to the queue.
+    ///
+    /// Once all the frames of a transfer have been received, messages and requests are kept until
+    /// they are popped, and responses until they are taken if they are expected.  Responses nobody
+    /// is waiting for are discarded, as are the frames of a transfer ID already received on the
+    /// same session within the transfer-ID timeout.
+    pub fn push(&mut self, header: Header, data: &[u8], timestamp: Duration) {
+        self.evict(timestamp);
+
+        let (header, payload) = match self.reassemble(header, data, timestamp) {
+            Some(transfer) => transfer,
+            None => return,
+        };
+
+        match Session::from(&header) {
+            session @ Session::Response(..) => {
+                let key = (session, header.transfer());
+                if self.expected_responses.contains(&key) {
+                    self.complete_responses.insert(key, (header, payload));
+                }
+            }
+            _ => self.complete_incoming.push_back((header, payload)),
+        }
+    }
+
+    /// Returns the next received message or request
+    pub fn pop_incoming(&mut self) -> Option<(Header, Vec<u8>)> {
+        self.complete_incoming.pop_front()
+    }
+
+    /// Keeps the response from `source` to `destination` once it is received
+    pub fn expect_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+    ) {
+        let key = (Session::Response(service, source, destination), transfer);
+        self.expected_responses.insert(key);
+    }
+
+    /// Returns the expected response if it has been received, in which case it is no longer expected
+    pub fn take_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+    ) -> Option<(Header, Vec<u8>)> {
+        let key = (Session::Response(service, source, destination), transfer);
+        let response = self.complete_responses.remove(&key)?;
+        self.expected_responses.remove(&key);
+
+        Some(response)
+    }
+
+    /// Stops expecting a response that hasn't been received
+    pub fn forget_response(
+        &mut self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+    ) {
+        let key = (Session::Response(service, source, destination), transfer);
+        self.expected_responses.remove(&key);
+        self.complete_responses.remove(&key);
+    }
+
+    /// Returns the header of the first frame and the payload, without the transfer CRC, once all the
+    /// frames of a transfer have been received.  Transfers failing the CRC check are discarded.
+    fn reassemble(
+        &mut self,
+        header: Header,
+        data: &[u8],
+        timestamp: Duration,
+    ) -> Option<(Header, Vec<u8>)> {
+        let session = Session::from(&header);
+        let transfer = header.transfer();
+
+        // anonymous transfers are single frame and can't be deduplicated, as they have no source
+        if let Session::Message(_, None) = session {
+            if header.index() == 0 && header.end_of_transfer() {
+                return validate(data).map(|payload| (header, payload));
+            }
+
+            return None;
+        }
+
+        // drop duplicates of the last transfer received on this session
+        if self
+            .last_transfers
+            .get(&session)
+            .is_some_and(|(last, _)| *last == transfer)
+        {
+            return None;
+        }
+
+        // single frame transfers don't need to be reassembled
+        if header.index() == 0 && header.end_of_transfer() {
+            self.last_transfers.insert(session, (transfer, timestamp));
+            return validate(data).map(|payload| (header, payload));
+        }
+
+        let key = (session, transfer);
+        let pending = self.pending.entry(key).or_insert_with(|| PendingTransfer {
+            header,
+            timestamp,
+            frames: BTreeMap::new(),
+            last_index: None,
+        });
+
+        // a frame beyond the end of the transfer, or a second end, makes the transfer malformed
+        let malformed = match pending.last_index {
+            Some(last_index) => {
+                header.index() > last_index
+                    || (header.end_of_transfer() && header.index() != last_index)
+            }
+            None => {
+                header.end_of_transfer()
+                    && pending
+                        .frames
+                        .keys()
+                        .next_back()
+                        .is_some_and(|index| *index > header.index())
+            }
+        };
+        if malformed {
+            self.pending.remove(&key);
+            return None;
+        }
+
+        if header.index() == 0 {
+            pending.header = header;
+        }
+        if header.end_of_transfer() {
+            pending.last_index = Some(header.index());
+        }
+        pending.frames.insert(header.index(), data.to_vec());
+
+        // frames can arrive out of order, so wait until every index up to the last one is present
+        let last_index = pending.last_index?;
+        if pending.frames.len() as u32 != last_index + 1 {
+            return None;
+        }
+
+        let pending = self.pending.remove(&key).unwrap();
+        self.last_transfers
+            .insert(session, (transfer, pending.timestamp));
+
+        let mut payload: Vec<u8> = Vec::new();
+        for frame in pending.frames.values() {
+            payload.extend_from_slice(frame);
+        }
+
+        validate(&payload).map(|payload| (pending.header, payload))
+    }
+
+    /// Removes the transfers that timed out and forgets the last transfers received longer than the
+    /// transfer-ID timeout ago.
+    fn evict(&mut self, now: Duration) {
+        let timeout = self.timeout;
+
+        self.pending
+            .retain(|_, pending| now.saturating_sub(pending.timestamp) < timeout);
+        self.last_transfers
+            .retain(|_, (_, timestamp)| now.saturating_sub(*timestamp) < timeout);
+    }
+}
+
+/// Verifies the trailing CRC-32C transfer checksum and returns the payload without it
+fn validate(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < 4 {
+        return None;
+    }
+
+    let (payload, checksum) = data.split_at(data.len() - 4);
+    if CRC32C.checksum(payload).to_le_bytes() != checksum {
+        return None;
+    }
+
+    Some(payload.to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::InboundQueue;
+    use crate::{transport::CRC32C, Header, MessageHeader, UdpTransferId};
+    use core::time::Duration;
+    use cyphal::Priority;
+    use std::vec::Vec;
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    fn header(source: Option<u16>, transfer: u64, index: u32, end_of_transfer: bool) -> Header {
+        let transfer = UdpTransferId::new(transfer).unwrap();
+        MessageHeader::new(
+            Priority::Nominal,
+            source,
+            None,
+            10,
+            transfer,
+            index,
+            end_of_transfer,
+        )
+        .unwrap()
+        .into()
+    }
+
+    fn with_crc(data: &[u8]) -> Vec<u8> {
+        let mut payload = data.to_vec();
+        payload.extend_from_slice(&CRC32C.checksum(data).to_le_bytes());
+        payload
+    }
+
+    fn message_count(queue: &mut InboundQueue) -> usize {
+        let mut count = 0;
+        while queue.pop_incoming().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn test_duplicate_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        let payload = with_crc(&[1, 2, 3]);
+
+        queue.push(header(Some(1), 5, 0, true), &payload, Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 1);
+
+        // same transfer ID within the timeout
+        queue.push(
+            header(Some(1), 5, 0, true),
+            &payload,
+            Duration::from_millis(500),
+        );
+        assert_eq!(message_count(&mut queue), 0);
+
+        // same transfer ID from another source
+        queue.push(
+            header(Some(2), 5, 0, true),
+            &payload,
+            Duration::from_millis(500),
+        );
+        assert_eq!(message_count(&mut queue), 1);
+
+        // same transfer ID after the timeout
+        queue.push(
+            header(Some(1), 5, 0, true),
+            &payload,
+            Duration::from_millis(2500),
+        );
+        assert_eq!(message_count(&mut queue), 1);
+    }
+
+    #[test]
+    fn test_duplicate_multi_frame_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        let payload = with_crc(&[1, 2, 3, 4, 5, 6]);
+        let (first, last) = payload.split_at(5);
+
+        queue.push(header(Some(1), 5, 0, false), first, Duration::ZERO);
+        queue.push(header(Some(1), 5, 1, true), last, Duration::from_millis(10));
+        assert_eq!(message_count(&mut queue), 1);
+
+        // the repeated frames are dropped rather than reassembled again
+        queue.push(
+            header(Some(1), 5, 0, false),
+            first,
+            Duration::from_millis(20),
+        );
+        queue.push(header(Some(1), 5, 1, true), last, Duration::from_millis(30));
+        assert_eq!(message_count(&mut queue), 0);
+        assert!(queue.pending.is_empty());
+    }
+
+    #[test]
+    fn test_anonymous_transfer() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        let payload = with_crc(&[1, 2, 3]);
+
+        queue.push(header(None, 5, 0, true), &payload, Duration::ZERO);
+        queue.push(header(None, 5, 0, true), &payload, Duration::ZERO);
+        assert_eq!(message_count(&mut queue), 2);
+
+        // anonymous transfers can't span several frames
+        queue.push(header(None, 6, 0, false), &payload, Duration::ZERO);
+        assert!(queue.pending.is_empty());
+    }
+
+    #[test]
+    fn test_lost_frame() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        let payload = with_crc(&[1, 2, 3, 4, 5, 6]);
+        let (first, last) = payload.split_at(5);
+
+        // the first frame is lost
+        queue.push(header(Some(1), 5, 1, true), last, Duration::ZERO);
+        assert_eq!(queue.pending.len(), 1);
+
+        // the incomplete transfer is evicted after the timeout
+        queue.push(header(Some(1), 6, 0, false), first, Duration::from_secs(3));
+        assert_eq!(queue.pending.len(), 1);
+        assert_eq!(message_count(&mut queue), 0);
+
+        queue.push(header(Some(1), 6, 1, true), last, Duration::from_secs(3));
+        assert_eq!(message_count(&mut queue), 1);
+        assert!(queue.pending.is_empty());
+    }
+
+    #[test]
+    fn test_frame_beyond_end() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        let payload = with_crc(&[1, 2, 3, 4, 5, 6]);
+        let (first, last) = payload.split_at(5);
+
+        queue.push(header(Some(1), 5, 2, false), first, Duration::ZERO);
+        assert_eq!(queue.pending.len(), 1);
+
+        // the end of the transfer comes before a frame that was already received
+        queue.push(header(Some(1), 5, 1, true), last, Duration::from_millis(10));
+        assert!(queue.pending.is_empty());
+
+        queue.push(header(Some(1), 6, 1, true), last, Duration::from_millis(20));
+        queue.push(
+            header(Some(1), 6, 2, false),
+            first,
+            Duration::from_millis(30),
+        );
+        assert!(queue.pending.is_empty());
+        assert_eq!(message_count(&mut queue), 0);
+    }
+
+    #[test]
+    fn test_set_timeout() {
+        let mut queue = InboundQueue::new(TIMEOUT);
+        queue.set_timeout(Duration::from_millis(100));
+        let payload = with_crc(&[1, 2, 3]);
+
+        queue.push(header(Some(1), 5, 0, true), &payload, Duration::ZERO);
+        queue.push(
+            header(Some(1), 5, 0, true),
+            &payload,
+            Duration::from_millis(200),
+        );
+        assert_eq!(message_count(&mut queue), 2);
+    }
+}
@@ -1,23 +1,223 @@
-use crate::Udp;
+mod inbound_queue;
+use inbound_queue::InboundQueue;
+
+extern crate alloc;
+
+use crate::{
+    GroupAddress, Header, MessageGroupAddress, MessageHeader, ServiceGroupAddress, ServiceHeader,
+    Udp, UdpTransferId, HEADER_SIZE, MULTIGROUP_ADDRESS_PORT,
+};
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    future::{poll_fn, Future},
+    mem,
+    net::Ipv4Addr,
+    pin::pin,
+    task::{Poll, Waker},
+    time::Duration,
+};
+use crc::Crc;
 use cyphal::{
-    CyphalError, CyphalResult, Message, NodeId, Request, Router, ServiceId, SubjectId, Transport,
+    Clock, CyphalError, CyphalResult, Message, NodeId, Request, Response, Router, ServiceId,
+    Shared, SubjectId, TransferId, Transport,
 };
 
+const CRC32C: Crc<u32> = Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+/// Default transfer-ID timeout recommended by the Cyphal specification
+const DEFAULT_TRANSFER_ID_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Represents a UDP Transport
-pub struct UdpTransport<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>> {
-    #[allow(dead_code)]
+///
+/// The transport can be shared between tasks: datagrams received while a task is waiting on a
+/// response are made available to the task serving the incoming transfers, and vice versa.
+pub struct UdpTransport<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>, K: Clock> {
     udp: U,
+    clock: K,
+    transfer: Shared<UdpTransferId>,
+    inbound_queue: Shared<InboundQueue>,
+    received: Shared<usize>,
+    waiters: Shared<Vec<Waker>>,
+    serving: Shared<usize>,
 }
 
-impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>> UdpTransport<MAX_PAYLOAD_SIZE, U> {
-    /// Constructs a new UDP transport
-    pub fn new(udp: U) -> CyphalResult<Self> {
-        Ok(UdpTransport { udp })
+impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>, K: Clock>
+    UdpTransport<MAX_PAYLOAD_SIZE, U, K>
+{
+    /// Constructs a new UDP transport.
+    ///
+    /// The clock times out service calls and timestamps the received datagrams.  Partially received
+    /// transfers are discarded, and repeated transfer IDs are ignored, for the duration of the
+    /// transfer-ID timeout which defaults to 2 seconds.
+    pub fn new(udp: U, clock: K) -> CyphalResult<Self> {
+        assert!(
+            MAX_PAYLOAD_SIZE > HEADER_SIZE,
+            "Invalid MAX_PAYLOAD_SIZE value.  Must be larger than the 24 bytes header"
+        );
+
+        Ok(UdpTransport {
+            udp,
+            clock,
+            transfer: Shared::new(UdpTransferId::default()),
+            inbound_queue: Shared::new(InboundQueue::new(DEFAULT_TRANSFER_ID_TIMEOUT)),
+            received: Shared::new(0),
+            waiters: Shared::new(Vec::new()),
+            serving: Shared::new(0),
+        })
+    }
+
+    /// Sets the transfer-ID timeout
+    pub fn set_transfer_id_timeout(&self, timeout: Duration) {
+        self.inbound_queue.with(|queue| queue.set_timeout(timeout));
+    }
+
+    fn next_transfer(&self) -> UdpTransferId {
+        // read and incremented at once, so that concurrent transfers get distinct IDs
+        self.transfer.with(|transfer| {
+            *transfer = transfer.next();
+            *transfer
+        })
+    }
+
+    /// Counts that a datagram was received and wakes the tasks waiting for it
+    fn notify(&self) {
+        // the count is incremented and the waiters taken at once, so that a task registering in
+        // the meantime sees the new count
+        let waiters = self.waiters.with(|waiters| {
+            self.received.with(|count| *count = count.wrapping_add(1));
+            mem::take(waiters)
+        });
+        waiters.into_iter().for_each(Waker::wake);
+    }
+
+    /// Registers the waker of a task until a datagram is received, unless the count moved past
+    /// `received` in the meantime, in which case true is returned
+    fn wait(&self, received: usize, waker: &Waker) -> bool {
+        self.waiters.with(|waiters| {
+            if self.received.get() != received {
+                return true;
+            }
+            if !waiters.iter().any(|w| w.will_wake(waker)) {
+                waiters.push(waker.clone());
+            }
+
+            false
+        })
+    }
+
+    /// Waits until a datagram has been added to the inbound queue.
+    ///
+    /// The inbound queue is shared by all the tasks using the transport, so this returns as soon
+    /// as a datagram is received by this task or any other.  Frames rejected by `accept` are
+    /// dropped without being added to the queue.
+    async fn receive<A>(&self, accept: A) -> CyphalResult<()>
+    where
+        A: Fn(&Header) -> bool,
+    {
+        let received = self.received.get();
+        let mut buffer: [u8; MAX_PAYLOAD_SIZE] = [0; MAX_PAYLOAD_SIZE];
+
+        let len = {
+            let mut receive = pin!(self.udp.receive(&mut buffer));
+
+            poll_fn(|cx| {
+                if self.received.get() != received {
+                    return Poll::Ready(Ok(None));
+                }
+
+                match receive.as_mut().poll(cx) {
+                    Poll::Ready(result) => Poll::Ready(result.map(Some)),
+                    Poll::Pending => match self.wait(received, cx.waker()) {
+                        true => Poll::Ready(Ok(None)),
+                        false => Poll::Pending,
+                    },
+                }
+            })
+            .await?
+        };
+
+        if let Some(len) = len {
+            if let Some((header, data)) = parse_frame(&buffer[..len]) {
+                if accept(&header) {
+                    let now = self.clock.now();
+                    self.inbound_queue
+                        .with(|queue| queue.push(header, data, now));
+                }
+            }
+
+            self.notify();
+        }
+
+        Ok(())
+    }
+
+    /// Receives datagrams until the expected response is complete or the deadline is reached
+    async fn receive_response(
+        &self,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        transfer: UdpTransferId,
+        deadline: Duration,
+    ) -> CyphalResult<(Header, Vec<u8>)> {
+        // messages and requests are only kept if a task is serving them
+        let accept = |header: &Header| match header {
+            Header::Service(id) if !id.is_request() => true,
+            _ => self.serving.get() > 0,
+        };
+
+        loop {
+            let response = self
+                .inbound_queue
+                .with(|queue| queue.take_response(service, source, destination, transfer));
+            if let Some(response) = response {
+                return Ok(response);
+            }
+
+            self.clock
+                .run_until(deadline, self.receive(accept))
+                .await??;
+        }
+    }
+
+    /// Splits the data into frames and sends them to the group address.  The CRC-32C transfer
+    /// checksum is appended to the data before it is split.
+    async fn send_frames<H>(
+        &self,
+        address: GroupAddress,
+        data: &[u8],
+        header: H,
+    ) -> CyphalResult<()>
+    where
+        H: Fn(u32, bool) -> CyphalResult<Header>,
+    {
+        let ip: Ipv4Addr = address.into();
+        let address: String = format!("{}:{}", ip, MULTIGROUP_ADDRESS_PORT);
+
+        let mut payload: Vec<u8> = Vec::with_capacity(data.len() + 4);
+        payload.extend_from_slice(data);
+        payload.extend_from_slice(&CRC32C.checksum(data).to_le_bytes());
+
+        let chunks = payload.chunks(MAX_PAYLOAD_SIZE - HEADER_SIZE);
+        let last_index = chunks.len() - 1;
+
+        let mut frame: Vec<u8> = Vec::with_capacity(MAX_PAYLOAD_SIZE);
+        for (index, chunk) in chunks.enumerate() {
+            let header = header(index as u32, index == last_index)?;
+
+            frame.clear();
+            frame.extend_from_slice(&header.as_raw());
+            frame.extend_from_slice(chunk);
+
+            self.udp.send(&address, &frame).await?;
+        }
+
+        Ok(())
     }
 }
 
-impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>> Transport
-    for UdpTransport<MAX_PAYLOAD_SIZE, U>
+impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>, K: Clock> Transport
+    for UdpTransport<MAX_PAYLOAD_SIZE, U, K>
 {
     /// Maximim Subject ID
     const MAX_SUBJECT_ID: SubjectId = 8191;
@@ -28,7 +228,7 @@ impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>> Transport
     /// Maximim Subject ID
     const MAX_NODE_ID: NodeId = 65534;
 
-    async fn publish<M>(&mut self, message: &M) -> CyphalResult<()>
+    async fn publish<M>(&self, message: &M) -> CyphalResult<()>
     where
         M: Message,
     {
@@ -38,10 +238,26 @@ impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>> Transport
             return Err(CyphalError::OutOfRange);
         }
 
-        todo!()
+        let transfer = self.next_transfer();
+        let address = MessageGroupAddress::new(message.subject());
+
+        self.send_frames(address.into(), message.data(), |index, end_of_transfer| {
+            let header = MessageHeader::new(
+                message.priority(),
+                message.source(),
+                None,
+                message.subject(),
+                transfer,
+                index,
+                end_of_transfer,
+            )?;
+
+            Ok(header.into())
+        })
+        .await
     }
 
-    async fn invoque<R>(&mut self, request: &R) -> CyphalResult<R::Response>
+    async fn invoque<R>(&self, request: &R, timeout: Duration) -> CyphalResult<R::Response>
     where
         R: Request,
     {
@@ -51,13 +267,399 @@ impl<const MAX_PAYLOAD_SIZE: usize, U: Udp<MAX_PAYLOAD_SIZE>> Transport
         {
             return Err(CyphalError::OutOfRange);
         }
-        todo!()
+
+        let deadline = self.clock.now() + timeout;
+        let transfer = self.next_transfer();
+        let address = ServiceGroupAddress::new(request.destination());
+
+        self.inbound_queue.with(|queue| {
+            queue.expect_response(
+                request.service(),
+                request.destination(),
+                request.source(),
+                transfer,
+            )
+        });
+
+        let response = async {
+            self.send_frames(address.into(), request.data(), |index, end_of_transfer| {
+                let header = ServiceHeader::new(
+                    request.priority(),
+                    request.source(),
+                    request.destination(),
+                    true,
+                    request.service(),
+                    transfer,
+                    index,
+                    end_of_transfer,
+                )?;
+
+                Ok(header.into())
+            })
+            .await?;
+
+            self.receive_response(
+                request.service(),
+                request.destination(),
+                request.source(),
+                transfer,
+                deadline,
+            )
+            .await
+        }
+        .await;
+
+        // a late response must not be kept forever
+        let (header, payload) = match response {
+            Ok(response) => response,
+            Err(error) => {
+                self.inbound_queue.with(|queue| {
+                    queue.forget_response(
+                        request.service(),
+                        request.destination(),
+                        request.source(),
+                        transfer,
+                    )
+                });
+                return Err(error);
+            }
+        };
+
+        match header {
+            Header::Service(id) => R::Response::new_raw(
+                id.priority(),
+                id.service(),
+                id.source(),
+                id.destination(),
+                &payload,
+            ),
+            Header::Message(_) => Err(CyphalError::MalformedTransfer),
+        }
     }
 
-    async fn serve<R>(&mut self, _router: R) -> CyphalResult<()>
+    async fn serve<R>(&self, router: R) -> CyphalResult<()>
     where
         R: Router,
     {
-        todo!()
+        // only the ports the router processes are reassembled
+        let accept = |header: &Header| match header {
+            Header::Message(id) => router.accepts_message(id.subject()),
+            Header::Service(id) if id.is_request() => router.accepts_request(id.service()),
+            Header::Service(_) => true,
+        };
+
+        // messages and requests received by the other tasks are kept for this one
+        let _serving = Serving::new(&self.serving);
+
+        loop {
+            let incoming = self.inbound_queue.with(|queue| queue.pop_incoming());
+            let (header, payload) = match incoming {
+                Some(transfer) => transfer,
+                None => {
+                    if self.receive(accept).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            match header {
+                // an error processing one transfer must not stop the transport from serving the
+                // next ones, and the router is the one able to report it, so it is ignored here
+                Header::Message(id) => {
+                    let _ = router
+                        .process_message(id.priority(), id.subject(), id.source(), &payload)
+                        .await;
+                }
+                Header::Service(id) => {
+                    if let Ok(Some(response)) = router
+                        .process_request(
+                            id.priority(),
+                            id.service(),
+                            id.source(),
+                            id.destination(),
+                            &payload,
+                        )
+                        .await
+                    {
+                        // the response must use the same transfer ID as the request
+                        let address = ServiceGroupAddress::new(response.destination());
+
+                        self.send_frames(
+                            address.into(),
+                            response.data(),
+                            |index, end_of_transfer| {
+                                let header = ServiceHeader::new(
+                                    response.priority(),
+                                    response.source(),
+                                    response.destination(),
+                                    false,
+                                    response.service(),
+                                    id.transfer(),
+                                    index,
+                                    end_of_transfer,
+                                )?;
+
+                                Ok(header.into())
+                            },
+                        )
+                        .await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a datagram into its header and its data.  Returns `None` if the header is not valid.
+fn parse_frame(datagram: &[u8]) -> Option<(Header, &[u8])> {
+    if datagram.len() < HEADER_SIZE {
+        return None;
+    }
+
+    let (header, data) = datagram.split_at(HEADER_SIZE);
+    let header: &[u8; HEADER_SIZE] = header.try_into().ok()?;
+
+    match Header::new(header) {
+        Ok(header) => Some((header, data)),
+        Err(_) => None,
+    }
+}
+
+/// Counts a task serving the incoming transfers until it is dropped
+struct Serving<'a>(&'a Shared<usize>);
+
+impl<'a> Serving<'a> {
+    fn new(count: &'a Shared<usize>) -> Self {
+        count.with(|count| *count += 1);
+        Self(count)
+    }
+}
+
+impl Drop for Serving<'_> {
+    fn drop(&mut self) {
+        self.0.with(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::CRC32C;
+    use crate::{
+        test::{
+            TestClock, TestLargeMessage, TestRequest, TestRouter, TestSmallMessage, TestUdp,
+            LARGE_MESSAGE_SIZE, TEST_PAYLOAD_SIZE, TEST_REQUEST_SIZE, TEST_RESPONSE_SIZE,
+        },
+        Header, MessageHeader, ServiceHeader, UdpTransferId, UdpTransport, HEADER_SIZE,
+    };
+    use core::time::Duration;
+    use cyphal::{CyphalError, Priority, Response, TransferId, Transport as _};
+    use std::{vec, vec::Vec};
+
+    fn datagram(header: Header, data: &[u8]) -> Vec<u8> {
+        let mut datagram = header.as_raw().to_vec();
+        datagram.extend_from_slice(data);
+        datagram
+    }
+
+    fn with_crc(data: &[u8]) -> Vec<u8> {
+        let mut payload = data.to_vec();
+        payload.extend_from_slice(&CRC32C.checksum(data).to_le_bytes());
+        payload
+    }
+
+    fn sent_transfer(frame: &[u8]) -> UdpTransferId {
+        let header: &[u8; HEADER_SIZE] = frame[..HEADER_SIZE].try_into().unwrap();
+        Header::new(header).unwrap().transfer()
+    }
+
+    #[async_std::test]
+    async fn transmit_small_message() {
+        let udp = TestUdp::new(Vec::new());
+        let transport: UdpTransport<TEST_PAYLOAD_SIZE, TestUdp, TestClock> =
+            UdpTransport::new(udp, TestClock::default()).expect("Could not create transport");
+
+        let message = TestSmallMessage::new(Priority::Nominal, 7509, Some(42), [1, 2]).unwrap();
+        transport.publish(&message).await.unwrap();
+
+        let sent = transport.udp.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "239.0.29.85:9382");
+
+        let header: &[u8; HEADER_SIZE] = sent[0].1[..HEADER_SIZE].try_into().unwrap();
+        let header = Header::new(header).unwrap();
+        assert_eq!(header.index(), 0);
+        assert!(header.end_of_transfer());
+        assert_eq!(&sent[0].1[HEADER_SIZE..], with_crc(&[1, 2]).as_slice());
+    }
+
+    #[async_std::test]
+    async fn transmit_large_message() {
+        let udp = TestUdp::new(Vec::new());
+        let transport: UdpTransport<TEST_PAYLOAD_SIZE, TestUdp, TestClock> =
+            UdpTransport::new(udp, TestClock::default()).expect("Could not create transport");
+
+        let data: Vec<u8> = (0..LARGE_MESSAGE_SIZE as u8).collect();
+        let data: [u8; LARGE_MESSAGE_SIZE] = data.try_into().unwrap();
+
+        let message = TestLargeMessage::new(Priority::Nominal, 1, None, data).unwrap();
+        transport.publish(&message).await.unwrap();
+
+        let sent = transport.udp.sent.borrow();
+        assert_eq!(sent.len(), 5);
+
+        let mut payload: Vec<u8> = Vec::new();
+        for (i, (_, frame)) in sent.iter().enumerate() {
+            assert!(frame.len() <= TEST_PAYLOAD_SIZE);
+
+            let header: &[u8; HEADER_SIZE] = frame[..HEADER_SIZE].try_into().unwrap();
+            let header = Header::new(header).unwrap();
+            assert_eq!(header.index(), i as u32);
+            assert_eq!(header.end_of_transfer(), i == 4);
+            assert_eq!(header.transfer(), sent_transfer(&sent[0].1));
+
+            payload.extend_from_slice(&frame[HEADER_SIZE..]);
+        }
+
+        assert_eq!(payload, with_crc(&data));
+    }
+
+    #[async_std::test]
+    async fn test_invoque() {
+        let transfer = UdpTransferId::default().next();
+        let header =
+            ServiceHeader::new(Priority::Nominal, 2, 3, false, 1, transfer, 0, true).unwrap();
+        let response = datagram(header.into(), &with_crc(&[1, 2]));
+
+        let udp = TestUdp::new(vec![response]);
+        let transport: UdpTransport<TEST_PAYLOAD_SIZE, TestUdp, TestClock> =
+            UdpTransport::new(udp, TestClock::default()).expect("Could not create transport");
+
+        let data: [u8; TEST_REQUEST_SIZE] = [5; TEST_REQUEST_SIZE];
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, data).unwrap();
+        let response = transport
+            .invoque(&request, Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        let sent = transport.udp.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "239.1.0.2:9382");
+        assert_eq!(sent_transfer(&sent[0].1), transfer);
+
+        assert_eq!(response.source(), 2);
+        assert_eq!(response.destination(), 3);
+        assert_eq!(response.data(), &[1, 2]);
+    }
+
+    #[async_std::test]
+    async fn invoque_drops_unserved_transfers() {
+        let transfer = UdpTransferId::default().next();
+        let message = MessageHeader::new(Priority::Nominal, Some(42), None, 100, transfer, 0, true);
+        let response =
+            ServiceHeader::new(Priority::Nominal, 2, 3, false, 1, transfer, 0, true).unwrap();
+        let udp = TestUdp::new(vec![
+            datagram(message.unwrap().into(), &with_crc(&[3])),
+            datagram(response.into(), &with_crc(&[1, 2])),
+        ]);
+        let transport: UdpTransport<TEST_PAYLOAD_SIZE, TestUdp, TestClock> =
+            UdpTransport::new(udp, TestClock::default()).expect("Could not create transport");
+
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, [5; TEST_REQUEST_SIZE]).unwrap();
+        let response = transport
+            .invoque(&request, Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data(), &[1, 2]);
+        assert!(transport
+            .inbound_queue
+            .with(|queue| queue.pop_incoming())
+            .is_none());
+    }
+
+    #[async_std::test]
+    async fn test_serve() {
+        let data: Vec<u8> = (0..LARGE_MESSAGE_SIZE as u8).collect();
+        let payload = with_crc(&data);
+        let transfer = UdpTransferId::default().next();
+
+        // multi-frame message received out of order
+        let mut received: Vec<Vec<u8>> = Vec::new();
+        let chunks: Vec<&[u8]> = payload.chunks(TEST_PAYLOAD_SIZE - HEADER_SIZE).collect();
+        for (index, chunk) in chunks.iter().enumerate().rev() {
+            let header = MessageHeader::new(
+                Priority::Nominal,
+                Some(42),
+                None,
+                100,
+                transfer,
+                index as u32,
+                index == chunks.len() - 1,
+            )
+            .unwrap();
+            received.push(datagram(header.into(), chunk));
+        }
+
+        // corrupted transfer must be discarded
+        let header =
+            MessageHeader::new(Priority::Nominal, None, None, 101, transfer, 0, true).unwrap();
+        received.push(datagram(header.into(), &[1, 2, 3, 4, 5]));
+
+        let header = ServiceHeader::new(Priority::High, 3, 2, true, 1, transfer, 0, true).unwrap();
+        received.push(datagram(header.into(), &with_crc(&[5, 6])));
+
+        let udp = TestUdp::new(received);
+        let transport: UdpTransport<TEST_PAYLOAD_SIZE, TestUdp, TestClock> =
+            UdpTransport::new(udp, TestClock::default()).expect("Could not create transport");
+
+        let router = TestRouter::new();
+        transport.serve(&router).await.unwrap();
+
+        let messages = router.messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0], (100, Some(42), data));
+
+        let requests = router.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0], (1, 3, 2, vec![5, 6]));
+
+        let sent = transport.udp.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "239.1.0.3:9382");
+
+        let header: &[u8; HEADER_SIZE] = sent[0].1[..HEADER_SIZE].try_into().unwrap();
+        match Header::new(header).unwrap() {
+            Header::Service(header) => {
+                assert!(!header.is_request());
+                assert_eq!(header.source(), 2);
+                assert_eq!(header.destination(), 3);
+                assert_eq!(header.transfer(), transfer);
+            }
+            Header::Message(_) => panic!("Expected a response"),
+        }
+        assert_eq!(
+            &sent[0].1[HEADER_SIZE..],
+            with_crc(&[7; TEST_RESPONSE_SIZE]).as_slice()
+        );
+    }
+
+    #[async_std::test]
+    async fn test_invoque_timeout() {
+        let udp = TestUdp::new(Vec::new());
+        udp.pending.set(true);
+        let transport: UdpTransport<TEST_PAYLOAD_SIZE, TestUdp, TestClock> =
+            UdpTransport::new(udp, TestClock::default()).expect("Could not create transport");
+
+        let data: [u8; TEST_REQUEST_SIZE] = [5; TEST_REQUEST_SIZE];
+        let request = TestRequest::new(Priority::Nominal, 1, 2, 3, data).unwrap();
+        let result = transport.invoque(&request, Duration::from_secs(1)).await;
+
+        assert_eq!(result.err(), Some(CyphalError::Timeout));
+        assert_eq!(transport.clock.now.get(), Duration::from_secs(1));
     }
 }
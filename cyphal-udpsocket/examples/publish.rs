@@ -1,6 +1,6 @@
-use cyphal::{CyphalResult, Message, NodeId, Priority, SubjectId, Transport};
+use cyphal::{CyphalError, CyphalResult, Message, NodeId, Priority, SubjectId, Transport};
 use cyphal_udp::{MessageGroupAddress, UdpTransport};
-use cyphal_udpsocket::UdpSocket;
+use cyphal_udpsocket::{SystemClock, UdpSocket};
 
 const MESSAGE_SIZE: usize = 65;
 const MAX_PAYLOAD_SIZE: usize = 565;
@@ -9,7 +9,7 @@ const MAX_PAYLOAD_SIZE: usize = 565;
 async fn main() {
     let address = MessageGroupAddress::new(123);
     let socket: UdpSocket<MAX_PAYLOAD_SIZE> = UdpSocket::new(address.into()).unwrap();
-    let mut transport = UdpTransport::new(socket).unwrap();
+    let transport = UdpTransport::new(socket, SystemClock::new()).unwrap();
 
     let data: Vec<u8> = (1..(MESSAGE_SIZE + 1) as u8).collect();
     let data: [u8; MESSAGE_SIZE] = data.try_into().unwrap();
@@ -47,6 +47,17 @@ impl TestMessage {
 impl Message for TestMessage {
     const SIZE: usize = MESSAGE_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn source(&self) -> Option<NodeId> {
         self.source
     }
@@ -2,5 +2,7 @@
 #![forbid(missing_docs)]
 #![allow(async_fn_in_trait)]
 
+pub use cyphal::SystemClock;
+
 mod udp_socket;
 pub use udp_socket::UdpSocket;
@@ -0,0 +1,83 @@
+use crate::{CyphalError, CyphalResult};
+use core::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+    time::Duration,
+};
+
+/// Trait representing a monotonic clock
+pub trait Clock {
+    /// Returns the time elapsed since an arbitrary, fixed point in the past.
+    ///
+    /// The value must never decrease between two calls.
+    fn now(&self) -> Duration;
+
+    /// Waits until the clock reaches the deadline.
+    async fn sleep_until(&self, deadline: Duration);
+
+    /// Runs the future until it completes or the clock reaches the deadline.
+    ///
+    /// On timeout, the future is dropped and `CyphalError::Timeout` is returned.
+    async fn run_until<F>(&self, deadline: Duration, future: F) -> CyphalResult<F::Output>
+    where
+        F: Future,
+    {
+        // a busy future must not starve the deadline
+        if self.now() >= deadline {
+            return Err(CyphalError::Timeout);
+        }
+
+        let mut future = pin!(future);
+        let mut sleep = pin!(self.sleep_until(deadline));
+
+        poll_fn(|cx| {
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+
+            match sleep.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Err(CyphalError::Timeout)),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{test::TestClock, Clock, CyphalError};
+    use core::{future::pending, time::Duration};
+
+    #[async_std::test]
+    async fn test_run_until() {
+        let clock = TestClock::default();
+
+        let result = clock.run_until(Duration::from_secs(1), async { 5 }).await;
+
+        assert_eq!(result, Ok(5));
+    }
+
+    #[async_std::test]
+    async fn test_timeout() {
+        let clock = TestClock::default();
+
+        let result = clock
+            .run_until(Duration::from_secs(1), pending::<()>())
+            .await;
+
+        assert_eq!(result, Err(CyphalError::Timeout));
+        assert_eq!(clock.now(), Duration::from_secs(1));
+    }
+
+    #[async_std::test]
+    async fn test_expired_deadline() {
+        let clock = TestClock::default();
+        clock.sleep_until(Duration::from_secs(2)).await;
+
+        let result = clock.run_until(Duration::from_secs(1), async { 5 }).await;
+
+        assert_eq!(result, Err(CyphalError::Timeout));
+    }
+}
@@ -0,0 +1,569 @@
+use crate::{
+    CyphalError, CyphalResult, Message, NodeId, Priority, Request, Response, Router, ServiceId,
+    SubjectId,
+};
+use core::{future::Future, marker::PhantomData};
+
+/// Trait representing the handler of the messages published on a subject
+pub trait MessageHandler<M: Message> {
+    /// Processes a received message
+    async fn handle(&self, message: M) -> CyphalResult<()>;
+}
+
+impl<M, F, Fut> MessageHandler<M> for F
+where
+    M: Message,
+    F: Fn(M) -> Fut,
+    Fut: Future<Output = CyphalResult<()>>,
+{
+    async fn handle(&self, message: M) -> CyphalResult<()> {
+        self(message).await
+    }
+}
+
+/// Trait representing the handler of the requests sent to a service
+pub trait RequestHandler<R: Request> {
+    /// Processes a received request and returns the response to send back, if any
+    async fn handle(&self, request: R) -> CyphalResult<Option<R::Response>>;
+}
+
+impl<R, F, Fut> RequestHandler<R> for F
+where
+    R: Request,
+    F: Fn(R) -> Fut,
+    Fut: Future<Output = CyphalResult<Option<R::Response>>>,
+{
+    async fn handle(&self, request: R) -> CyphalResult<Option<R::Response>> {
+        self(request).await
+    }
+}
+
+/// Trait representing the handlers registered on a [`Dispatcher`]
+pub trait Handlers {
+    /// Type of the responses returned by the request handlers
+    type Response: Response;
+
+    /// Returns true if a handler is registered for the subject
+    fn accepts_message(&self, subject: SubjectId) -> bool;
+
+    /// Returns true if a handler is registered for the service
+    fn accepts_request(&self, service: ServiceId) -> bool;
+
+    /// Decodes the message and hands it to the handler registered for the subject
+    async fn process_message(
+        &self,
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<()>;
+
+    /// Decodes the request and hands it to the handler registered for the service
+    async fn process_request(
+        &self,
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Option<Self::Response>>;
+}
+
+impl Handlers for () {
+    type Response = NoResponse;
+
+    fn accepts_message(&self, _subject: SubjectId) -> bool {
+        false
+    }
+
+    fn accepts_request(&self, _service: ServiceId) -> bool {
+        false
+    }
+
+    async fn process_message(
+        &self,
+        _priority: Priority,
+        _subject: SubjectId,
+        _source: Option<NodeId>,
+        _data: &[u8],
+    ) -> CyphalResult<()> {
+        Ok(())
+    }
+
+    async fn process_request(
+        &self,
+        _priority: Priority,
+        _service: ServiceId,
+        _source: NodeId,
+        _destination: NodeId,
+        _data: &[u8],
+    ) -> CyphalResult<Option<Self::Response>> {
+        Ok(None)
+    }
+}
+
+/// Router handing the incoming transfers to the handlers registered for their port.
+///
+/// Payloads longer than the message or request type are truncated before being decoded, as
+/// single frame transfers can be padded by the transport.
+pub struct Dispatcher<H: Handlers = ()> {
+    handlers: H,
+}
+
+impl Dispatcher {
+    /// Constructs a new dispatcher without any handler
+    pub fn new() -> Self {
+        Self { handlers: () }
+    }
+}
+
+impl Default for Dispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Handlers> Dispatcher<H> {
+    /// Registers the handler of the messages of type `M` published on the subject
+    pub fn subscribe<M, F>(
+        self,
+        subject: SubjectId,
+        handler: F,
+    ) -> Dispatcher<Subscription<M, F, H>>
+    where
+        M: Message,
+        F: MessageHandler<M>,
+    {
+        Dispatcher {
+            handlers: Subscription {
+                subject,
+                handler,
+                next: self.handlers,
+                message: PhantomData,
+            },
+        }
+    }
+
+    /// Registers the handler of the requests of type `R` sent to the service
+    pub fn serve<R, F>(self, service: ServiceId, handler: F) -> Dispatcher<Service<R, F, H>>
+    where
+        R: Request,
+        F: RequestHandler<R>,
+    {
+        Dispatcher {
+            handlers: Service {
+                service,
+                handler,
+                next: self.handlers,
+                request: PhantomData,
+            },
+        }
+    }
+}
+
+impl<H: Handlers> Router for Dispatcher<H> {
+    fn accepts_message(&self, subject: SubjectId) -> bool {
+        self.handlers.accepts_message(subject)
+    }
+
+    fn accepts_request(&self, service: ServiceId) -> bool {
+        self.handlers.accepts_request(service)
+    }
+
+    async fn process_message(
+        &self,
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<()> {
+        self.handlers
+            .process_message(priority, subject, source, data)
+            .await
+    }
+
+    async fn process_request(
+        &self,
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Option<impl Response>> {
+        self.handlers
+            .process_request(priority, service, source, destination, data)
+            .await
+    }
+}
+
+// forwards to the dispatcher, so that it can be borrowed by the transports
+impl<H: Handlers> Router for &Dispatcher<H> {
+    fn accepts_message(&self, subject: SubjectId) -> bool {
+        (**self).accepts_message(subject)
+    }
+
+    fn accepts_request(&self, service: ServiceId) -> bool {
+        (**self).accepts_request(service)
+    }
+
+    async fn process_message(
+        &self,
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<()> {
+        (**self)
+            .process_message(priority, subject, source, data)
+            .await
+    }
+
+    async fn process_request(
+        &self,
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Option<impl Response>> {
+        (**self)
+            .process_request(priority, service, source, destination, data)
+            .await
+    }
+}
+
+/// Handler registered for the messages published on a subject
+pub struct Subscription<M, F, N> {
+    subject: SubjectId,
+    handler: F,
+    next: N,
+    message: PhantomData<M>,
+}
+
+impl<M, F, N> Handlers for Subscription<M, F, N>
+where
+    M: Message,
+    F: MessageHandler<M>,
+    N: Handlers,
+{
+    type Response = N::Response;
+
+    fn accepts_message(&self, subject: SubjectId) -> bool {
+        subject == self.subject || self.next.accepts_message(subject)
+    }
+
+    fn accepts_request(&self, service: ServiceId) -> bool {
+        self.next.accepts_request(service)
+    }
+
+    async fn process_message(
+        &self,
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<()> {
+        if subject != self.subject {
+            return self
+                .next
+                .process_message(priority, subject, source, data)
+                .await;
+        }
+
+        let data = &data[..data.len().min(M::SIZE)];
+        let message = M::new_raw(priority, subject, source, data)?;
+
+        self.handler.handle(message).await
+    }
+
+    async fn process_request(
+        &self,
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Option<Self::Response>> {
+        self.next
+            .process_request(priority, service, source, destination, data)
+            .await
+    }
+}
+
+/// Handler registered for the requests sent to a service
+pub struct Service<R, F, N> {
+    service: ServiceId,
+    handler: F,
+    next: N,
+    request: PhantomData<R>,
+}
+
+impl<R, F, N> Handlers for Service<R, F, N>
+where
+    R: Request,
+    F: RequestHandler<R>,
+    N: Handlers,
+{
+    type Response = EitherResponse<R::Response, N::Response>;
+
+    fn accepts_message(&self, subject: SubjectId) -> bool {
+        self.next.accepts_message(subject)
+    }
+
+    fn accepts_request(&self, service: ServiceId) -> bool {
+        service == self.service || self.next.accepts_request(service)
+    }
+
+    async fn process_message(
+        &self,
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<()> {
+        self.next
+            .process_message(priority, subject, source, data)
+            .await
+    }
+
+    async fn process_request(
+        &self,
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Option<Self::Response>> {
+        if service != self.service {
+            let response = self
+                .next
+                .process_request(priority, service, source, destination, data)
+                .await?;
+
+            return Ok(response.map(EitherResponse::Second));
+        }
+
+        let data = &data[..data.len().min(R::SIZE)];
+        let request = R::new_raw(priority, service, source, destination, data)?;
+        let response = self.handler.handle(request).await?;
+
+        Ok(response.map(EitherResponse::First))
+    }
+}
+
+/// Response returned by one of the request handlers of a [`Dispatcher`]
+pub enum EitherResponse<A, B> {
+    /// Response of the last registered handler
+    First(A),
+    /// Response of one of the handlers registered before
+    Second(B),
+}
+
+impl<A: Response, B: Response> Response for EitherResponse<A, B> {
+    const SIZE: usize = if A::SIZE > B::SIZE { A::SIZE } else { B::SIZE };
+
+    fn new_raw(
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        match A::new_raw(priority, service, source, destination, data) {
+            Ok(response) => Ok(Self::First(response)),
+            Err(_) => B::new_raw(priority, service, source, destination, data).map(Self::Second),
+        }
+    }
+
+    fn priority(&self) -> Priority {
+        match self {
+            Self::First(response) => response.priority(),
+            Self::Second(response) => response.priority(),
+        }
+    }
+
+    fn service(&self) -> ServiceId {
+        match self {
+            Self::First(response) => response.service(),
+            Self::Second(response) => response.service(),
+        }
+    }
+
+    fn destination(&self) -> NodeId {
+        match self {
+            Self::First(response) => response.destination(),
+            Self::Second(response) => response.destination(),
+        }
+    }
+
+    fn source(&self) -> NodeId {
+        match self {
+            Self::First(response) => response.source(),
+            Self::Second(response) => response.source(),
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        match self {
+            Self::First(response) => response.data(),
+            Self::Second(response) => response.data(),
+        }
+    }
+}
+
+/// Response of a [`Dispatcher`] without request handlers.  It can't be constructed.
+pub enum NoResponse {}
+
+impl Response for NoResponse {
+    const SIZE: usize = 0;
+
+    fn new_raw(
+        _priority: Priority,
+        _service: ServiceId,
+        _source: NodeId,
+        _destination: NodeId,
+        _data: &[u8],
+    ) -> CyphalResult<Self> {
+        Err(CyphalError::OutOfRange)
+    }
+
+    fn priority(&self) -> Priority {
+        match *self {}
+    }
+
+    fn service(&self) -> ServiceId {
+        match *self {}
+    }
+
+    fn destination(&self) -> NodeId {
+        match *self {}
+    }
+
+    fn source(&self) -> NodeId {
+        match *self {}
+    }
+
+    fn data(&self) -> &[u8] {
+        match *self {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use crate::{
+        test::{
+            TestMessage, TestRequest, TestResponse, TEST_MESSAGE_SIZE, TEST_REQUEST_SIZE,
+            TEST_RESPONSE_SIZE,
+        },
+        Dispatcher, Message, Priority, Request, Response, Router,
+    };
+    use core::cell::RefCell;
+    use std::vec::Vec;
+
+    #[async_std::test]
+    async fn test_subscribe() {
+        let received: RefCell<Vec<TestMessage>> = RefCell::new(Vec::new());
+        let dispatcher = Dispatcher::new().subscribe(7, |message: TestMessage| {
+            received.borrow_mut().push(message);
+            async { Ok(()) }
+        });
+
+        assert!(dispatcher.accepts_message(7));
+        assert!(!dispatcher.accepts_message(8));
+        assert!(!dispatcher.accepts_request(7));
+
+        // padding added by the transport is ignored
+        let data = [3; TEST_MESSAGE_SIZE + 2];
+        let result = dispatcher
+            .process_message(Priority::High, 7, Some(5), &data)
+            .await;
+        assert_eq!(result, Ok(()));
+
+        let result = dispatcher
+            .process_message(Priority::High, 8, Some(5), &data)
+            .await;
+        assert_eq!(result, Ok(()));
+
+        let received = received.borrow();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].priority(), Priority::High);
+        assert_eq!(received[0].source(), Some(5));
+        assert_eq!(received[0].data(), &[3; TEST_MESSAGE_SIZE]);
+    }
+
+    #[async_std::test]
+    async fn test_subscribe_anonymous() {
+        let received: RefCell<Vec<TestMessage>> = RefCell::new(Vec::new());
+        let dispatcher = Dispatcher::new().subscribe(7, |message: TestMessage| {
+            received.borrow_mut().push(message);
+            async { Ok(()) }
+        });
+
+        let data = [3; TEST_MESSAGE_SIZE];
+        let result = dispatcher
+            .process_message(Priority::High, 7, None, &data)
+            .await;
+        assert_eq!(result, Ok(()));
+
+        let received = received.borrow();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].source(), None);
+    }
+
+    #[async_std::test]
+    async fn test_serve() {
+        let dispatcher = Dispatcher::new()
+            .serve(1, |request: TestRequest| async move {
+                let data = [request.data()[0]; TEST_RESPONSE_SIZE];
+                TestResponse::new_raw(
+                    request.priority(),
+                    request.service(),
+                    request.destination(),
+                    request.source(),
+                    &data,
+                )
+                .map(Some)
+            })
+            .serve(2, |_request: TestRequest| async { Ok(None) });
+
+        assert!(dispatcher.accepts_request(1));
+        assert!(dispatcher.accepts_request(2));
+        assert!(!dispatcher.accepts_request(3));
+        assert!(!dispatcher.accepts_message(1));
+
+        let data = [9; TEST_REQUEST_SIZE];
+        let response = dispatcher
+            .process_request(Priority::Nominal, 1, 3, 4, &data)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.service(), 1);
+        assert_eq!(response.source(), 4);
+        assert_eq!(response.destination(), 3);
+        assert_eq!(response.data(), &[9; TEST_RESPONSE_SIZE]);
+
+        let response = dispatcher
+            .process_request(Priority::Nominal, 2, 3, 4, &data)
+            .await
+            .unwrap();
+        assert!(response.is_none());
+
+        let response = dispatcher
+            .process_request(Priority::Nominal, 3, 3, 4, &data)
+            .await
+            .unwrap();
+        assert!(response.is_none());
+    }
+
+    #[async_std::test]
+    async fn test_malformed_payload() {
+        let dispatcher = Dispatcher::new().subscribe(7, |_: TestMessage| async { Ok(()) });
+
+        let result = dispatcher
+            .process_message(Priority::High, 7, Some(5), &[1])
+            .await;
+        assert!(result.is_err());
+    }
+}
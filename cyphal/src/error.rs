@@ -2,19 +2,85 @@ use core::fmt::{Display, Formatter, Result as FmtResult};
 
 /// Cyphal Error
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
 pub enum CyphalError {
     /// An error caused by the underlying transport
-    Transport,
+    Transport(TransportErrorKind),
 
     /// The value is out of the permissible range
     OutOfRange,
+
+    /// The operation did not complete before its deadline
+    Timeout,
+
+    /// The CRC of a received transfer or header doesn't match its content
+    CrcMismatch,
+
+    /// A received transfer doesn't follow the transport protocol, e.g. a frame is missing or out of
+    /// sequence
+    MalformedTransfer,
+
+    /// A buffer or a queue has no room left for the data
+    BufferFull,
 }
 
 impl Display for CyphalError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Self::Transport => write!(f, "An error has occured in the underlying transport"),
+            Self::Transport(kind) => write!(
+                f,
+                "An error has occured in the underlying transport: {}",
+                kind
+            ),
             Self::OutOfRange => write!(f, "The value is outside the permissable range"),
+            Self::Timeout => write!(f, "The operation has timed out"),
+            Self::CrcMismatch => write!(f, "The CRC does not match the received data"),
+            Self::MalformedTransfer => write!(f, "The received transfer is malformed"),
+            Self::BufferFull => write!(f, "There is no room left in the buffer"),
+        }
+    }
+}
+
+/// The cause of an error in the underlying transport
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum TransportErrorKind {
+    /// The bit value monitored on the bus differs from the bit value sent
+    Bit,
+
+    /// Six consecutive equal bits were detected in a bit stuffed field
+    Stuff,
+
+    /// The CRC of a frame computed by the controller doesn't match the received one
+    FrameCrc,
+
+    /// A fixed-form bit field contains one or more illegal bits
+    Form,
+
+    /// A transmitted frame was not acknowledged
+    Acknowledge,
+
+    /// The address is invalid
+    Address,
+
+    /// The connection to the network or to the peer failed
+    Connection,
+
+    /// Another error, which the transport may describe in more detail
+    Other,
+}
+
+impl Display for TransportErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Bit => write!(f, "bit error"),
+            Self::Stuff => write!(f, "stuff error"),
+            Self::FrameCrc => write!(f, "frame CRC error"),
+            Self::Form => write!(f, "form error"),
+            Self::Acknowledge => write!(f, "acknowledgement error"),
+            Self::Address => write!(f, "invalid address"),
+            Self::Connection => write!(f, "connection failure"),
+            Self::Other => write!(f, "other error"),
         }
     }
 }
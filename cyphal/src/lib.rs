@@ -3,8 +3,20 @@
 #![forbid(missing_docs)]
 #![allow(async_fn_in_trait)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod clock;
+pub use clock::Clock;
+
+mod dispatcher;
+pub use dispatcher::{
+    Dispatcher, EitherResponse, Handlers, MessageHandler, NoResponse, RequestHandler, Service,
+    Subscription,
+};
+
 mod error;
-pub use error::{CyphalError, CyphalResult};
+pub use error::{CyphalError, CyphalResult, TransportErrorKind};
 
 mod message;
 pub use message::Message;
@@ -27,12 +39,20 @@ pub use router::Router;
 mod service_id;
 pub use service_id::ServiceId;
 
+mod shared;
+pub use shared::Shared;
+
 mod subject_id;
 pub use subject_id::SubjectId;
 
 #[cfg(test)]
 pub(crate) mod test;
 
+#[cfg(feature = "std")]
+mod system_clock;
+#[cfg(feature = "std")]
+pub use system_clock::SystemClock;
+
 mod transfer_id;
 pub use transfer_id::TransferId;
 
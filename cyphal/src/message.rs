@@ -1,10 +1,18 @@
-use crate::{NodeId, Priority, SubjectId};
+use crate::{CyphalResult, NodeId, Priority, SubjectId};
 
 /// Trait representing a message
 pub trait Message: Sized {
     /// Size of the message payload
     const SIZE: usize;
 
+    /// Constructs a new message from a received payload
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self>;
+
     /// The Priority of the message
     fn priority(&self) -> Priority;
 
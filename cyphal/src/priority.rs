@@ -80,17 +80,16 @@ mod test {
 
     fn get_priorities() -> Vec<(Priority, u8)> {
         // Arrange
-        let mut values: Vec<(Priority, u8)> = Vec::new();
-        values.push((Priority::Exceptional, 0));
-        values.push((Priority::Immediate, 1));
-        values.push((Priority::Fast, 2));
-        values.push((Priority::High, 3));
-        values.push((Priority::Nominal, 4));
-        values.push((Priority::Low, 5));
-        values.push((Priority::Slow, 6));
-        values.push((Priority::Optional, 7));
-
-        values
+        std::vec![
+            (Priority::Exceptional, 0),
+            (Priority::Immediate, 1),
+            (Priority::Fast, 2),
+            (Priority::High, 3),
+            (Priority::Nominal, 4),
+            (Priority::Low, 5),
+            (Priority::Slow, 6),
+            (Priority::Optional, 7),
+        ]
     }
 
     #[test]
@@ -2,13 +2,29 @@ use crate::{CyphalResult, NodeId, Priority, Response, ServiceId, SubjectId};
 
 /// Represents a router
 pub trait Router {
-    /// Processes an incoming message
+    /// Returns true if messages published on the subject are processed.
+    ///
+    /// Transports don't reassemble the transfers of subjects that aren't processed.
+    #[allow(unused_variables)]
+    fn accepts_message(&self, subject: SubjectId) -> bool {
+        true
+    }
+
+    /// Returns true if requests to the service are processed.
+    ///
+    /// Transports don't reassemble the transfers of services that aren't processed.
+    #[allow(unused_variables)]
+    fn accepts_request(&self, service: ServiceId) -> bool {
+        true
+    }
+
+    /// Processes an incoming message.  `source` is `None` for messages published by anonymous nodes.
     #[allow(unused_variables)]
     async fn process_message(
         &self,
         priority: Priority,
         subject: SubjectId,
-        source: NodeId,
+        source: Option<NodeId>,
         data: &[u8],
     ) -> CyphalResult<()> {
         Ok(())
@@ -36,7 +52,9 @@ pub trait Router {
                 _destination: NodeId,
                 _data: &[u8],
             ) -> CyphalResult<Self> {
-                Err(crate::CyphalError::Transport)
+                Err(crate::CyphalError::Transport(
+                    crate::TransportErrorKind::Other,
+                ))
             }
 
             fn priority(&self) -> Priority {
@@ -0,0 +1,73 @@
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// Represents state shared by the tasks using a transport.
+///
+/// Accesses are guarded by a critical section, so the state can be shared between threads as long
+/// as it can be sent between them.  Binaries must provide a critical section implementation, e.g.
+/// the `std` feature of this crate or of the `critical-section` crate.
+pub struct Shared<T> {
+    inner: Mutex<RefCell<T>>,
+}
+
+impl<T> Shared<T> {
+    /// Constructs new shared state
+    pub const fn new(value: T) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(value)),
+        }
+    }
+
+    /// Runs `f` with exclusive access to the state.  `f` must not access the same state again, but
+    /// critical sections nest, so it can access other shared state.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.inner.borrow_ref_mut(cs)))
+    }
+}
+
+impl<T: Copy> Shared<T> {
+    /// Returns a copy of the state
+    pub fn get(&self) -> T {
+        self.with(|value| *value)
+    }
+
+    /// Replaces the state
+    pub fn set(&self, value: T) {
+        self.with(|v| *v = value)
+    }
+}
+
+impl<T: Default> Default for Shared<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use crate::Shared;
+    use std::{sync::Arc, thread, vec::Vec};
+
+    #[test]
+    fn test_shared_between_threads() {
+        let shared = Arc::new(Shared::new(0_usize));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        shared.with(|value| *value += 1);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(shared.get(), 400);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::Clock;
+use core::time::Duration;
+use std::time::Instant;
+
+/// Represents a monotonic clock based on the operating system clock, which sleeps with async-std
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Constructs a new system clock
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        async_std::task::sleep(deadline.saturating_sub(self.now())).await
+    }
+}
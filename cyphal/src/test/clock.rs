@@ -0,0 +1,20 @@
+use crate::Clock;
+use core::{cell::Cell, time::Duration};
+
+/// Clock jumping straight to the deadline when sleeping
+#[derive(Default)]
+pub struct TestClock {
+    pub now: Cell<Duration>,
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        if deadline > self.now.get() {
+            self.now.set(deadline);
+        }
+    }
+}
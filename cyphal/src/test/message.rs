@@ -1,5 +1,5 @@
 use super::TEST_MESSAGE_SIZE;
-use crate::{CyphalResult, Message, NodeId, Priority, SubjectId};
+use crate::{CyphalError, CyphalResult, Message, NodeId, Priority, SubjectId};
 
 pub struct TestMessage {
     priority: Priority,
@@ -27,6 +27,17 @@ impl TestMessage {
 impl Message for TestMessage {
     const SIZE: usize = TEST_MESSAGE_SIZE;
 
+    fn new_raw(
+        priority: Priority,
+        subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, subject, source, data)
+    }
+
     fn priority(&self) -> Priority {
         self.priority
     }
@@ -1,3 +1,6 @@
+mod clock;
+pub use clock::TestClock;
+
 mod message;
 pub use message::TestMessage;
 
@@ -1,5 +1,5 @@
 use super::{TestResponse, TEST_REQUEST_SIZE};
-use crate::{CyphalResult, NodeId, Priority, Request, ServiceId};
+use crate::{CyphalError, CyphalResult, NodeId, Priority, Request, ServiceId};
 
 pub struct TestRequest {
     priority: Priority,
@@ -32,13 +32,15 @@ impl Request for TestRequest {
     type Response = TestResponse;
 
     fn new_raw(
-        _priority: Priority,
-        _service: ServiceId,
-        _source: NodeId,
-        _destination: NodeId,
-        _data: &[u8],
+        priority: Priority,
+        service: ServiceId,
+        source: NodeId,
+        destination: NodeId,
+        data: &[u8],
     ) -> CyphalResult<Self> {
-        todo!()
+        let data = data.try_into().map_err(|_| CyphalError::OutOfRange)?;
+
+        Self::new(priority, service, destination, source, data)
     }
 
     fn priority(&self) -> Priority {
@@ -6,22 +6,24 @@ use crate::{
     ServiceId, SubjectId, TransferId, Transport,
 };
 use alloc::vec::Vec;
+use core::{cell::Cell, time::Duration};
 
 pub struct TestTransport {
-    pub transfer: TestTransferId,
+    pub transfer: Cell<TestTransferId>,
 }
 
 impl TestTransport {
     pub fn new() -> Self {
         TestTransport {
-            transfer: TestTransferId::default(),
+            transfer: Cell::new(TestTransferId::default()),
         }
     }
 
-    fn next_transfer(&mut self) -> TestTransferId {
-        self.transfer = self.transfer.next();
+    fn next_transfer(&self) -> TestTransferId {
+        let transfer = self.transfer.get().next();
+        self.transfer.set(transfer);
 
-        self.transfer
+        transfer
     }
 }
 
@@ -35,7 +37,7 @@ impl Transport for TestTransport {
     /// Maximim Subject ID
     const MAX_NODE_ID: NodeId = 127;
 
-    async fn publish<M>(&mut self, message: &M) -> CyphalResult<()>
+    async fn publish<M>(&self, message: &M) -> CyphalResult<()>
     where
         M: Message,
     {
@@ -44,7 +46,7 @@ impl Transport for TestTransport {
         Ok(())
     }
 
-    async fn invoque<R>(&mut self, request: &R) -> CyphalResult<R::Response>
+    async fn invoque<R>(&self, request: &R, _timeout: Duration) -> CyphalResult<R::Response>
     where
         R: Request,
     {
@@ -53,16 +55,16 @@ impl Transport for TestTransport {
             data.push(i + 1);
         }
 
-        Ok(R::Response::new_raw(
+        R::Response::new_raw(
             request.priority(),
             request.service(),
             request.source(),
             request.destination(),
             &data,
-        )?)
+        )
     }
 
-    async fn serve<R>(&mut self, router: R) -> CyphalResult<()>
+    async fn serve<R>(&self, router: R) -> CyphalResult<()>
     where
         R: Router,
     {
@@ -1,6 +1,10 @@
 use crate::{CyphalResult, Message, NodeId, Request, Router, ServiceId, SubjectId};
+use core::time::Duration;
 
-/// Trait representing the Cyphal transport
+/// Trait representing the Cyphal transport.
+///
+/// All the operations take a shared reference so that one task can serve incoming requests while
+/// other tasks publish messages and invoque services on the same transport.
 pub trait Transport {
     /// Maximim Subject ID
     const MAX_SUBJECT_ID: SubjectId;
@@ -12,17 +16,20 @@ pub trait Transport {
     const MAX_NODE_ID: NodeId;
 
     /// Publishes a message
-    async fn publish<M>(&mut self, message: &M) -> CyphalResult<()>
+    async fn publish<M>(&self, message: &M) -> CyphalResult<()>
     where
         M: Message;
 
-    /// Invoques a service call
-    async fn invoque<R>(&mut self, request: &R) -> CyphalResult<R::Response>
+    /// Invoques a service call and waits for the response.
+    ///
+    /// Returns `CyphalError::Timeout` if no response is received within the timeout.  Dropping the
+    /// returned future cancels the call.
+    async fn invoque<R>(&self, request: &R, timeout: Duration) -> CyphalResult<R::Response>
     where
         R: Request;
 
     /// Listen to incoming traffic
-    async fn serve<R>(&mut self, router: R) -> CyphalResult<()>
+    async fn serve<R>(&self, router: R) -> CyphalResult<()>
     where
         R: Router;
 }
@@ -36,6 +43,7 @@ mod test {
         },
         Priority, Transport,
     };
+    use core::time::Duration;
 
     #[async_std::test]
     async fn test_publish() {
@@ -47,7 +55,7 @@ mod test {
         )
         .unwrap();
 
-        let mut transport = TestTransport::new();
+        let transport = TestTransport::new();
         let result = transport.publish(&message).await;
 
         assert!(result.is_ok())
@@ -64,8 +72,8 @@ mod test {
         )
         .unwrap();
 
-        let mut transport = TestTransport::new();
-        let result = transport.invoque(&request).await;
+        let transport = TestTransport::new();
+        let result = transport.invoque(&request, Duration::from_secs(1)).await;
 
         assert!(result.is_ok())
     }
@@ -74,7 +82,7 @@ mod test {
     async fn tesr_router() {
         let router = TestRouter {};
 
-        let mut transport = TestTransport::new();
+        let transport = TestTransport::new();
         let result = transport.serve(router).await;
 
         assert!(result.is_ok())
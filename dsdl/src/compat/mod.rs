@@ -0,0 +1,114 @@
+use crate::report::parse_error;
+use clap::{
+    error::{Error, ErrorKind, Result as ClapResult},
+    Args,
+};
+use cyphal_dsdl::{compare_minor_versions, Compatibility, Parser};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Args)]
+pub struct Compat {
+    /// The old version of the DSDL file, or a root namespace directory whose minor versions are
+    /// compared
+    #[arg()]
+    old: PathBuf,
+
+    /// The new version of the DSDL file
+    #[arg()]
+    new: Option<PathBuf>,
+
+    /// A root namespace directory holding the types referenced by the DSDL files
+    #[arg(short, long)]
+    root: Vec<PathBuf>,
+}
+
+impl Compat {
+    pub fn execute(&self) -> ClapResult<()> {
+        let mut parser = match Parser::new() {
+            Ok(p) => p,
+            Err(e) => {
+                return Err(Error::raw(
+                    ErrorKind::Io,
+                    format!("Could not create parser: {}", e),
+                ))
+            }
+        };
+        for root in self.root.iter() {
+            parser.add_root(root).map_err(|e| parse_error(&e))?;
+        }
+
+        let comparisons = match &self.new {
+            Some(new) => {
+                let old = parse(&mut parser, &self.old)?;
+                let new = parse(&mut parser, new)?;
+                let find = |path: &str| parser.files().find(|f| f.path() == path);
+                match (find(&old), find(&new)) {
+                    (Some(old), Some(new)) => vec![Compatibility::new(old, new)],
+                    _ => {
+                        return Err(Error::raw(
+                            ErrorKind::Io,
+                            "Could not find the parsed DSDL files",
+                        ))
+                    }
+                }
+            }
+            None if self.old.is_dir() => {
+                parser.add_root(&self.old).map_err(|e| parse_error(&e))?;
+                parser.parse_roots().map_err(|e| parse_error(&e))?;
+                compare_minor_versions(parser.files())
+            }
+            None => {
+                return Err(Error::raw(
+                    ErrorKind::MissingRequiredArgument,
+                    "A new version is required to compare a DSDL file, unlike a root namespace directory",
+                ))
+            }
+        };
+
+        let mut breaking = Vec::new();
+        for comparison in comparisons.iter() {
+            let (old, new) = (name(comparison, false), name(comparison, true));
+            println!("{} -> {}", old, new);
+            if comparison.changes().is_empty() {
+                println!("  no changes to the serialized data");
+            }
+            for change in comparison.changes() {
+                let label = match change.is_breaking() {
+                    true => "breaking",
+                    false => "compatible",
+                };
+                println!("  {}: {}", label, change);
+            }
+
+            if comparison.is_breaking_minor_bump() {
+                breaking.push(format!(
+                    "`{}` is a minor version that is not backward compatible with `{}`",
+                    new, old
+                ));
+            }
+        }
+
+        match breaking.is_empty() {
+            true => Ok(()),
+            false => Err(Error::raw(ErrorKind::InvalidValue, breaking.join("\n"))),
+        }
+    }
+}
+
+// Parses a DSDL file and returns its path, as the parser can only lend one file at a time
+fn parse(parser: &mut Parser, path: &Path) -> ClapResult<String> {
+    match parser.parse_dsdl(path) {
+        Ok(file) => Ok(file.path().to_string()),
+        Err(e) => Err(parse_error(&e)),
+    }
+}
+
+// The name of the old or new version of the compared type, e.g. `uavcan.node.Heartbeat.1.0`
+fn name(comparison: &Compatibility, new: bool) -> String {
+    let (name, (major, minor)) = match new {
+        true => (comparison.new_name(), comparison.new_version()),
+        false => (comparison.old_name(), comparison.old_version()),
+    };
+
+    format!("{}.{}.{}", name, major, minor)
+}
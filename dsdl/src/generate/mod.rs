@@ -1,16 +1,15 @@
-pub mod message;
-
+use crate::report::{locate_codegen, parse_error, render};
 use clap::{
     error::{Error, ErrorKind, Result as ClapResult},
     Args,
 };
 use cyphal_dsdl::Parser;
-use message::MessageGenerator;
-use std::path::PathBuf;
+use cyphal_dsdl_codegen::{module_name, CodegenError, NamespaceGenerator, TypeGenerator};
+use std::{fs, path::PathBuf};
 
 #[derive(Debug, Clone, Args)]
 pub struct Generate {
-    /// The DSDL file used to generate code
+    /// The DSDL file or root namespace directory used to generate code
     #[arg()]
     path: PathBuf,
 
@@ -21,10 +20,18 @@ pub struct Generate {
     /// Manually set the generated struct's name
     #[arg(short, long)]
     name: Option<String>,
+
+    /// A root namespace directory holding the types referenced by the DSDL files
+    #[arg(short, long)]
+    root: Vec<PathBuf>,
 }
 
 impl Generate {
     pub fn execute(&self) -> ClapResult<()> {
+        if self.path.is_dir() {
+            return self.execute_namespace();
+        }
+
         let mut parser = match Parser::new() {
             Ok(p) => p,
             Err(e) => {
@@ -34,26 +41,84 @@ impl Generate {
                 ))
             }
         };
+        for root in self.root.iter() {
+            parser.add_root(root).map_err(|e| parse_error(&e))?;
+        }
 
         let file = match parser.parse_dsdl(&self.path) {
             Ok(s) => s,
-            Err(e) => {
-                return Err(Error::raw(
-                    ErrorKind::Io,
-                    format!("Could not parse DSDL file: {}", e),
-                ))
-            }
+            Err(e) => return Err(parse_error(&e)),
         };
+        for diagnostic in file.diagnostics() {
+            eprintln!("{}", diagnostic);
+        }
 
-        let generator = MessageGenerator::new(self.name.clone())?;
-        let code = generator.generate_code();
+        let generator =
+            TypeGenerator::new(file, self.name.clone(), Vec::new()).map_err(codegen_error)?;
+        let code = generator.generate_code().map_err(codegen_error)?;
 
-        match self.output {
-            Some(_) => Err(Error::raw(ErrorKind::Io, "Not implemented")),
+        match &self.output {
+            Some(output) => {
+                let module = module_name(file.name(), file.major(), file.minor());
+                match fs::create_dir_all(output)
+                    .and_then(|_| fs::write(output.join(format!("{}.rs", module)), code))
+                {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(Error::raw(
+                        ErrorKind::Io,
+                        format!("Could not write generated code: {}", e),
+                    )),
+                }
+            }
             None => {
                 println!("{}", code);
                 Ok(())
             }
         }
     }
+
+    fn execute_namespace(&self) -> ClapResult<()> {
+        let output = match &self.output {
+            Some(o) => o,
+            None => {
+                return Err(Error::raw(
+                    ErrorKind::MissingRequiredArgument,
+                    "An output directory is required to generate a namespace",
+                ))
+            }
+        };
+
+        if self.name.is_some() {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "The name can only be set when generating a single DSDL file",
+            ));
+        }
+
+        // the types of the other roots are generated too, so that the references to them resolve
+        let mut roots = vec![self.path.clone()];
+        roots.extend(self.root.iter().cloned());
+
+        let generator = NamespaceGenerator::new(roots).map_err(codegen_error)?;
+        let result = generator.generate(output);
+        for diagnostic in generator.diagnostics() {
+            eprintln!("{}", diagnostic);
+        }
+        result.map_err(codegen_error)?;
+
+        Ok(())
+    }
+}
+
+fn codegen_error(error: CodegenError) -> Error {
+    let kind = match error {
+        CodegenError::Io(_) => ErrorKind::Io,
+        _ => ErrorKind::InvalidValue,
+    };
+    let message = match locate_codegen(&error) {
+        Some((message, span)) => render(&message, span),
+        None => error.to_string(),
+    };
+
+    Error::raw(kind, format!("Could not generate code: {}", message))
 }
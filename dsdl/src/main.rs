@@ -1,19 +1,30 @@
 #![doc = include_str!("../README.md")]
 #![forbid(missing_docs)]
 
+mod compat;
+use compat::Compat;
+
 mod generate;
 use generate::Generate;
 
-use clap::{error::Result as ClapResult, Parser, Subcommand};
+mod report;
+
+use clap::{Parser, Subcommand};
+use std::process;
 
-fn main() -> ClapResult<()> {
+fn main() {
     let cli = DsdlCli::parse();
 
-    match cli.command {
-        Commands::Generate(a) => a.execute()?,
-    }
+    let result = match cli.command {
+        Commands::Generate(a) => a.execute(),
+        Commands::Compat(a) => a.execute(),
+    };
 
-    Ok(())
+    // errors are printed like usage errors, rather than debug formatted
+    if let Err(e) = result {
+        eprintln!("{}", e.to_string().trim_end());
+        process::exit(e.exit_code());
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -33,8 +44,15 @@ enum Commands {
     /// Clones repos
     #[command(arg_required_else_help = true)]
     #[command(
-        about = "Generate Rust code from a DSDL file",
-        long_about = "Generates Rust code from an OpenCyphal Data Structure Description Language (DSDL) file"
+        about = "Generate Rust code from a DSDL file or namespace",
+        long_about = "Generates Rust code from an OpenCyphal Data Structure Description Language (DSDL) file or from a root namespace directory"
     )]
     Generate(Generate),
+
+    #[command(arg_required_else_help = true)]
+    #[command(
+        about = "Check the compatibility of two versions of a DSDL type",
+        long_about = "Compares two versions of an OpenCyphal Data Structure Description Language (DSDL) type, or the minor versions of every type of a root namespace directory, and fails if a minor version breaks compatibility"
+    )]
+    Compat(Compat),
 }
@@ -0,0 +1,70 @@
+use clap::error::{Error, ErrorKind};
+use cyphal_dsdl::{DsdlError, Span};
+use cyphal_dsdl_codegen::CodegenError;
+
+// Turns a parse error into a CLI error, rendered with its source when it is known
+pub fn parse_error(error: &DsdlError) -> Error {
+    let (kind, message) = match locate_dsdl(error) {
+        Some((message, span)) => (ErrorKind::InvalidValue, render(&message, span)),
+        None => (ErrorKind::Io, error.to_string()),
+    };
+
+    Error::raw(kind, format!("Could not parse DSDL file: {}", message))
+}
+
+// Returns the message of an error and where it occurred, if it is known
+pub fn locate_dsdl(error: &DsdlError) -> Option<(String, &Span)> {
+    match error {
+        DsdlError::InvalidStatement(span, message) => Some((message.clone(), span)),
+        DsdlError::UnresolvedType(name, span) => {
+            Some((format!("Unresolved type `{}`", name), span))
+        }
+        DsdlError::InFile(_, error) => locate_dsdl(error),
+        _ => None,
+    }
+}
+
+// Returns the message of a code generation error and where it occurred, if it is known
+pub fn locate_codegen(error: &CodegenError) -> Option<(String, &Span)> {
+    match error {
+        CodegenError::Dsdl(_, error) => locate_dsdl(error),
+        CodegenError::UnresolvedReference(name, span) => {
+            Some((format!("Unresolved reference to `{}`", name), span))
+        }
+        _ => None,
+    }
+}
+
+// Renders a message followed by the source line it refers to, with carets under the offending
+// text like a compiler does:
+//
+// Unresolved type `uavcan.node.Health.1.1`
+//  --> uavcan/node/7509.Heartbeat.1.0.dsdl:2:1
+//   |
+// 2 | Health.1.1 health
+//   | ^^^^^^^^^^
+pub fn render(message: &str, span: &Span) -> String {
+    let line = span.line().to_string();
+    let gutter = " ".repeat(line.len());
+    // tabs are kept so that the carets line up with the source
+    let indent: String = span
+        .source()
+        .chars()
+        .take(span.column() as usize - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = "^".repeat(span.length().max(1) as usize);
+
+    format!(
+        "{}\n{}--> {}\n{} |\n{} | {}\n{} | {}{}",
+        message,
+        gutter,
+        span,
+        gutter,
+        line,
+        span.source(),
+        gutter,
+        indent,
+        carets
+    )
+}
@@ -1,21 +1,125 @@
-use cyphal::{CyphalResult, Message, NodeId, Priority, SubjectId};
+use cyphal::{CyphalError, CyphalResult, Message, NodeId, Priority, SubjectId};
+use cyphal_dsdl_runtime::{BitReader, BitWriter, CastMode, DataType, RuntimeResult};
 
-const HEARTBEAT_MESSAGE_SIZE: usize = 65;
 const HEARTBEAT_PORT_ID: SubjectId = 7509;
 
+/// uavcan.node.Health.1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    pub value: u8,
+}
+
+impl Health {
+    pub const NOMINAL: u8 = 0;
+}
+
+impl DataType for Health {
+    const SEALED: bool = true;
+    const EXTENT: usize = 1;
+
+    fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+        writer.write_uint(self.value as u64, 2, CastMode::Saturated)
+    }
+
+    fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+        Ok(Self {
+            value: reader.read_uint(2) as u8,
+        })
+    }
+}
+
+/// uavcan.node.Mode.1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mode {
+    pub value: u8,
+}
+
+impl Mode {
+    pub const OPERATIONAL: u8 = 0;
+}
+
+impl DataType for Mode {
+    const SEALED: bool = true;
+    const EXTENT: usize = 1;
+
+    fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+        writer.write_uint(self.value as u64, 3, CastMode::Saturated)
+    }
+
+    fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+        Ok(Self {
+            value: reader.read_uint(3) as u8,
+        })
+    }
+}
+
+/// uavcan.node.Heartbeat.1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Heartbeat {
+    pub uptime: u32,
+    pub health: Health,
+    pub mode: Mode,
+    pub vendor_specific_status_code: u8,
+}
+
+impl DataType for Heartbeat {
+    const SEALED: bool = false;
+    const EXTENT: usize = 12;
+
+    fn serialize(&self, writer: &mut BitWriter) -> RuntimeResult<()> {
+        writer.write_uint(self.uptime as u64, 32, CastMode::Saturated)?;
+        writer.write_composite(&self.health)?;
+        writer.write_composite(&self.mode)?;
+        writer.write_uint(
+            self.vendor_specific_status_code as u64,
+            8,
+            CastMode::Saturated,
+        )
+    }
+
+    fn deserialize(reader: &mut BitReader) -> RuntimeResult<Self> {
+        Ok(Self {
+            uptime: reader.read_uint(32) as u32,
+            health: reader.read_composite()?,
+            mode: reader.read_composite()?,
+            vendor_specific_status_code: reader.read_uint(8) as u8,
+        })
+    }
+}
+
 pub struct HeartbeatMessage {
     source: NodeId,
-    payload: [u8; HEARTBEAT_MESSAGE_SIZE],
+    payload: [u8; Heartbeat::EXTENT],
+    size: usize,
 }
 
 impl HeartbeatMessage {
-    pub fn new(source: NodeId, payload: [u8; HEARTBEAT_MESSAGE_SIZE]) -> CyphalResult<Self> {
-        Ok(Self { source, payload })
+    pub fn new(source: NodeId, heartbeat: Heartbeat) -> CyphalResult<Self> {
+        let mut payload = [0; Heartbeat::EXTENT];
+        let size = heartbeat.serialize_to(&mut payload)?;
+
+        Ok(Self {
+            source,
+            payload,
+            size,
+        })
     }
 }
 
 impl Message for HeartbeatMessage {
-    const SIZE: usize = HEARTBEAT_MESSAGE_SIZE;
+    const SIZE: usize = Heartbeat::EXTENT;
+
+    fn new_raw(
+        _priority: Priority,
+        _subject: SubjectId,
+        source: Option<NodeId>,
+        data: &[u8],
+    ) -> CyphalResult<Self> {
+        let source = source.ok_or(CyphalError::OutOfRange)?;
+        let heartbeat = Heartbeat::deserialize_from(data)?;
+
+        Self::new(source, heartbeat)
+    }
 
     fn source(&self) -> Option<NodeId> {
         Some(self.source)
@@ -30,6 +134,6 @@ impl Message for HeartbeatMessage {
     }
 
     fn data(&self) -> &[u8] {
-        &self.payload
+        &self.payload[..self.size]
     }
 }
@@ -2,23 +2,24 @@ mod get_info;
 mod heartbeat;
 mod router;
 
-use async_std::sync::Mutex;
-use cyphal::Transport;
-use cyphal_can::CanTransport;
-use cyphal_socketcan::CanFdSocket;
-use heartbeat::HeartbeatMessage;
+use cyphal::{CyphalResult, Transport};
+use cyphal_can::{CanTransport, FD_PAYLOAD_SIZE};
+use cyphal_socketcan::{CanFdSocket, SystemClock};
+use heartbeat::{Health, Heartbeat, HeartbeatMessage, Mode};
 use router::Router;
 use std::{sync::Arc, time::Duration};
 
+type SocketTransport = CanTransport<FD_PAYLOAD_SIZE, CanFdSocket, SystemClock>;
+
 #[async_std::main]
 async fn main() {
     let socket = CanFdSocket::new("vcan1").unwrap();
-    let transport = Arc::new(Mutex::new(CanTransport::new(socket).unwrap()));
+    let transport = Arc::new(CanTransport::new(socket, SystemClock::new()).unwrap());
     let router = Router {};
 
     async_std::task::spawn(send_heartbeat(transport.clone()));
 
-    if let Err(e) = transport.lock().await.serve(router).await {
+    if let Err(e) = transport.serve(router).await {
         print!(
             "An error occured while trying to serve incoming requests: {}",
             e
@@ -26,10 +27,22 @@ async fn main() {
     };
 }
 
-async fn send_heartbeat<T: Transport>(transport: Arc<Mutex<T>>) {
+async fn send_heartbeat(transport: Arc<SocketTransport>) -> CyphalResult<()> {
+    let mut uptime = 0;
     loop {
-        let message = HeartbeatMessage::new(1, [0; 65]).unwrap();
-        let _r = &transport.lock().await.publish(&message).await;
+        let heartbeat = Heartbeat {
+            uptime,
+            health: Health {
+                value: Health::NOMINAL,
+            },
+            mode: Mode {
+                value: Mode::OPERATIONAL,
+            },
+            vendor_specific_status_code: 0,
+        };
+        let message = HeartbeatMessage::new(1, heartbeat)?;
+        let _r = transport.publish(&message).await;
         async_std::task::sleep(Duration::from_secs(1)).await;
+        uptime += 1;
     }
 }